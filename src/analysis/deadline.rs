@@ -0,0 +1,31 @@
+//! A shared wall-clock deadline for the global `--timeout` flag, checked
+//! between loop iterations by walker-driven commands so a scan over a large
+//! tree yields partial results instead of running unbounded.
+
+use std::time::{Duration, Instant};
+
+/// `None` means no timeout was configured; [`Deadline::is_expired`] then
+/// always returns `false` and callers behave exactly as before `--timeout`
+/// existed.
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    expires_at: Option<Instant>,
+}
+
+impl Deadline {
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            expires_at: timeout.map(|d| Instant::now() + d),
+        }
+    }
+
+    /// A deadline that never expires, for call sites without a configured
+    /// timeout (e.g. library-style helpers called outside `run_with_cli`).
+    pub fn none() -> Self {
+        Self { expires_at: None }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|t| Instant::now() >= t)
+    }
+}
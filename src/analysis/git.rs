@@ -1,14 +1,30 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Local, TimeZone, Utc};
-use git2::{DiffOptions, Repository, StatusOptions};
+use git2::{Delta, DiffOptions, ObjectType, Oid, Repository, StatusOptions, TreeWalkMode, TreeWalkResult};
+use rayon::prelude::*;
+use schemars::JsonSchema;
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
 
+use super::walker;
+
+/// Converts a raw commit timestamp (seconds since epoch, as stored by git)
+/// into a UTC datetime, falling back to the Unix epoch for a timestamp
+/// `chrono` can't represent. Git doesn't validate author/committer dates,
+/// so a corrupted or adversarially-crafted commit (e.g. via `git commit
+/// --date`) can carry an out-of-range value; degrading to the epoch beats
+/// panicking on it.
+fn commit_datetime(time: i64) -> DateTime<Utc> {
+    Utc.timestamp_opt(time, 0).single().unwrap_or_else(|| Utc.timestamp_opt(0, 0).single().unwrap())
+}
+
 /// Represents the current status of a git repository's working tree.
 ///
 /// This struct provides a snapshot of the repository state, including
-/// branch information and categorized lists of changed files.
+/// branch information and categorized lists of changed files - the same
+/// picture a shell prompt needs (ahead/behind, stashed, conflicted), not
+/// just "dirty/not dirty".
 ///
 /// # Fields
 /// * `branch` - Current branch name (or "HEAD" if detached)
@@ -16,18 +32,49 @@ use std::path::Path;
 /// * `staged_files` - Files added to the index (ready to commit)
 /// * `modified_files` - Files modified in the working directory
 /// * `untracked_files` - Files not yet tracked by git
+/// * `renamed_files` - Files renamed between HEAD and the index
+/// * `deleted_files` - Files staged for deletion
+/// * `conflicted_files` - Files with unresolved merge conflicts
+/// * `ahead` - Commits the current branch has that its upstream doesn't
+/// * `behind` - Commits the upstream has that the current branch doesn't
+/// * `stash_count` - Number of stash entries in the repository
 #[derive(Debug, Serialize)]
 pub struct GitStatus {
     /// The name of the current branch, or "HEAD" if in detached HEAD state.
     pub branch: String,
-    /// True if there are staged or modified files (uncommitted changes).
+    /// True if there are staged, modified, renamed, or deleted files (uncommitted changes).
     pub is_dirty: bool,
-    /// Paths of files staged in the index (new, modified, or deleted).
+    /// Paths of files staged in the index (new or modified; renames and
+    /// deletions are split out into `renamed_files`/`deleted_files`).
     pub staged_files: Vec<String>,
     /// Paths of files modified in the working directory but not yet staged.
     pub modified_files: Vec<String>,
     /// Paths of files not tracked by git.
     pub untracked_files: Vec<String>,
+    /// Files renamed between HEAD and the index, detected via
+    /// `StatusOptions::renames_head_to_index`.
+    pub renamed_files: Vec<RenamedFile>,
+    /// Paths of files staged for deletion.
+    pub deleted_files: Vec<String>,
+    /// Paths of files with unresolved merge conflicts.
+    pub conflicted_files: Vec<String>,
+    /// Number of commits the current branch is ahead of its upstream, or 0
+    /// if there is no upstream.
+    pub ahead: usize,
+    /// Number of commits the current branch is behind its upstream, or 0
+    /// if there is no upstream.
+    pub behind: usize,
+    /// Number of stash entries in the repository.
+    pub stash_count: usize,
+}
+
+/// A file renamed between HEAD and the index (a staged rename).
+#[derive(Debug, Serialize)]
+pub struct RenamedFile {
+    /// The path before the rename.
+    pub from: String,
+    /// The path after the rename.
+    pub to: String,
 }
 
 /// Represents a single commit from the repository history.
@@ -41,7 +88,7 @@ pub struct GitStatus {
 /// * `author` - Name of the commit author
 /// * `time` - Formatted timestamp (YYYY-MM-DD HH:MM)
 /// * `time_ago` - Human-readable relative time (e.g., "2h ago")
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct RecentCommit {
     /// Abbreviated commit SHA (first 7 characters).
     pub sha: String,
@@ -86,7 +133,7 @@ pub struct FileActivity {
 /// # Fields
 /// * `path` - Directory path relative to repository root
 /// * `commit_count` - Total number of file changes in this directory
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct HotDirectory {
     /// Directory path relative to the repository root.
     /// Root-level files are represented as ".".
@@ -116,27 +163,110 @@ pub fn find_repo(path: &Path) -> Result<Repository> {
     Repository::discover(path).context("Not a git repository")
 }
 
+/// A submodule's path and the commit its parent repository currently
+/// points at.
+#[derive(Debug, Serialize)]
+pub struct SubmoduleInfo {
+    /// Submodule path relative to the repository root.
+    pub path: String,
+    /// The submodule's currently checked-out commit SHA, if resolvable.
+    pub head_sha: Option<String>,
+}
+
+/// Describes the repository layout `find_repo` opened: whether it's bare,
+/// a linked worktree, in detached HEAD, and where its toplevel/prefix and
+/// submodules are.
+///
+/// Mirrors the information `git rev-parse --is-bare-repository
+/// --is-inside-worktree --show-toplevel --show-prefix` exposes, so
+/// downstream formatting can adapt correctly in unusual layouts instead
+/// of assuming a normal single working tree.
+#[derive(Debug, Serialize)]
+pub struct RepoContext {
+    /// True if this is a bare repository (no working directory).
+    pub is_bare: bool,
+    /// True if this repository is a linked worktree of another repository.
+    pub is_worktree: bool,
+    /// True if HEAD points directly at a commit rather than a branch.
+    pub is_detached_head: bool,
+    /// The repository's working directory, if it has one.
+    pub toplevel: Option<String>,
+    /// The current directory's path relative to `toplevel`, if both are known.
+    pub prefix: Option<String>,
+    /// Submodules registered in this repository.
+    pub submodules: Vec<SubmoduleInfo>,
+}
+
+/// Probes a repository for the layout details [`RepoContext`] describes.
+///
+/// # Arguments
+/// * `repo` - Reference to an open git repository
+///
+/// # Returns
+/// A [`RepoContext`] describing whether the repo is bare, a linked
+/// worktree, or in detached HEAD, along with its toplevel/prefix paths
+/// and submodules.
+pub fn get_repo_context(repo: &Repository) -> Result<RepoContext> {
+    let is_bare = repo.is_bare();
+    let is_worktree = repo.is_worktree();
+    let is_detached_head = repo.head_detached().unwrap_or(false);
+
+    let toplevel = repo.workdir().map(|p| p.to_string_lossy().to_string());
+
+    let prefix = toplevel.as_ref().and_then(|top| {
+        let cwd = std::env::current_dir().ok()?;
+        cwd.strip_prefix(Path::new(top)).ok().map(|p| p.to_string_lossy().to_string())
+    });
+
+    let submodules = repo
+        .submodules()
+        .ok()
+        .map(|subs| {
+            subs.iter()
+                .map(|sub| SubmoduleInfo {
+                    path: sub.path().to_string_lossy().to_string(),
+                    head_sha: sub.head_id().map(|oid| oid.to_string()),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(RepoContext {
+        is_bare,
+        is_worktree,
+        is_detached_head,
+        toplevel,
+        prefix,
+        submodules,
+    })
+}
+
 /// Retrieves the current status of the repository's working tree.
 ///
-/// Collects information about the current branch and categorizes all
-/// changed files into staged, modified, and untracked groups.
+/// Collects information about the current branch, categorizes all changed
+/// files (staged, modified, untracked, renamed, deleted, conflicted), and
+/// reports upstream divergence and stash count.
+///
+/// Takes `&mut Repository` because stash enumeration
+/// ([`Repository::stash_foreach`]) requires mutable access.
 ///
 /// # Arguments
 /// * `repo` - Reference to an open git repository
 ///
 /// # Returns
-/// A [`GitStatus`] struct containing branch name and categorized file lists.
+/// A [`GitStatus`] struct containing branch name, categorized file lists,
+/// ahead/behind counts, and stash count.
 ///
 /// # Example
 /// ```ignore
-/// let repo = find_repo(Path::new("."))?;
-/// let status = get_status(&repo)?;
+/// let mut repo = find_repo(Path::new("."))?;
+/// let status = get_status(&mut repo)?;
 /// println!("On branch: {}", status.branch);
 /// if status.is_dirty {
 ///     println!("Working directory has changes");
 /// }
 /// ```
-pub fn get_status(repo: &Repository) -> Result<GitStatus> {
+pub fn get_status(repo: &mut Repository) -> Result<GitStatus> {
     let head = repo.head().ok();
     let branch = head
         .as_ref()
@@ -147,20 +277,42 @@ pub fn get_status(repo: &Repository) -> Result<GitStatus> {
     let mut opts = StatusOptions::new();
     opts.include_untracked(true);
     opts.recurse_untracked_dirs(true);
+    opts.renames_head_to_index(true);
 
     let statuses = repo.statuses(Some(&mut opts))?;
 
     let mut staged_files = Vec::new();
     let mut modified_files = Vec::new();
     let mut untracked_files = Vec::new();
+    let mut renamed_files = Vec::new();
+    let mut deleted_files = Vec::new();
+    let mut conflicted_files = Vec::new();
 
     for entry in statuses.iter() {
         let path = entry.path().unwrap_or("").to_string();
         let status = entry.status();
 
-        if status.is_index_new() || status.is_index_modified() || status.is_index_deleted() {
+        if status.is_conflicted() {
+            conflicted_files.push(path);
+            continue;
+        }
+
+        if let Some(rename) = entry.head_to_index().filter(|delta| delta.status() == Delta::Renamed) {
+            let from = rename
+                .old_file()
+                .path()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            renamed_files.push(RenamedFile { from, to: path });
+            continue;
+        }
+
+        if status.is_index_deleted() {
+            deleted_files.push(path.clone());
+        } else if status.is_index_new() || status.is_index_modified() {
             staged_files.push(path.clone());
         }
+
         if status.is_wt_modified() || status.is_wt_deleted() {
             modified_files.push(path.clone());
         }
@@ -169,7 +321,13 @@ pub fn get_status(repo: &Repository) -> Result<GitStatus> {
         }
     }
 
-    let is_dirty = !staged_files.is_empty() || !modified_files.is_empty();
+    let is_dirty = !staged_files.is_empty()
+        || !modified_files.is_empty()
+        || !renamed_files.is_empty()
+        || !deleted_files.is_empty();
+
+    let (ahead, behind) = get_ahead_behind(repo).unwrap_or((0, 0));
+    let stash_count = count_stashes(repo);
 
     Ok(GitStatus {
         branch,
@@ -177,16 +335,186 @@ pub fn get_status(repo: &Repository) -> Result<GitStatus> {
         staged_files,
         modified_files,
         untracked_files,
+        renamed_files,
+        deleted_files,
+        conflicted_files,
+        ahead,
+        behind,
+        stash_count,
     })
 }
 
-/// Retrieves the most recent commits from the repository history.
+/// Resolves how far the current branch has diverged from its upstream, via
+/// `branch_upstream_name` + `graph_ahead_behind`. Returns `None` if HEAD
+/// has no upstream configured; [`get_status`] reports that as `(0, 0)`.
+fn get_ahead_behind(repo: &Repository) -> Option<(usize, usize)> {
+    let head = repo.head().ok()?;
+    let local_oid = head.target()?;
+    let ref_name = head.name()?;
+
+    let upstream_name = repo.branch_upstream_name(ref_name).ok()?;
+    let upstream_name = upstream_name.as_str()?;
+    let upstream_oid = repo.find_reference(upstream_name).ok()?.target()?;
+
+    repo.graph_ahead_behind(local_oid, upstream_oid).ok()
+}
+
+/// Counts stash entries via `Repository::stash_foreach`.
+fn count_stashes(repo: &mut Repository) -> usize {
+    let mut count = 0;
+    let _ = repo.stash_foreach(|_, _, _| {
+        count += 1;
+        true
+    });
+    count
+}
+
+/// A single commit's metadata plus the set of paths it touched, computed
+/// once by diffing it against its first parent.
+#[derive(Debug, Clone)]
+pub struct CommitNode {
+    /// The commit's full OID.
+    pub oid: Oid,
+    /// Name of the commit author.
+    pub author: String,
+    /// Commit timestamp (seconds since epoch).
+    pub time: i64,
+    /// First line of the commit message.
+    pub message: String,
+    /// Paths touched by this commit, relative to the repository root.
+    pub files: Vec<String>,
+}
+
+/// Default number of commits scanned when building a [`CommitGraph`] for
+/// history-wide queries (recent commits, file activity, hot directories,
+/// co-change), matching the window [`get_files_changed_with`] has always used.
+pub const DEFAULT_HISTORY_POOL_SIZE: usize = 500;
+
+/// A single cached walk of repository history: each commit's metadata and
+/// touched-file set, diffed against its parent exactly once, plus an
+/// inverted `file -> [commits touching it]` index.
 ///
-/// Walks the commit history starting from HEAD and collects metadata
-/// about each commit up to the specified count.
+/// [`get_recent_commits`], [`get_recent_file_activity`],
+/// [`get_hot_directories`], and [`get_files_changed_with`] each used to
+/// re-walk the revwalk and re-diff every commit against its parent
+/// independently. Building one `CommitGraph` up front and querying it from
+/// all four means that work happens exactly once per `ctx` invocation.
+pub struct CommitGraph {
+    commits: BTreeMap<Oid, CommitNode>,
+    /// Commit OIDs in walk order (newest first), since `commits`'s OID keys
+    /// don't preserve history order.
+    order: Vec<Oid>,
+    file_refs: BTreeMap<String, Vec<Oid>>,
+}
+
+impl CommitGraph {
+    /// Walks up to `limit` commits from HEAD, diffing each against its
+    /// first parent and recording its metadata and touched paths.
+    ///
+    /// The revwalk itself is sequential (`git2` iterators aren't `Send`),
+    /// but once the commit OIDs are collected, the per-commit diffs - the
+    /// expensive part on repos with deep history - are computed concurrently
+    /// via rayon, each worker opening its own `Repository` handle since
+    /// `git2` objects can't cross threads.
+    ///
+    /// # Arguments
+    /// * `repo` - Reference to an open git repository
+    /// * `limit` - Maximum number of commits to walk
+    pub fn build(repo: &Repository, limit: usize) -> Result<CommitGraph> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push_head()?;
+
+        let oids = revwalk
+            .take(limit)
+            .collect::<std::result::Result<Vec<Oid>, _>>()?;
+
+        let repo_path = repo.path().to_path_buf();
+
+        let nodes = oids
+            .par_iter()
+            .map(|&oid| -> Result<CommitNode> {
+                let repo = Repository::open(&repo_path)?;
+                let commit = repo.find_commit(oid)?;
+
+                let tree = commit.tree()?;
+                let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+
+                let mut diff_opts = DiffOptions::new();
+                let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))?;
+
+                let mut files = Vec::new();
+                diff.foreach(
+                    &mut |delta, _| {
+                        if let Some(path) = delta.new_file().path() {
+                            files.push(path.to_string_lossy().to_string());
+                        }
+                        true
+                    },
+                    None,
+                    None,
+                    None,
+                )?;
+
+                let message = commit
+                    .message()
+                    .unwrap_or("")
+                    .lines()
+                    .next()
+                    .unwrap_or("")
+                    .to_string();
+                let author = commit.author().name().unwrap_or("unknown").to_string();
+                let time = commit.time().seconds();
+
+                Ok(CommitNode {
+                    oid,
+                    author,
+                    time,
+                    message,
+                    files,
+                })
+            })
+            .collect::<Result<Vec<CommitNode>>>()?;
+
+        let mut commits = BTreeMap::new();
+        let mut order = Vec::with_capacity(nodes.len());
+        let mut file_refs: BTreeMap<String, Vec<Oid>> = BTreeMap::new();
+
+        for node in nodes {
+            order.push(node.oid);
+            for f in &node.files {
+                file_refs.entry(f.clone()).or_default().push(node.oid);
+            }
+            commits.insert(node.oid, node);
+        }
+
+        Ok(CommitGraph {
+            commits,
+            order,
+            file_refs,
+        })
+    }
+
+    /// Commits in walk order (newest first).
+    pub fn commits_in_order(&self) -> impl Iterator<Item = &CommitNode> {
+        self.order.iter().filter_map(move |oid| self.commits.get(oid))
+    }
+
+    /// Looks up a single commit's cached metadata by OID.
+    pub fn get(&self, oid: &Oid) -> Option<&CommitNode> {
+        self.commits.get(oid)
+    }
+
+    /// The inverted `file path -> commits touching it` index, in walk order
+    /// (newest first) per file.
+    pub fn file_refs(&self) -> &BTreeMap<String, Vec<Oid>> {
+        &self.file_refs
+    }
+}
+
+/// Retrieves the most recent commits from the repository history.
 ///
 /// # Arguments
-/// * `repo` - Reference to an open git repository
+/// * `graph` - A [`CommitGraph`] built from the repository
 /// * `count` - Maximum number of commits to retrieve
 ///
 /// # Returns
@@ -194,54 +522,38 @@ pub fn get_status(repo: &Repository) -> Result<GitStatus> {
 ///
 /// # Example
 /// ```ignore
-/// let commits = get_recent_commits(&repo, 10)?;
+/// let graph = CommitGraph::build(&repo, DEFAULT_HISTORY_POOL_SIZE)?;
+/// let commits = get_recent_commits(&graph, 10);
 /// for commit in commits {
 ///     println!("{} {} - {}", commit.sha, commit.time_ago, commit.message);
 /// }
 /// ```
-pub fn get_recent_commits(repo: &Repository, count: usize) -> Result<Vec<RecentCommit>> {
-    let mut revwalk = repo.revwalk()?;
-    revwalk.push_head()?;
-
-    let mut commits = Vec::new();
+pub fn get_recent_commits(graph: &CommitGraph, count: usize) -> Vec<RecentCommit> {
     let now = Utc::now();
 
-    for oid in revwalk.take(count) {
-        let oid = oid?;
-        let commit = repo.find_commit(oid)?;
-
-        let time = commit.time();
-        let datetime = Utc.timestamp_opt(time.seconds(), 0).unwrap();
-        let local: DateTime<Local> = datetime.into();
-
-        let duration = now.signed_duration_since(datetime);
-        let time_ago = format_duration(duration);
-
-        commits.push(RecentCommit {
-            sha: oid.to_string()[..7].to_string(),
-            message: commit
-                .message()
-                .unwrap_or("")
-                .lines()
-                .next()
-                .unwrap_or("")
-                .to_string(),
-            author: commit.author().name().unwrap_or("unknown").to_string(),
-            time: local.format("%Y-%m-%d %H:%M").to_string(),
-            time_ago,
-        });
-    }
+    graph
+        .commits_in_order()
+        .take(count)
+        .map(|node| {
+            let datetime = commit_datetime(node.time);
+            let local: DateTime<Local> = datetime.into();
+            let duration = now.signed_duration_since(datetime);
 
-    Ok(commits)
+            RecentCommit {
+                sha: node.oid.to_string()[..7].to_string(),
+                message: node.message.clone(),
+                author: node.author.clone(),
+                time: local.format("%Y-%m-%d %H:%M").to_string(),
+                time_ago: format_duration(duration),
+            }
+        })
+        .collect()
 }
 
-/// Analyzes recent commit history to find the most actively modified files.
-///
-/// Scans the last 100 commits and aggregates file modification statistics,
-/// returning the files with the highest commit counts.
+/// Finds the most actively modified files over the commits in `graph`.
 ///
 /// # Arguments
-/// * `repo` - Reference to an open git repository
+/// * `graph` - A [`CommitGraph`] built from the repository
 /// * `count` - Maximum number of files to return
 ///
 /// # Returns
@@ -253,14 +565,129 @@ pub fn get_recent_commits(repo: &Repository, count: usize) -> Result<Vec<RecentC
 /// - Identifying hot spots in the codebase
 /// - Finding files that may need code review attention
 /// - Understanding which files change together frequently
-pub fn get_recent_file_activity(repo: &Repository, count: usize) -> Result<Vec<FileActivity>> {
+pub fn get_recent_file_activity(graph: &CommitGraph, count: usize) -> Vec<FileActivity> {
+    let now = Utc::now();
+
+    let mut activities: Vec<_> = graph
+        .file_refs()
+        .iter()
+        .filter_map(|(path, oids)| {
+            let latest = oids.iter().filter_map(|oid| graph.get(oid)).max_by_key(|c| c.time)?;
+            let datetime = commit_datetime(latest.time);
+            let duration = now.signed_duration_since(datetime);
+
+            Some(FileActivity {
+                path: path.clone(),
+                commit_count: oids.len(),
+                last_modified: format_duration(duration),
+                last_author: latest.author.clone(),
+            })
+        })
+        .collect();
+
+    activities.sort_by(|a, b| b.commit_count.cmp(&a.commit_count));
+    activities.truncate(count);
+
+    activities
+}
+
+/// Identifies directories with the most commit activity within a time window.
+///
+/// # Arguments
+/// * `graph` - A [`CommitGraph`] built from the repository
+/// * `days` - Number of days to look back from now
+///
+/// # Returns
+/// A vector of up to 10 [`HotDirectory`] structs sorted by commit count
+/// (descending). Root-level files are grouped under ".".
+///
+/// # Example
+/// ```ignore
+/// let graph = CommitGraph::build(&repo, DEFAULT_HISTORY_POOL_SIZE)?;
+/// let hot_dirs = get_hot_directories(&graph, 7); // Last week
+/// for dir in hot_dirs {
+///     println!("{}: {} changes", dir.path, dir.commit_count);
+/// }
+/// ```
+pub fn get_hot_directories(graph: &CommitGraph, days: i64) -> Vec<HotDirectory> {
+    let cutoff = Utc::now().timestamp() - (days * 24 * 60 * 60);
+    let mut dir_commits: HashMap<String, usize> = HashMap::new();
+
+    for node in graph.commits_in_order() {
+        if node.time < cutoff {
+            break;
+        }
+
+        for f in &node.files {
+            let dir = match Path::new(f).parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.to_string_lossy().to_string(),
+                _ => ".".to_string(),
+            };
+            *dir_commits.entry(dir).or_insert(0) += 1;
+        }
+    }
+
+    let mut hot_dirs: Vec<_> = dir_commits
+        .into_iter()
+        .map(|(path, commit_count)| HotDirectory { path, commit_count })
+        .collect();
+
+    hot_dirs.sort_by(|a, b| b.commit_count.cmp(&a.commit_count));
+    hot_dirs.truncate(10);
+
+    hot_dirs
+}
+
+/// Gets a summary of uncommitted changes as insertion/deletion counts.
+///
+/// Computes the total number of lines added and removed across all
+/// uncommitted changes (both staged and unstaged) compared to HEAD.
+///
+/// # Arguments
+/// * `repo` - Reference to an open git repository
+///
+/// # Returns
+/// A tuple of `(insertions, deletions)` representing the total line counts.
+///
+/// # Example
+/// ```ignore
+/// let (added, removed) = get_diff_summary(&repo)?;
+/// println!("+{} -{} lines", added, removed);
+/// ```
+pub fn get_diff_summary(repo: &Repository) -> Result<(usize, usize)> {
+    let head = repo.head()?.peel_to_tree()?;
+    let mut diff_opts = DiffOptions::new();
+
+    let diff = repo.diff_tree_to_workdir_with_index(Some(&head), Some(&mut diff_opts))?;
+
+    let stats = diff.stats()?;
+    Ok((stats.insertions(), stats.deletions()))
+}
+
+/// Counts how many of the most recent commits touched each file — a churn
+/// signal independent of *when* a file last changed (contrast with
+/// [`get_recent_file_activity`], which weights toward recency and also
+/// tracks last-author/last-modified metadata).
+///
+/// # Arguments
+/// * `repo` - Reference to an open git repository
+/// * `pool_size` - Number of recent commits to scan
+///
+/// # Returns
+/// A map from file path to the number of scanned commits that touched it.
+///
+/// # Example
+/// ```ignore
+/// let churn = get_file_churn(&repo, 200)?;
+/// let hottest = churn.iter().max_by_key(|(_, &count)| count);
+/// ```
+pub fn get_file_churn(repo: &Repository, pool_size: usize) -> Result<HashMap<String, usize>> {
     let mut revwalk = repo.revwalk()?;
     revwalk.push_head()?;
 
-    let mut file_commits: HashMap<String, (usize, i64, String)> = HashMap::new();
-    let now = Utc::now();
+    let mut churn: HashMap<String, usize> = HashMap::new();
 
-    for oid in revwalk.take(100) {
+    for oid in revwalk.take(pool_size) {
         let oid = oid?;
         let commit = repo.find_commit(oid)?;
 
@@ -270,21 +697,10 @@ pub fn get_recent_file_activity(repo: &Repository, count: usize) -> Result<Vec<F
         let mut diff_opts = DiffOptions::new();
         let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))?;
 
-        let author = commit.author().name().unwrap_or("unknown").to_string();
-        let time = commit.time().seconds();
-
         diff.foreach(
             &mut |delta, _| {
                 if let Some(path) = delta.new_file().path() {
-                    let path_str = path.to_string_lossy().to_string();
-                    let entry = file_commits
-                        .entry(path_str)
-                        .or_insert((0, time, author.clone()));
-                    entry.0 += 1;
-                    if time > entry.1 {
-                        entry.1 = time;
-                        entry.2 = author.clone();
-                    }
+                    *churn.entry(path.to_string_lossy().to_string()).or_insert(0) += 1;
                 }
                 true
             },
@@ -294,78 +710,171 @@ pub fn get_recent_file_activity(repo: &Repository, count: usize) -> Result<Vec<F
         )?;
     }
 
-    let mut activities: Vec<_> = file_commits
+    Ok(churn)
+}
+
+/// How much of a file's current content a single author is responsible
+/// for, per [`get_file_ownership`].
+#[derive(Debug, Serialize)]
+pub struct AuthorOwnership {
+    /// Name of the author, as recorded in blame's final signature.
+    pub author: String,
+    /// Number of lines in the file currently attributed to this author.
+    pub lines_owned: usize,
+    /// `lines_owned` as a percentage of the file's total line count.
+    pub percentage: f64,
+    /// Human-readable time since this author's most recent owning commit.
+    pub last_modified: String,
+}
+
+/// Identifies per-author code ownership for a file via git blame.
+///
+/// Complements [`FileActivity`] - which only counts how many commits
+/// touched a file - by attributing the file's *current* lines to whoever
+/// last wrote them, so reviewers know who to ask about a hot file.
+///
+/// # Arguments
+/// * `repo` - Reference to an open git repository
+/// * `file_path` - Path of the file to blame (relative to repo root)
+///
+/// # Returns
+/// A vector of [`AuthorOwnership`] sorted by `lines_owned` descending.
+///
+/// # Example
+/// ```ignore
+/// let ownership = get_file_ownership(&repo, "src/lib.rs")?;
+/// for author in ownership.iter().take(3) {
+///     println!("{}: {:.0}% ({} lines)", author.author, author.percentage, author.lines_owned);
+/// }
+/// ```
+pub fn get_file_ownership(repo: &Repository, file_path: &str) -> Result<Vec<AuthorOwnership>> {
+    let blame = repo.blame_file(Path::new(file_path), None)?;
+
+    let mut owned: HashMap<String, (usize, i64)> = HashMap::new();
+    let mut total_lines = 0usize;
+
+    for hunk in blame.iter() {
+        let lines = hunk.lines_in_hunk();
+        total_lines += lines;
+
+        let signature = hunk.final_signature();
+        let author = signature.name().unwrap_or("unknown").to_string();
+        let time = signature.when().seconds();
+
+        let entry = owned.entry(author).or_insert((0, time));
+        entry.0 += lines;
+        if time > entry.1 {
+            entry.1 = time;
+        }
+    }
+
+    let now = Utc::now();
+    let total = total_lines.max(1) as f64;
+
+    let mut ownership: Vec<AuthorOwnership> = owned
         .into_iter()
-        .map(|(path, (commit_count, time, last_author))| {
-            let datetime = Utc.timestamp_opt(time, 0).unwrap();
+        .map(|(author, (lines_owned, time))| {
+            let datetime = commit_datetime(time);
             let duration = now.signed_duration_since(datetime);
-            FileActivity {
-                path,
-                commit_count,
+            AuthorOwnership {
+                author,
+                lines_owned,
+                percentage: (lines_owned as f64 / total) * 100.0,
                 last_modified: format_duration(duration),
-                last_author,
             }
         })
         .collect();
 
-    activities.sort_by(|a, b| b.commit_count.cmp(&a.commit_count));
-    activities.truncate(count);
+    ownership.sort_by(|a, b| b.lines_owned.cmp(&a.lines_owned));
 
-    Ok(activities)
+    Ok(ownership)
 }
 
-/// Identifies directories with the most commit activity within a time window.
+/// A tracked file's age since the commit that last touched it, per
+/// [`get_file_staleness`].
+#[derive(Debug, Clone, Serialize)]
+pub struct FileStaleness {
+    /// File path relative to the repository root.
+    pub path: String,
+    /// Human-readable time since the last commit that touched this path.
+    pub last_modified: String,
+    /// Age in whole days since the last commit that touched this path.
+    pub age_days: i64,
+}
+
+/// Freshest and stalest tracked files, plus files present on disk that
+/// history has no record of - see [`get_file_staleness`].
+#[derive(Debug, Serialize)]
+pub struct FileStalenessReport {
+    /// Most recently touched tracked files, freshest first.
+    pub freshest: Vec<FileStaleness>,
+    /// Least recently touched tracked files, stalest first - candidates
+    /// for dead or abandoned code.
+    pub stalest: Vec<FileStaleness>,
+    /// Files present in the working tree but absent from git history
+    /// (untracked), reported rather than silently skipped.
+    pub untracked: Vec<String>,
+}
+
+/// Computes whole-tree file staleness from each tracked file's
+/// last-touching-commit date.
 ///
-/// Analyzes commits within the specified number of days and counts
-/// file modifications per directory to find where active development
-/// is concentrated.
+/// Unlike [`FileActivity`], which only looks at the commits in a
+/// [`CommitGraph`] (usually the last [`DEFAULT_HISTORY_POOL_SIZE`]),
+/// this walks the *entire* history once, recording the first-seen
+/// (i.e. most recent, since history is walked newest-first) touch time
+/// per path, and stops early as soon as every tracked file has been
+/// resolved. A file that was added and never modified again is resolved
+/// by its introducing commit, which naturally falls out of this logic
+/// since that's the only commit that ever touches it.
 ///
 /// # Arguments
 /// * `repo` - Reference to an open git repository
-/// * `days` - Number of days to look back from now
+/// * `limit` - Maximum number of files to include in each of
+///   `freshest`/`stalest`
 ///
 /// # Returns
-/// A vector of up to 10 [`HotDirectory`] structs sorted by commit count
-/// (descending). Root-level files are grouped under ".".
-///
-/// # Example
-/// ```ignore
-/// let hot_dirs = get_hot_directories(&repo, 7)?; // Last week
-/// for dir in hot_dirs {
-///     println!("{}: {} changes", dir.path, dir.commit_count);
-/// }
-/// ```
-pub fn get_hot_directories(repo: &Repository, days: i64) -> Result<Vec<HotDirectory>> {
+/// A [`FileStalenessReport`] with the freshest and stalest tracked files
+/// and the set of on-disk files git has never recorded touching.
+pub fn get_file_staleness(repo: &Repository, limit: usize) -> Result<FileStalenessReport> {
+    let cwd = repo.workdir().unwrap_or_else(|| Path::new(".")).to_path_buf();
+
+    let head_tree = repo.head()?.peel_to_tree()?;
+    let mut tracked_paths: HashSet<String> = HashSet::new();
+    head_tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+        if entry.kind() == Some(ObjectType::Blob) {
+            tracked_paths.insert(format!("{}{}", root, entry.name().unwrap_or("")));
+        }
+        TreeWalkResult::Ok
+    })?;
+
+    let mut remaining = tracked_paths.clone();
+    let mut last_touch: HashMap<String, i64> = HashMap::new();
+
     let mut revwalk = repo.revwalk()?;
     revwalk.push_head()?;
 
-    let cutoff = Utc::now().timestamp() - (days * 24 * 60 * 60);
-    let mut dir_commits: HashMap<String, usize> = HashMap::new();
-
     for oid in revwalk {
-        let oid = oid?;
-        let commit = repo.find_commit(oid)?;
-
-        if commit.time().seconds() < cutoff {
+        if remaining.is_empty() {
             break;
         }
 
+        let oid = oid?;
+        let commit = repo.find_commit(oid)?;
+
         let tree = commit.tree()?;
         let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
 
         let mut diff_opts = DiffOptions::new();
         let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))?;
 
+        let time = commit.time().seconds();
         diff.foreach(
             &mut |delta, _| {
                 if let Some(path) = delta.new_file().path() {
-                    if let Some(parent) = path.parent() {
-                        let dir = if parent.as_os_str().is_empty() {
-                            ".".to_string()
-                        } else {
-                            parent.to_string_lossy().to_string()
-                        };
-                        *dir_commits.entry(dir).or_insert(0) += 1;
+                    let path_str = path.to_string_lossy().to_string();
+                    if remaining.remove(&path_str) {
+                        last_touch.insert(path_str, time);
                     }
                 }
                 true
@@ -376,41 +885,44 @@ pub fn get_hot_directories(repo: &Repository, days: i64) -> Result<Vec<HotDirect
         )?;
     }
 
-    let mut hot_dirs: Vec<_> = dir_commits
+    let now = Utc::now();
+    let mut resolved: Vec<FileStaleness> = last_touch
         .into_iter()
-        .map(|(path, commit_count)| HotDirectory { path, commit_count })
+        .map(|(path, time)| {
+            let datetime = commit_datetime(time);
+            let duration = now.signed_duration_since(datetime);
+            FileStaleness {
+                path,
+                last_modified: format_duration(duration),
+                age_days: duration.num_days(),
+            }
+        })
         .collect();
 
-    hot_dirs.sort_by(|a, b| b.commit_count.cmp(&a.commit_count));
-    hot_dirs.truncate(10);
-
-    Ok(hot_dirs)
-}
+    resolved.sort_by(|a, b| a.age_days.cmp(&b.age_days));
 
-/// Gets a summary of uncommitted changes as insertion/deletion counts.
-///
-/// Computes the total number of lines added and removed across all
-/// uncommitted changes (both staged and unstaged) compared to HEAD.
-///
-/// # Arguments
-/// * `repo` - Reference to an open git repository
-///
-/// # Returns
-/// A tuple of `(insertions, deletions)` representing the total line counts.
-///
-/// # Example
-/// ```ignore
-/// let (added, removed) = get_diff_summary(&repo)?;
-/// println!("+{} -{} lines", added, removed);
-/// ```
-pub fn get_diff_summary(repo: &Repository) -> Result<(usize, usize)> {
-    let head = repo.head()?.peel_to_tree()?;
-    let mut diff_opts = DiffOptions::new();
+    let freshest = resolved.iter().take(limit).cloned().collect();
+    let stalest = resolved.iter().rev().take(limit).cloned().collect();
 
-    let diff = repo.diff_tree_to_workdir_with_index(Some(&head), Some(&mut diff_opts))?;
+    let untracked = walker::create_walker(&cwd)
+        .build()
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let rel = entry.path().strip_prefix(&cwd).ok()?.to_string_lossy().to_string();
+            if tracked_paths.contains(&rel) {
+                None
+            } else {
+                Some(rel)
+            }
+        })
+        .collect();
 
-    let stats = diff.stats()?;
-    Ok((stats.insertions(), stats.deletions()))
+    Ok(FileStalenessReport {
+        freshest,
+        stalest,
+        untracked,
+    })
 }
 
 fn format_duration(duration: chrono::Duration) -> String {
@@ -430,12 +942,12 @@ fn format_duration(duration: chrono::Duration) -> String {
 
 /// Finds files that frequently change together with a given file.
 ///
-/// Analyzes the last 500 commits to identify files that are commonly
-/// modified in the same commits as the target file. This is useful for
-/// understanding file relationships and dependencies.
+/// Reads [`CommitGraph::file_refs`] to find every commit that touched
+/// `file_path`, then counts how often each other file appeared in those
+/// same commits - no re-walking or re-diffing history.
 ///
 /// # Arguments
-/// * `repo` - Reference to an open git repository
+/// * `graph` - A [`CommitGraph`] built from the repository
 /// * `file_path` - Path of the file to analyze (relative to repo root)
 /// * `limit` - Maximum number of co-changed files to return
 ///
@@ -450,18 +962,97 @@ fn format_duration(duration: chrono::Duration) -> String {
 ///
 /// # Example
 /// ```ignore
-/// let related = get_files_changed_with(&repo, "src/lib.rs", 5)?;
+/// let graph = CommitGraph::build(&repo, DEFAULT_HISTORY_POOL_SIZE)?;
+/// let related = get_files_changed_with(&graph, "src/lib.rs", 5);
 /// for (path, count) in related {
 ///     println!("{} changed together {} times", path, count);
 /// }
 /// ```
-pub fn get_files_changed_with(repo: &Repository, file_path: &str, limit: usize) -> Result<Vec<(String, usize)>> {
+pub fn get_files_changed_with(graph: &CommitGraph, file_path: &str, limit: usize) -> Vec<(String, usize)> {
+    let target_commits = match graph.file_refs().get(file_path) {
+        Some(oids) => oids,
+        None => return Vec::new(),
+    };
+
+    let mut co_changes: HashMap<String, usize> = HashMap::new();
+
+    for oid in target_commits {
+        if let Some(node) = graph.get(oid) {
+            for f in &node.files {
+                if f != file_path {
+                    *co_changes.entry(f.clone()).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    let mut result: Vec<_> = co_changes.into_iter().collect();
+    result.sort_by(|a, b| b.1.cmp(&a.1));
+    result.truncate(limit);
+
+    result
+}
+
+/// An association rule describing how `file` co-changes with a target
+/// file, normalized against how often each side changes on its own.
+///
+/// Raw co-occurrence counts (as returned by [`get_files_changed_with`])
+/// over-weight files that change in nearly every commit (a changelog, a
+/// lockfile). These metrics answer "does B change *because* A changed, or
+/// does B just change a lot?":
+/// - `support` - fraction of all analyzed commits where both changed together
+/// - `confidence` - fraction of A's commits that also touched B (`P(B|A)`)
+/// - `lift` - confidence relative to B's overall change frequency; `1.0`
+///   means no coupling, `>1.0` means A and B change together more than
+///   chance would predict
+#[derive(Debug, Clone, Serialize)]
+pub struct TemporalCoupling {
+    pub file: String,
+    pub co_change_count: usize,
+    pub support: f64,
+    pub confidence: f64,
+    pub lift: f64,
+}
+
+/// Default number of recent commits scanned for [`get_temporal_coupling`],
+/// matching the window [`get_files_changed_with`] has always used.
+pub const DEFAULT_COUPLING_POOL_SIZE: usize = 500;
+
+/// Mines association rules over the commit history to find files that are
+/// temporally coupled to `file_path` - see [`TemporalCoupling`] for what
+/// `support`/`confidence`/`lift` mean.
+///
+/// Walks the last `pool_size` commits, tracking each file's total touch
+/// count `n(X)`, each pair's joint touch count `n(X,Y)`, and the total
+/// commit count `N`, then derives the rule metrics for every file that
+/// co-changed with `file_path`.
+///
+/// # Arguments
+/// * `repo` - Reference to an open git repository
+/// * `file_path` - Path of the file to analyze (relative to repo root)
+/// * `pool_size` - Number of recent commits to scan
+/// * `min_support` - Drops rules below this `support` (e.g. `0.02` requires
+///   the pair to have co-changed in at least 2% of analyzed commits),
+///   filtering out spurious couplings from small absolute counts
+/// * `limit` - Maximum number of rules to return
+///
+/// # Returns
+/// Rules sorted by `confidence` descending.
+pub fn get_temporal_coupling(
+    repo: &Repository,
+    file_path: &str,
+    pool_size: usize,
+    min_support: f64,
+    limit: usize,
+) -> Result<Vec<TemporalCoupling>> {
     let mut revwalk = repo.revwalk()?;
     revwalk.push_head()?;
 
-    let mut co_changes: HashMap<String, usize> = HashMap::new();
+    let mut touch_counts: HashMap<String, usize> = HashMap::new();
+    let mut joint_counts: HashMap<String, usize> = HashMap::new();
+    let mut total_commits = 0usize;
 
-    for oid in revwalk.take(500) {
+    for oid in revwalk.take(pool_size) {
         let oid = oid?;
         let commit = repo.find_commit(oid)?;
 
@@ -472,16 +1063,10 @@ pub fn get_files_changed_with(repo: &Repository, file_path: &str, limit: usize)
         let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))?;
 
         let mut files_in_commit = Vec::new();
-        let mut contains_target = false;
-
         diff.foreach(
             &mut |delta, _| {
                 if let Some(path) = delta.new_file().path() {
-                    let path_str = path.to_string_lossy().to_string();
-                    if path_str == file_path {
-                        contains_target = true;
-                    }
-                    files_in_commit.push(path_str);
+                    files_in_commit.push(path.to_string_lossy().to_string());
                 }
                 true
             },
@@ -490,18 +1075,179 @@ pub fn get_files_changed_with(repo: &Repository, file_path: &str, limit: usize)
             None,
         )?;
 
+        if files_in_commit.is_empty() {
+            continue;
+        }
+        total_commits += 1;
+
+        let contains_target = files_in_commit.iter().any(|f| f == file_path);
+
+        for f in &files_in_commit {
+            *touch_counts.entry(f.clone()).or_insert(0) += 1;
+        }
+
         if contains_target {
-            for f in files_in_commit {
+            for f in &files_in_commit {
                 if f != file_path {
-                    *co_changes.entry(f).or_insert(0) += 1;
+                    *joint_counts.entry(f.clone()).or_insert(0) += 1;
                 }
             }
         }
     }
 
-    let mut result: Vec<_> = co_changes.into_iter().collect();
-    result.sort_by(|a, b| b.1.cmp(&a.1));
-    result.truncate(limit);
+    let n_total = total_commits.max(1) as f64;
+    let n_a = *touch_counts.get(file_path).unwrap_or(&0) as f64;
+
+    let mut rules: Vec<TemporalCoupling> = joint_counts
+        .into_iter()
+        .filter_map(|(file, joint)| {
+            let n_b = *touch_counts.get(&file).unwrap_or(&0) as f64;
+            if n_a == 0.0 || n_b == 0.0 {
+                return None;
+            }
+
+            let support = joint as f64 / n_total;
+            if support < min_support {
+                return None;
+            }
+
+            let confidence = joint as f64 / n_a;
+            let lift = confidence / (n_b / n_total);
+
+            Some(TemporalCoupling {
+                file,
+                co_change_count: joint,
+                support,
+                confidence,
+                lift,
+            })
+        })
+        .collect();
+
+    rules.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
+    rules.truncate(limit);
+
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Initializes a throwaway repository and commits `files` one at a time
+    /// (in order), each commit writing/overwriting the given paths with the
+    /// given contents. Returns the `TempDir` (kept alive so the repo isn't
+    /// deleted out from under the test) and the opened `Repository`.
+    fn repo_with_commits(commits: &[(&str, &[(&str, &str)])]) -> (TempDir, Repository) {
+        let dir = TempDir::new().expect("failed to create temp directory");
+        let repo = Repository::init(dir.path()).expect("failed to init repo");
+
+        {
+            let mut config = repo.config().expect("failed to open repo config");
+            config.set_str("user.name", "Test User").unwrap();
+            config.set_str("user.email", "test@example.com").unwrap();
+        }
+
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let mut parent_oid: Option<Oid> = None;
+
+        for (message, files) in commits {
+            for (path, contents) in *files {
+                let full_path = dir.path().join(path);
+                if let Some(parent) = full_path.parent() {
+                    std::fs::create_dir_all(parent).unwrap();
+                }
+                std::fs::write(&full_path, contents).unwrap();
+            }
+
+            let mut index = repo.index().unwrap();
+            index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None).unwrap();
+            index.write().unwrap();
+            let tree_oid = index.write_tree().unwrap();
+            let tree = repo.find_tree(tree_oid).unwrap();
+
+            let parents: Vec<_> = parent_oid
+                .map(|oid| repo.find_commit(oid).unwrap())
+                .into_iter()
+                .collect();
+            let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+
+            let oid = repo
+                .commit(Some("HEAD"), &signature, &signature, message, &tree, &parent_refs)
+                .unwrap();
+            parent_oid = Some(oid);
+        }
+
+        (dir, repo)
+    }
+
+    #[test]
+    fn test_commit_graph_build_orders_newest_first_and_tracks_files() {
+        let (_dir, repo) = repo_with_commits(&[
+            ("first", &[("a.txt", "one")]),
+            ("second", &[("b.txt", "two")]),
+            ("third", &[("a.txt", "one-updated")]),
+        ]);
+
+        let graph = CommitGraph::build(&repo, DEFAULT_HISTORY_POOL_SIZE).unwrap();
+        let messages: Vec<&str> = graph.commits_in_order().map(|c| c.message.as_str()).collect();
+        assert_eq!(messages, vec!["third", "second", "first"]);
+
+        let a_commits = graph.file_refs().get("a.txt").unwrap();
+        assert_eq!(a_commits.len(), 2);
+        let b_commits = graph.file_refs().get("b.txt").unwrap();
+        assert_eq!(b_commits.len(), 1);
+    }
 
-    Ok(result)
+    #[test]
+    fn test_commit_graph_build_respects_limit() {
+        let (_dir, repo) = repo_with_commits(&[
+            ("first", &[("a.txt", "one")]),
+            ("second", &[("a.txt", "two")]),
+            ("third", &[("a.txt", "three")]),
+        ]);
+
+        let graph = CommitGraph::build(&repo, 2).unwrap();
+        assert_eq!(graph.commits_in_order().count(), 2);
+    }
+
+    #[test]
+    fn test_get_recent_commits_orders_and_truncates() {
+        let (_dir, repo) = repo_with_commits(&[
+            ("first", &[("a.txt", "one")]),
+            ("second", &[("a.txt", "two")]),
+            ("third", &[("a.txt", "three")]),
+        ]);
+
+        let graph = CommitGraph::build(&repo, DEFAULT_HISTORY_POOL_SIZE).unwrap();
+        let recent = get_recent_commits(&graph, 2);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].message, "third");
+        assert_eq!(recent[1].message, "second");
+    }
+
+    #[test]
+    fn test_get_recent_file_activity_sorts_by_commit_count() {
+        let (_dir, repo) = repo_with_commits(&[
+            ("first", &[("hot.txt", "1"), ("cold.txt", "1")]),
+            ("second", &[("hot.txt", "2")]),
+            ("third", &[("hot.txt", "3")]),
+        ]);
+
+        let graph = CommitGraph::build(&repo, DEFAULT_HISTORY_POOL_SIZE).unwrap();
+        let activity = get_recent_file_activity(&graph, 10);
+        assert_eq!(activity[0].path, "hot.txt");
+        assert_eq!(activity[0].commit_count, 3);
+        assert_eq!(activity.iter().find(|a| a.path == "cold.txt").unwrap().commit_count, 1);
+    }
+
+    #[test]
+    fn test_commit_datetime_falls_back_to_epoch_on_out_of_range_timestamp() {
+        let out_of_range = commit_datetime(i64::MAX);
+        assert_eq!(out_of_range, Utc.timestamp_opt(0, 0).unwrap());
+
+        let in_range = commit_datetime(1_700_000_000);
+        assert_eq!(in_range, Utc.timestamp_opt(1_700_000_000, 0).unwrap());
+    }
 }
@@ -0,0 +1,208 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+
+use super::walker;
+
+/// Linguist-style attributes resolved for a single path - see
+/// [`GitAttributes::resolve`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinguistAttrs {
+    /// `linguist-generated` - machine-generated code (protobuf output,
+    /// minified bundles, lockfiles written by a tool).
+    pub generated: bool,
+    /// `linguist-vendored` - third-party code checked in outside the usual
+    /// `vendor`/`node_modules` conventions.
+    pub vendored: bool,
+    /// `linguist-documentation` - prose rather than source.
+    pub documentation: bool,
+    /// `linguist-language=<name>` - forces language detection to `<name>`
+    /// regardless of extension.
+    pub language: Option<String>,
+}
+
+impl LinguistAttrs {
+    /// Whether linguist would exclude this path from language-aware analysis.
+    pub fn is_excluded(&self) -> bool {
+        self.generated || self.vendored || self.documentation
+    }
+}
+
+/// A parsed attribute value: either a boolean flag (`attr`, `-attr`) or a
+/// named value (`attr=<value>`, including `attr=true`/`attr=false`, which
+/// [`AttrValue::as_bool`] reads as true/false).
+enum AttrValue {
+    Bool(bool),
+    Named(String),
+}
+
+impl AttrValue {
+    fn as_bool(&self) -> bool {
+        match self {
+            AttrValue::Bool(b) => *b,
+            AttrValue::Named(v) => v != "false",
+        }
+    }
+}
+
+/// One `.gitattributes` line: a pattern matcher scoped to the directory the
+/// line was declared in, plus whichever linguist attributes it sets.
+/// `None` means "not mentioned by this line" so applying a rule only
+/// overwrites the attributes it actually sets.
+struct AttributeRule {
+    matcher: Gitignore,
+    generated: Option<bool>,
+    vendored: Option<bool>,
+    documentation: Option<bool>,
+    language: Option<Option<String>>,
+}
+
+/// Parsed `.gitattributes` linguist overrides for a directory tree.
+///
+/// Understands the subset of linguist attributes that affect analysis:
+/// `linguist-generated`, `linguist-vendored`, and `linguist-documentation`
+/// (each excludes a path unless explicitly negated with `-attr` or
+/// `attr=false`), plus `linguist-language=<name>` to force a file's
+/// language regardless of extension.
+///
+/// Matching follows gitattributes semantics: patterns are glob-matched
+/// against the path relative to the `.gitattributes` file that declared
+/// them (the same syntax as `.gitignore`), and later/more-specific rules
+/// win - rules from deeper directories, and later lines within the same
+/// file, are applied after earlier ones and therefore override them.
+pub struct GitAttributes {
+    rules: Vec<AttributeRule>,
+}
+
+impl GitAttributes {
+    /// Loads and parses every `.gitattributes` file from `root` downward.
+    ///
+    /// Files are processed shallowest-directory-first so that a deeper,
+    /// more specific `.gitattributes` file's rules are applied after - and
+    /// therefore override - a shallower one's, matching git's own
+    /// precedence for nested attributes files.
+    pub fn load(root: &Path) -> GitAttributes {
+        let mut files: Vec<PathBuf> = walker::create_walker_with_hidden(root)
+            .build()
+            .flatten()
+            .filter(|entry| entry.file_name() == ".gitattributes")
+            .map(|entry| entry.path().to_path_buf())
+            .collect();
+
+        files.sort_by_key(|p| p.components().count());
+
+        let mut rules = Vec::new();
+        for file in files {
+            let base = file.parent().unwrap_or(root).to_path_buf();
+            if let Ok(content) = std::fs::read_to_string(&file) {
+                for line in content.lines() {
+                    let line = line.trim();
+                    if line.is_empty() || line.starts_with('#') {
+                        continue;
+                    }
+                    if let Some(rule) = parse_line(&base, line) {
+                        rules.push(rule);
+                    }
+                }
+            }
+        }
+
+        GitAttributes { rules }
+    }
+
+    /// Resolves the linguist attributes that apply to `path`, which must be
+    /// relative to the same root `load` was given.
+    ///
+    /// Rules are applied in load order (shallowest directory, then file
+    /// order), so a later matching rule overrides whichever attributes it
+    /// sets, while leaving attributes it doesn't mention as inherited from
+    /// earlier matches.
+    pub fn resolve(&self, path: &Path) -> LinguistAttrs {
+        let mut attrs = LinguistAttrs::default();
+
+        for rule in &self.rules {
+            if !rule.matcher.matched(path, false).is_ignore() {
+                continue;
+            }
+            if let Some(v) = rule.generated {
+                attrs.generated = v;
+            }
+            if let Some(v) = rule.vendored {
+                attrs.vendored = v;
+            }
+            if let Some(v) = rule.documentation {
+                attrs.documentation = v;
+            }
+            if let Some(v) = &rule.language {
+                attrs.language = v.clone();
+            }
+        }
+
+        attrs
+    }
+
+    /// Shorthand for `resolve(path).is_excluded()`.
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        self.resolve(path).is_excluded()
+    }
+
+    /// Shorthand for `resolve(path).language`, the name a
+    /// `linguist-language=<name>` override forces for this path, if any.
+    pub fn forced_language(&self, path: &Path) -> Option<String> {
+        self.resolve(path).language
+    }
+}
+
+/// Parses one `.gitattributes` line (`<pattern> <attr> <attr>...`) into an
+/// [`AttributeRule`] scoped to `base`. Returns `None` if the pattern can't
+/// be compiled or the line has no pattern at all.
+fn parse_line(base: &Path, line: &str) -> Option<AttributeRule> {
+    let mut parts = line.split_whitespace();
+    let pattern = parts.next()?;
+
+    let mut builder = GitignoreBuilder::new(base);
+    builder.add_line(None, pattern).ok()?;
+    let matcher = builder.build().ok()?;
+
+    let mut generated = None;
+    let mut vendored = None;
+    let mut documentation = None;
+    let mut language = None;
+
+    for token in parts {
+        let (name, value) = parse_attr_token(token);
+        match name {
+            "linguist-generated" => generated = Some(value.as_bool()),
+            "linguist-vendored" => vendored = Some(value.as_bool()),
+            "linguist-documentation" => documentation = Some(value.as_bool()),
+            "linguist-language" => {
+                language = Some(match value {
+                    AttrValue::Named(name) => Some(name),
+                    AttrValue::Bool(false) => None,
+                    AttrValue::Bool(true) => None,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Some(AttributeRule {
+        matcher,
+        generated,
+        vendored,
+        documentation,
+        language,
+    })
+}
+
+/// Splits a single attribute token into its name and value: `-attr` clears
+/// it (`Bool(false)`), `attr=value` sets a named value, and a bare `attr`
+/// sets it (`Bool(true)`).
+fn parse_attr_token(token: &str) -> (&str, AttrValue) {
+    if let Some(name) = token.strip_prefix('-') {
+        (name, AttrValue::Bool(false))
+    } else if let Some((name, value)) = token.split_once('=') {
+        (name, AttrValue::Named(value.to_string()))
+    } else {
+        (token, AttrValue::Bool(true))
+    }
+}
@@ -0,0 +1,471 @@
+//! Runtime loading of external tree-sitter grammars.
+//!
+//! This module lets `ctx` pick up languages that aren't compiled into the
+//! binary. A grammar is described by a [`GrammarSpec`] (file extensions plus
+//! where to find the grammar's C sources or a prebuilt shared library), the
+//! sources are compiled into a `.so`/`.dylib` cached under `.ctx/grammars/`,
+//! and the grammar's `tree_sitter_<lang>` symbol is resolved at runtime to
+//! obtain a [`tree_sitter::Language`]. This mirrors the approach used by
+//! tree-sitter's own CLI loader.
+//!
+//! A grammar's sources can also live in a remote repository
+//! ([`GrammarSource::Git`]): [`fetch_git_grammar`] clones it at a pinned
+//! revision into a cache directory before compiling, so `languages.toml`
+//! can declare a grammar by `git`/`rev`/`subpath` instead of a local
+//! `path`. The `ctx grammar fetch`/`build` commands drive this explicitly;
+//! [`load_all`] also does it implicitly the first time a git-sourced
+//! grammar is actually needed.
+
+use anyhow::{bail, Context, Result};
+use git2::Repository;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tree_sitter::Language;
+
+/// Where a grammar's compiled artifact comes from.
+#[derive(Debug, Clone)]
+pub enum GrammarSource {
+    /// A `tree-sitter-<lang>` directory containing `src/parser.c` (and
+    /// optionally `src/scanner.c`) to be compiled on demand.
+    Directory(PathBuf),
+    /// An already-built shared library (`.so`/`.dylib`/`.dll`).
+    Prebuilt(PathBuf),
+    /// A `tree-sitter-<lang>` repository to clone at a pinned revision
+    /// before compiling, optionally rooted at `subpath` within the clone
+    /// (for grammar monorepos with multiple `tree-sitter-*` directories).
+    Git {
+        url: String,
+        rev: String,
+        subpath: Option<String>,
+    },
+}
+
+/// A single dynamically-loadable grammar, as declared in config.
+#[derive(Debug, Clone)]
+pub struct GrammarSpec {
+    /// Language name, used both for display and to derive the
+    /// `tree_sitter_<name>` symbol to look up.
+    pub name: String,
+    /// File extensions (without the leading dot) this grammar handles.
+    pub extensions: Vec<String>,
+    /// Exact or glob (`*` wildcard) filenames that identify this language
+    /// regardless of extension, e.g. `Dockerfile` or `*.in`.
+    pub filenames: Vec<String>,
+    /// `#!` interpreter names (the last path segment after `env`, if any)
+    /// that identify this language, e.g. `bash` or `python3`.
+    pub shebangs: Vec<String>,
+    /// Where the grammar's sources or prebuilt library live.
+    pub source: GrammarSource,
+}
+
+/// A grammar that has been compiled (if needed) and loaded into memory.
+#[derive(Clone)]
+pub struct LoadedGrammar {
+    pub name: String,
+    pub extensions: Vec<String>,
+    pub filenames: Vec<String>,
+    pub shebangs: Vec<String>,
+    pub language: Language,
+}
+
+impl std::fmt::Debug for LoadedGrammar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoadedGrammar")
+            .field("name", &self.name)
+            .field("extensions", &self.extensions)
+            .finish()
+    }
+}
+
+/// Directory (relative to the project root) where compiled grammars are cached.
+const GRAMMAR_CACHE_DIR: &str = ".ctx/grammars";
+
+/// Directory (relative to the project root) auto-scanned for prebuilt
+/// grammar libraries, on top of anything declared explicitly via
+/// `[[grammars]]` config entries. Lets a user drop in a
+/// `libtree-sitter-<lang>.so`/`.dylib`/`.dll` and have it picked up without
+/// writing config, the same way Helix resolves grammars it finds already
+/// built in its runtime directory.
+pub const GRAMMAR_RUNTIME_DIR: &str = "grammars";
+
+/// Scans `project_root/`[`GRAMMAR_RUNTIME_DIR`] for prebuilt shared
+/// libraries named `libtree-sitter-<lang>.{so,dylib,dll}` and builds a
+/// [`GrammarSpec`] for each, deriving the language name from the filename
+/// and defaulting its extensions to that same name (a config entry can
+/// still be added for finer-grained extensions, filenames, or shebangs).
+/// Returns an empty vector if the directory doesn't exist.
+pub fn discover_runtime_grammars(project_root: &Path) -> Vec<GrammarSpec> {
+    let dir = project_root.join(GRAMMAR_RUNTIME_DIR);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut specs = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if let Some(name) = parse_runtime_grammar_filename(&path) {
+            specs.push(GrammarSpec {
+                extensions: vec![name.clone()],
+                filenames: Vec::new(),
+                shebangs: Vec::new(),
+                source: GrammarSource::Prebuilt(path),
+                name,
+            });
+        }
+    }
+    specs
+}
+
+/// Extracts `<lang>` from a `libtree-sitter-<lang>.{so,dylib,dll}` file
+/// name, or `None` if `path` doesn't match that convention.
+fn parse_runtime_grammar_filename(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let ext = path.extension()?.to_str()?;
+    if !matches!(ext, "so" | "dylib" | "dll") {
+        return None;
+    }
+    stem.strip_prefix("libtree-sitter-").map(|s| s.to_string())
+}
+
+/// Compiles (if necessary) and loads every grammar in `specs`.
+///
+/// Each grammar is compiled to a shared library cached under
+/// [`GRAMMAR_CACHE_DIR`], keyed by a hash of its sources so recompilation
+/// only happens when the grammar changes. A grammar that fails to build or
+/// load is skipped rather than aborting the whole batch, since a single bad
+/// entry in user config shouldn't prevent built-in languages from working.
+pub fn load_all(specs: &[GrammarSpec], project_root: &Path) -> Vec<LoadedGrammar> {
+    let mut loaded = Vec::new();
+
+    for spec in specs {
+        match load_one(spec, project_root) {
+            Ok(grammar) => loaded.push(grammar),
+            Err(err) => {
+                eprintln!("warning: failed to load grammar '{}': {:#}", spec.name, err);
+            }
+        }
+    }
+
+    loaded
+}
+
+fn load_one(spec: &GrammarSpec, project_root: &Path) -> Result<LoadedGrammar> {
+    let lib_path = match &spec.source {
+        GrammarSource::Prebuilt(path) => path.clone(),
+        GrammarSource::Directory(dir) => compile_grammar(&spec.name, dir, project_root)?,
+        GrammarSource::Git { url, rev, subpath } => {
+            let dir = fetch_git_grammar(url, rev, subpath.as_deref(), project_root)?;
+            compile_grammar(&spec.name, &dir, project_root)?
+        }
+    };
+
+    let language = unsafe { load_language_symbol(&lib_path, &spec.name)? };
+
+    Ok(LoadedGrammar {
+        name: spec.name.clone(),
+        extensions: spec.extensions.clone(),
+        filenames: spec.filenames.clone(),
+        shebangs: spec.shebangs.clone(),
+        language,
+    })
+}
+
+/// Compiles a `tree-sitter-<lang>` directory into a shared library, reusing
+/// a cached build when the sources haven't changed.
+fn compile_grammar(name: &str, grammar_dir: &Path, project_root: &Path) -> Result<PathBuf> {
+    let parser_c = grammar_dir.join("src").join("parser.c");
+    if !parser_c.exists() {
+        bail!("no src/parser.c found in {}", grammar_dir.display());
+    }
+    let scanner_c = grammar_dir.join("src").join("scanner.c");
+
+    let hash = hash_grammar_sources(&parser_c, scanner_c.exists().then_some(&scanner_c))?;
+
+    let cache_dir = project_root.join(GRAMMAR_CACHE_DIR);
+    std::fs::create_dir_all(&cache_dir).context("Failed to create grammar cache directory")?;
+
+    let lib_ext = if cfg!(target_os = "macos") {
+        "dylib"
+    } else if cfg!(target_os = "windows") {
+        "dll"
+    } else {
+        "so"
+    };
+    let cached_lib = cache_dir.join(format!("{}-{:016x}.{}", name, hash, lib_ext));
+
+    if cached_lib.exists() {
+        return Ok(cached_lib);
+    }
+
+    let mut cmd = Command::new(cc_compiler());
+    cmd.arg("-shared")
+        .arg("-fPIC")
+        .arg("-O2")
+        .arg("-I")
+        .arg(grammar_dir.join("src"))
+        .arg(&parser_c)
+        .arg("-o")
+        .arg(&cached_lib);
+
+    if scanner_c.exists() {
+        cmd.arg(&scanner_c);
+    }
+
+    let status = cmd
+        .status()
+        .with_context(|| format!("Failed to invoke C compiler for grammar '{}'", name))?;
+
+    if !status.success() {
+        bail!("compiling grammar '{}' failed with status {}", name, status);
+    }
+
+    Ok(cached_lib)
+}
+
+/// Directory (relative to the project root) where grammar git clones are cached.
+const GRAMMAR_SOURCES_DIR: &str = ".ctx/grammars/sources";
+
+/// Clones `url` at `rev` into a cache directory keyed by a hash of
+/// `(url, rev)`, reusing an existing clone rather than re-cloning on every
+/// call, and returns the path to compile from: `subpath` within the clone
+/// if given, otherwise the clone root.
+pub fn fetch_git_grammar(url: &str, rev: &str, subpath: Option<&str>, project_root: &Path) -> Result<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    rev.hash(&mut hasher);
+    let clone_dir = project_root.join(GRAMMAR_SOURCES_DIR).join(format!("{:016x}", hasher.finish()));
+
+    if !clone_dir.join(".git").exists() {
+        if let Some(parent) = clone_dir.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create grammar sources cache directory")?;
+        }
+        let repo = Repository::clone(url, &clone_dir)
+            .with_context(|| format!("Failed to clone grammar repository {}", url))?;
+        checkout_rev(&repo, rev).with_context(|| format!("Failed to check out '{}' in {}", rev, url))?;
+    }
+
+    Ok(match subpath {
+        Some(sub) => clone_dir.join(sub),
+        None => clone_dir,
+    })
+}
+
+/// Checks out `rev` (a branch, tag, or commit) in a freshly cloned
+/// repository, detaching `HEAD` at its commit.
+fn checkout_rev(repo: &Repository, rev: &str) -> Result<()> {
+    let (object, _reference) = repo.revparse_ext(rev)?;
+    repo.checkout_tree(&object, None)?;
+    repo.set_head_detached(object.id())?;
+    Ok(())
+}
+
+fn cc_compiler() -> String {
+    std::env::var("CC").unwrap_or_else(|_| "cc".to_string())
+}
+
+fn hash_grammar_sources(parser_c: &Path, scanner_c: Option<&Path>) -> Result<u64> {
+    let mut hasher = DefaultHasher::new();
+
+    let parser_contents = std::fs::read(parser_c).context("Failed to read parser.c")?;
+    parser_contents.hash(&mut hasher);
+
+    if let Some(scanner_c) = scanner_c {
+        let scanner_contents = std::fs::read(scanner_c).context("Failed to read scanner.c")?;
+        scanner_contents.hash(&mut hasher);
+    }
+
+    Ok(hasher.finish())
+}
+
+/// Loads a `tree_sitter_<name>()` symbol from a compiled grammar library.
+///
+/// # Safety
+/// This calls into a dynamically loaded C library. The caller is trusted to
+/// point at an actual tree-sitter grammar built against a compatible ABI
+/// version; loading arbitrary shared libraries is inherently unsafe.
+unsafe fn load_language_symbol(lib_path: &Path, name: &str) -> Result<Language> {
+    use libloading::{Library, Symbol};
+
+    let lib = Library::new(lib_path)
+        .with_context(|| format!("Failed to dlopen grammar library {}", lib_path.display()))?;
+
+    let symbol_name = format!("tree_sitter_{}\0", sanitize_symbol(name));
+    let constructor: Symbol<unsafe extern "C" fn() -> Language> = lib
+        .get(symbol_name.as_bytes())
+        .with_context(|| format!("Symbol {} not found in {}", symbol_name.trim_end_matches('\0'), lib_path.display()))?;
+
+    let language = constructor();
+
+    // Leak the library handle: the `Language` value borrows code from it for
+    // the lifetime of the process, and we have no safe point to unload it.
+    std::mem::forget(lib);
+
+    Ok(language)
+}
+
+fn sanitize_symbol(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A `src/parser.c` that compiles with no tree-sitter headers and
+    /// exports a `tree_sitter_<name>()` returning null - enough to exercise
+    /// the real compile/dlopen/symbol-lookup pipeline without needing an
+    /// actual tree-sitter grammar.
+    fn minimal_grammar_source(symbol_name: &str) -> String {
+        format!("const void *tree_sitter_{}(void) {{ return (const void *)0; }}\n", symbol_name)
+    }
+
+    #[test]
+    fn test_sanitize_symbol_replaces_non_alphanumerics() {
+        assert_eq!(sanitize_symbol("c-sharp"), "c_sharp");
+        assert_eq!(sanitize_symbol("rust"), "rust");
+    }
+
+    #[test]
+    fn test_parse_runtime_grammar_filename_accepts_known_extensions() {
+        assert_eq!(
+            parse_runtime_grammar_filename(Path::new("libtree-sitter-zig.so")),
+            Some("zig".to_string())
+        );
+        assert_eq!(
+            parse_runtime_grammar_filename(Path::new("libtree-sitter-zig.dylib")),
+            Some("zig".to_string())
+        );
+        assert_eq!(
+            parse_runtime_grammar_filename(Path::new("libtree-sitter-zig.dll")),
+            Some("zig".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_runtime_grammar_filename_rejects_non_matching_names() {
+        assert_eq!(parse_runtime_grammar_filename(Path::new("libtree-sitter-zig.a")), None);
+        assert_eq!(parse_runtime_grammar_filename(Path::new("tree-sitter-zig.so")), None);
+        assert_eq!(parse_runtime_grammar_filename(Path::new("libzig.so")), None);
+    }
+
+    #[test]
+    fn test_discover_runtime_grammars_finds_prebuilt_libraries_only() {
+        let project_root = TempDir::new().unwrap();
+        let grammar_dir = project_root.path().join(GRAMMAR_RUNTIME_DIR);
+        std::fs::create_dir_all(&grammar_dir).unwrap();
+        std::fs::write(grammar_dir.join("libtree-sitter-zig.so"), b"not a real library").unwrap();
+        std::fs::write(grammar_dir.join("README.md"), b"not a grammar").unwrap();
+
+        let specs = discover_runtime_grammars(project_root.path());
+        assert_eq!(specs.len(), 1);
+        assert_eq!(specs[0].name, "zig");
+        assert_eq!(specs[0].extensions, vec!["zig".to_string()]);
+        assert!(matches!(specs[0].source, GrammarSource::Prebuilt(_)));
+    }
+
+    #[test]
+    fn test_discover_runtime_grammars_missing_directory_returns_empty() {
+        let project_root = TempDir::new().unwrap();
+        assert!(discover_runtime_grammars(project_root.path()).is_empty());
+    }
+
+    #[test]
+    fn test_compile_grammar_missing_parser_errors() {
+        let grammar_dir = TempDir::new().unwrap();
+        let project_root = TempDir::new().unwrap();
+
+        let err = compile_grammar("nope", grammar_dir.path(), project_root.path()).unwrap_err();
+        assert!(err.to_string().contains("parser.c"));
+    }
+
+    #[test]
+    fn test_compile_and_load_grammar_from_directory() {
+        let grammar_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(grammar_dir.path().join("src")).unwrap();
+        std::fs::write(grammar_dir.path().join("src/parser.c"), minimal_grammar_source("fixture")).unwrap();
+
+        let project_root = TempDir::new().unwrap();
+        let spec = GrammarSpec {
+            name: "fixture".to_string(),
+            extensions: vec!["fix".to_string()],
+            filenames: Vec::new(),
+            shebangs: Vec::new(),
+            source: GrammarSource::Directory(grammar_dir.path().to_path_buf()),
+        };
+
+        let loaded = load_all(std::slice::from_ref(&spec), project_root.path());
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].name, "fixture");
+
+        // Recompiling with unchanged sources should hit the cached library
+        // rather than failing (it would fail loudly if `cc` were invoked
+        // again against a stale/partial cache entry).
+        let loaded_again = load_all(std::slice::from_ref(&spec), project_root.path());
+        assert_eq!(loaded_again.len(), 1);
+    }
+
+    #[test]
+    fn test_load_all_skips_failed_grammar_without_aborting_batch() {
+        let project_root = TempDir::new().unwrap();
+        let broken = GrammarSpec {
+            name: "broken".to_string(),
+            extensions: vec!["brk".to_string()],
+            filenames: Vec::new(),
+            shebangs: Vec::new(),
+            source: GrammarSource::Directory(TempDir::new().unwrap().path().to_path_buf()),
+        };
+
+        let loaded = load_all(&[broken], project_root.path());
+        assert!(loaded.is_empty());
+    }
+
+    /// Creates a throwaway git repository containing a `src/parser.c` and
+    /// returns it alongside the sha of the commit that added it, for use as
+    /// a local (network-free) clone source in `fetch_git_grammar` tests.
+    fn init_grammar_source_repo() -> (TempDir, String) {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        {
+            let mut config = repo.config().unwrap();
+            config.set_str("user.name", "Test User").unwrap();
+            config.set_str("user.email", "test@example.com").unwrap();
+        }
+
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/parser.c"), minimal_grammar_source("cloned")).unwrap();
+
+        let signature = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None).unwrap();
+        index.write().unwrap();
+        let tree_oid = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_oid).unwrap();
+        let oid = repo
+            .commit(Some("HEAD"), &signature, &signature, "add parser", &tree, &[])
+            .unwrap();
+
+        (dir, oid.to_string())
+    }
+
+    #[test]
+    fn test_fetch_git_grammar_checks_out_pinned_rev_from_local_clone() {
+        let (source_dir, rev) = init_grammar_source_repo();
+        let project_root = TempDir::new().unwrap();
+        let url = source_dir.path().to_string_lossy().to_string();
+
+        let fetched = fetch_git_grammar(&url, &rev, None, project_root.path()).unwrap();
+        assert!(fetched.join("src").join("parser.c").exists());
+
+        // A second fetch should reuse the existing clone instead of
+        // re-cloning.
+        let fetched_again = fetch_git_grammar(&url, &rev, None, project_root.path()).unwrap();
+        assert_eq!(fetched, fetched_again);
+    }
+}
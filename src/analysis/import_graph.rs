@@ -0,0 +1,261 @@
+//! A directed, file-to-file dependency graph built from each file's
+//! [`ImportRecord`](super::symbols::ImportRecord)s.
+//!
+//! Resolution is necessarily best-effort: Rust `use` paths are only matched
+//! against files in the given set by their trailing segment (no crate
+//! metadata is available to resolve `external_crate::Thing`), and JS/Python
+//! imports are resolved relative to the importing file for
+//! `./`/`../`-prefixed or dotted-relative specifiers. Anything that doesn't
+//! resolve to a file in `files` is still recorded (as an edge with
+//! `resolved: None`) so it shows up as an external dependency, but it isn't
+//! traversed any further.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::symbols::{self, ImportRecord};
+use super::treesitter::{self, LanguageRegistry, SupportedLanguage};
+
+/// One dependency edge from a file: the path/module as imported, and the
+/// file it resolved to, if any.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportEdge {
+    pub imported: String,
+    pub resolved: Option<PathBuf>,
+}
+
+/// A directed graph of intra-repo import dependencies, keyed by importing
+/// file. Build with [`build_dependency_graph`].
+#[derive(Debug, Clone, Default)]
+pub struct ImportGraph {
+    pub edges: HashMap<PathBuf, Vec<ImportEdge>>,
+}
+
+impl ImportGraph {
+    /// Files in the graph that import `path`.
+    pub fn dependents_of(&self, path: &Path) -> Vec<&PathBuf> {
+        self.edges
+            .iter()
+            .filter(|(_, edges)| edges.iter().any(|e| e.resolved.as_deref() == Some(path)))
+            .map(|(file, _)| file)
+            .collect()
+    }
+
+    /// Depth-first search for a cycle among resolved edges, returning the
+    /// files involved (in traversal order, first file repeated at the end)
+    /// if one exists.
+    pub fn find_cycle(&self) -> Option<Vec<PathBuf>> {
+        let mut visited = HashSet::new();
+
+        for start in self.edges.keys() {
+            if visited.contains(start) {
+                continue;
+            }
+
+            let mut stack = Vec::new();
+            if let Some(cycle) = self.dfs(start, &mut visited, &mut stack) {
+                return Some(cycle);
+            }
+        }
+
+        None
+    }
+
+    fn dfs(
+        &self,
+        file: &Path,
+        visited: &mut HashSet<PathBuf>,
+        stack: &mut Vec<PathBuf>,
+    ) -> Option<Vec<PathBuf>> {
+        if let Some(pos) = stack.iter().position(|f| f == file) {
+            let mut cycle = stack[pos..].to_vec();
+            cycle.push(file.to_path_buf());
+            return Some(cycle);
+        }
+        if visited.contains(file) {
+            return None;
+        }
+
+        stack.push(file.to_path_buf());
+        let result = self.edges.get(file).and_then(|edges| {
+            edges
+                .iter()
+                .filter_map(|e| e.resolved.as_deref())
+                .find_map(|next| self.dfs(next, visited, stack))
+        });
+        stack.pop();
+        visited.insert(file.to_path_buf());
+
+        result
+    }
+}
+
+/// Builds the project-wide module dependency graph: parses each file,
+/// pulls out its [`ImportRecord`]s, and resolves each to another file in
+/// `files` where possible. Only the built-in languages (Rust/Python/JS/TS).
+/// See [`build_dependency_graph_with_registry`] to also recognize dynamic
+/// grammars declared in config.
+pub fn build_dependency_graph(files: &[PathBuf]) -> ImportGraph {
+    build_dependency_graph_with_registry(files, &LanguageRegistry::new())
+}
+
+/// Same as [`build_dependency_graph`], but resolves each file's language
+/// through `registry`, so dynamically loaded grammars are recognized too.
+pub fn build_dependency_graph_with_registry(files: &[PathBuf], registry: &LanguageRegistry) -> ImportGraph {
+    let known: HashSet<&Path> = files.iter().map(PathBuf::as_path).collect();
+    let mut graph = ImportGraph::default();
+
+    for file in files {
+        let Ok(source) = std::fs::read_to_string(file) else {
+            continue;
+        };
+        let Some(lang) = registry.detect(file, source.lines().next()) else {
+            continue;
+        };
+        let Ok(Some(tree)) = treesitter::parse_with_language(&source, &lang) else {
+            continue;
+        };
+
+        let edges = symbols::find_imports(&tree, &source, &lang)
+            .into_iter()
+            .flat_map(|record| import_record_edges(file, &record, &lang, &known))
+            .collect();
+
+        graph.edges.insert(file.clone(), edges);
+    }
+
+    graph
+}
+
+/// Expands one [`ImportRecord`] into its dependency edges. Rust/Python
+/// group several names under one declaration (`use a::{B, C};`,
+/// `from a import b, c`), each of which is its own edge; JS/TS specifiers
+/// all share a single module source, so the whole record is one edge.
+fn import_record_edges(
+    file: &Path,
+    record: &ImportRecord,
+    lang: &SupportedLanguage,
+    known: &HashSet<&Path>,
+) -> Vec<ImportEdge> {
+    match lang {
+        SupportedLanguage::Rust => {
+            let leaves: Vec<Option<&str>> = if record.is_glob {
+                vec![None]
+            } else if record.imported_names.is_empty() {
+                vec![None]
+            } else {
+                record.imported_names.iter().map(|(name, _)| Some(name.as_str())).collect()
+            };
+
+            leaves
+                .into_iter()
+                .map(|leaf| {
+                    let imported = match leaf {
+                        Some(leaf) if !record.module_path.is_empty() => {
+                            format!("{}::{leaf}", record.module_path.join("::"))
+                        }
+                        Some(leaf) => leaf.to_string(),
+                        None => format!("{}::*", record.module_path.join("::")),
+                    };
+                    let resolved = resolve_rust_import(file, &imported, known);
+                    ImportEdge { imported, resolved }
+                })
+                .collect()
+        }
+        SupportedLanguage::Python => {
+            let dotted_prefix = if record.is_relative {
+                ".".repeat(record.module_path.iter().take_while(|s| s.is_empty()).count() + 1)
+            } else {
+                String::new()
+            };
+            let rest: Vec<&str> = record
+                .module_path
+                .iter()
+                .skip_while(|s| s.is_empty())
+                .map(String::as_str)
+                .collect();
+
+            let leaves: Vec<Option<&str>> = if record.imported_names.is_empty() {
+                vec![None]
+            } else {
+                record.imported_names.iter().map(|(name, _)| Some(name.as_str())).collect()
+            };
+
+            leaves
+                .into_iter()
+                .map(|leaf| {
+                    let mut segments = rest.clone();
+                    if let Some(leaf) = leaf {
+                        segments.push(leaf);
+                    } else if record.is_glob {
+                        segments.push("*");
+                    }
+                    let imported = format!("{dotted_prefix}{}", segments.join("."));
+                    let resolved = resolve_python_import(file, &imported, known);
+                    ImportEdge { imported, resolved }
+                })
+                .collect()
+        }
+        SupportedLanguage::JavaScript | SupportedLanguage::TypeScript => {
+            let imported = record.module_path.first().cloned().unwrap_or_default();
+            let resolved = resolve_js_import(file, &imported, known);
+            vec![ImportEdge { imported, resolved }]
+        }
+        SupportedLanguage::Dynamic(_) => Vec::new(),
+    }
+}
+
+/// Matches a `use` path's trailing segment against `mod.rs`/`<name>.rs`
+/// siblings of `from` — there's no crate manifest available here to
+/// resolve paths properly, so external crates never resolve.
+fn resolve_rust_import(from: &Path, imported: &str, known: &HashSet<&Path>) -> Option<PathBuf> {
+    let segment = imported.trim_end_matches("::*").rsplit("::").next()?;
+    let parent = from.parent()?;
+
+    let candidates = [
+        parent.join(format!("{segment}.rs")),
+        parent.join(segment).join("mod.rs"),
+    ];
+
+    candidates.into_iter().find(|c| known.contains(c.as_path()))
+}
+
+/// Resolves `from.mod`/`.sibling`/`..pkg.thing`-style module paths relative
+/// to `from`'s directory; absolute (non-relative) imports aren't resolved
+/// against the file set since there's no `sys.path` to consult.
+fn resolve_python_import(from: &Path, imported: &str, known: &HashSet<&Path>) -> Option<PathBuf> {
+    let imported = imported.trim_end_matches(".*");
+    if !imported.starts_with('.') {
+        return None;
+    }
+
+    let dots = imported.chars().take_while(|c| *c == '.').count();
+    let rest = &imported[dots..];
+
+    let mut dir = from.parent()?.to_path_buf();
+    for _ in 1..dots {
+        dir = dir.parent()?.to_path_buf();
+    }
+
+    let rel = rest.replace('.', "/");
+    let candidates = [dir.join(format!("{rel}.py")), dir.join(rel).join("__init__.py")];
+
+    candidates.into_iter().find(|c| known.contains(c.as_path()))
+}
+
+/// Resolves `./`/`../`-relative JS/TS specifiers against common extensions
+/// and `index` files; bare package specifiers (`react`) aren't resolved
+/// against the file set.
+fn resolve_js_import(from: &Path, imported: &str, known: &HashSet<&Path>) -> Option<PathBuf> {
+    if !imported.starts_with('.') {
+        return None;
+    }
+
+    let parent = from.parent()?;
+    let extensions = ["", ".js", ".ts", ".jsx", ".tsx", "/index.js", "/index.ts"];
+
+    extensions
+        .iter()
+        .map(|ext| parent.join(format!("{imported}{ext}")))
+        .find(|c| known.contains(c.as_path()))
+}
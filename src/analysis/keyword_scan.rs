@@ -0,0 +1,51 @@
+use aho_corasick::AhoCorasick;
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// A reusable multi-keyword scanner: builds one Aho-Corasick automaton for a
+/// keyword set and scans any text against all of them in a single linear
+/// pass, rather than an `O(candidates * keywords * len)` nested
+/// `text.contains(keyword)` loop. Build once per query, then reuse the same
+/// scanner across every candidate path — and, in a content-scoring mode,
+/// every file body — it's run against.
+pub struct KeywordScanner {
+    ac: AhoCorasick,
+    keywords: Vec<String>,
+}
+
+impl KeywordScanner {
+    /// Builds a case-insensitive scanner over `keywords`. An empty keyword
+    /// set builds a scanner that never matches anything.
+    pub fn new(keywords: &[String]) -> Result<KeywordScanner> {
+        let ac = AhoCorasick::builder()
+            .ascii_case_insensitive(true)
+            .build(keywords)?;
+        Ok(KeywordScanner {
+            ac,
+            keywords: keywords.to_vec(),
+        })
+    }
+
+    /// Scans `text` in one pass, tallying how many times each keyword
+    /// occurred. Keywords with zero occurrences are absent from the result.
+    pub fn scan<'a>(&'a self, text: &str) -> HashMap<&'a str, usize> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for m in self.ac.find_iter(text) {
+            let keyword = self.keywords[m.pattern().as_usize()].as_str();
+            *counts.entry(keyword).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Whether `text` contains at least one occurrence of any keyword.
+    pub fn is_match(&self, text: &str) -> bool {
+        self.ac.is_match(text)
+    }
+
+    /// Finds every match of any keyword in `text`, in order, as byte ranges
+    /// into `text` — for callers that need to highlight hits rather than
+    /// just count them.
+    pub fn find_ranges(&self, text: &str) -> Vec<std::ops::Range<usize>> {
+        self.ac.find_iter(text).map(|m| m.start()..m.end()).collect()
+    }
+}
@@ -0,0 +1,15 @@
+pub mod deadline;
+pub mod git;
+pub mod gitattributes;
+pub mod grammar;
+pub mod import_graph;
+pub mod keyword_scan;
+pub mod monorepo;
+pub mod pathspec;
+pub mod relevance;
+pub mod stats;
+pub mod symbol_index;
+pub mod symbols;
+pub mod tokenizer;
+pub mod treesitter;
+pub mod walker;
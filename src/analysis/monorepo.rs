@@ -0,0 +1,90 @@
+//! Monorepo-aware project partitioning: groups a repo's directories into
+//! logical projects (each rooted at a manifest file) via a path trie, so a
+//! changed file can be attributed to the nearest enclosing project instead
+//! of treating the whole repo as one unit.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::walker;
+
+/// Manifest filenames that mark a directory as owning its own project.
+/// A directory containing one of these is a project root even when it's
+/// nested under another project root (e.g. a `services/api/Cargo.toml`
+/// inside a repo whose top level is a `package.json` workspace).
+const MANIFEST_FILES: &[&str] =
+    &["Cargo.toml", "package.json", "pyproject.toml", "go.mod", "pom.xml", "build.gradle"];
+
+#[derive(Debug, Default)]
+struct TrieNode {
+    children: HashMap<String, TrieNode>,
+    is_project_root: bool,
+}
+
+/// A path trie over the repo's directory structure, one node per path
+/// component, with nodes flagged as project roots wherever a manifest
+/// file was found during the walk. The repo root itself is always a
+/// project root, so every file attributes to *some* project even when no
+/// manifest is found below it.
+#[derive(Debug)]
+pub struct ProjectTrie {
+    root: TrieNode,
+}
+
+impl ProjectTrie {
+    /// Walks `repo_root` and builds the trie, marking any directory
+    /// containing one of [`MANIFEST_FILES`] as a project root.
+    pub fn build(repo_root: &Path) -> Self {
+        let mut root = TrieNode { is_project_root: true, ..Default::default() };
+
+        let dir_walker = walker::create_walker(repo_root).build();
+        for entry in dir_walker.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+
+            if MANIFEST_FILES.iter().any(|m| path.join(m).exists()) {
+                let rel = path.strip_prefix(repo_root).unwrap_or(path);
+                mark_project_root(&mut root, rel);
+            }
+        }
+
+        Self { root }
+    }
+
+    /// Returns the path (relative to the repo root, `.` for the root
+    /// itself) of the nearest enclosing project for `rel_path`: the
+    /// deepest project-root node encountered while descending the trie
+    /// along the file's path components.
+    pub fn owning_project(&self, rel_path: &Path) -> String {
+        let mut node = &self.root;
+        let mut deepest = ".".to_string();
+        let mut current = PathBuf::new();
+
+        for component in rel_path.components() {
+            let key = component.as_os_str().to_string_lossy().to_string();
+            let next = match node.children.get(&key) {
+                Some(n) => n,
+                None => break,
+            };
+
+            current.push(&key);
+            if next.is_project_root {
+                deepest = current.to_string_lossy().to_string();
+            }
+            node = next;
+        }
+
+        deepest
+    }
+}
+
+fn mark_project_root(root: &mut TrieNode, rel: &Path) {
+    let mut node = root;
+    for component in rel.components() {
+        let key = component.as_os_str().to_string_lossy().to_string();
+        node = node.children.entry(key).or_default();
+    }
+    node.is_project_root = true;
+}
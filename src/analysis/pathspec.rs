@@ -0,0 +1,117 @@
+use anyhow::{anyhow, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::Path;
+
+/// One parsed pathspec: a glob matcher plus whether it's a negative
+/// (exclude) spec.
+struct ParsedSpec {
+    matcher: Gitignore,
+    negative: bool,
+}
+
+/// A Git-style pathspec filter, built from `--include`/`--exclude` values.
+///
+/// Each raw spec may carry "magic" in the `:(flag,flag)` prefix Git itself
+/// uses: `:(glob)` enables full `**` globbing (without it, `**` is treated
+/// literally, matching two asterisks rather than any directory depth),
+/// `:(icase)` makes matching case-insensitive, and
+/// `:(exclude)` (or a leading `!`) marks the spec negative regardless of
+/// which flag (`--include`/`--exclude`) it arrived through.
+///
+/// A path is kept if it matches no negative spec and matches at least one
+/// positive spec, or there are no positive specs at all — negative specs
+/// always win over positive ones.
+#[derive(Default)]
+pub struct PathSpecSet {
+    specs: Vec<ParsedSpec>,
+    has_positive: bool,
+}
+
+impl PathSpecSet {
+    /// Builds a filter from `--include` values (positive by default) and
+    /// `--exclude` values (always negative, regardless of their own magic).
+    /// Returns an empty, pass-everything filter if both lists are empty.
+    pub fn new(includes: &[String], excludes: &[String]) -> Result<PathSpecSet> {
+        let mut specs = Vec::new();
+        let mut has_positive = false;
+
+        for raw in includes {
+            let spec = parse_one(raw, false)?;
+            has_positive = has_positive || !spec.negative;
+            specs.push(spec);
+        }
+        for raw in excludes {
+            specs.push(parse_one(raw, true)?);
+        }
+
+        Ok(PathSpecSet { specs, has_positive })
+    }
+
+    /// Whether this filter has no specs at all, i.e. it passes every path.
+    pub fn is_empty(&self) -> bool {
+        self.specs.is_empty()
+    }
+
+    /// Whether `path` (relative to the walk root) should be kept.
+    pub fn matches(&self, path: &Path) -> bool {
+        if self.specs.iter().any(|s| s.negative && s.matcher.matched(path, false).is_ignore()) {
+            return false;
+        }
+
+        if !self.has_positive {
+            return true;
+        }
+
+        self.specs.iter().any(|s| !s.negative && s.matcher.matched(path, false).is_ignore())
+    }
+}
+
+/// Parses one raw `--include`/`--exclude` value into a [`ParsedSpec`].
+/// `force_negative` is set for values that arrived via `--exclude`, which
+/// are always negative no matter what magic (or lack of it) they carry.
+fn parse_one(raw: &str, force_negative: bool) -> Result<ParsedSpec> {
+    let mut rest = raw;
+    let mut negative = force_negative || rest.starts_with('!');
+    if let Some(stripped) = rest.strip_prefix('!') {
+        rest = stripped;
+    }
+
+    let mut glob_magic = false;
+    let mut icase = false;
+
+    while let Some(stripped) = rest.strip_prefix(":(") {
+        let end = stripped
+            .find(')')
+            .ok_or_else(|| anyhow!("unterminated pathspec magic in `{}`", raw))?;
+
+        for flag in stripped[..end].split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            match flag {
+                "glob" => glob_magic = true,
+                "icase" => icase = true,
+                "exclude" => negative = true,
+                other => return Err(anyhow!("unknown pathspec magic `:({})` in `{}`", other, raw)),
+            }
+        }
+
+        rest = &stripped[end + 1..];
+    }
+
+    if rest.is_empty() {
+        return Err(anyhow!("empty pathspec pattern in `{}`", raw));
+    }
+
+    // Without `:(glob)` magic, `**` doesn't cross directory boundaries —
+    // treat it as two literal asterisks rather than a recursive glob.
+    let pattern = if glob_magic {
+        rest.to_string()
+    } else {
+        rest.replace("**", "\\*\\*")
+    };
+
+    let mut builder = GitignoreBuilder::new(".");
+    builder.case_insensitive(icase)?;
+    builder.add_line(None, &pattern)?;
+    let matcher = builder.build()?;
+
+    Ok(ParsedSpec { matcher, negative })
+}
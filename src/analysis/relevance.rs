@@ -1,7 +1,10 @@
 use std::collections::HashMap;
+use std::ops::Range;
 use std::path::Path;
 
 use super::git;
+use super::keyword_scan::KeywordScanner;
+use super::walker;
 use anyhow::Result;
 use git2::Repository;
 
@@ -25,6 +28,22 @@ pub struct RelevanceScore {
     /// Human-readable reasons explaining why this file was scored as relevant.
     /// Examples: "path mentioned", "filename mentioned", "3 recent commits".
     pub reasons: Vec<String>,
+    /// Best-matching lines for the query keywords, with byte-range
+    /// highlights, when `with_snippets` was set on
+    /// [`score_files_for_prompt`]. `None` otherwise.
+    pub snippets: Option<Vec<Snippet>>,
+}
+
+/// One matched line of a scored file's content, for rendering an
+/// annotated, caret-underlined excerpt instead of just a filename.
+#[derive(Debug)]
+pub struct Snippet {
+    /// 1-based line number within the file.
+    pub line_no: usize,
+    /// The full text of the matched line.
+    pub text: String,
+    /// Byte ranges within `text` where a query keyword matched.
+    pub highlights: Vec<Range<usize>>,
 }
 
 /// Scores a list of candidate files for relevance to a given prompt.
@@ -38,39 +57,128 @@ pub struct RelevanceScore {
 /// * `prompt` - The user's query or prompt text
 /// * `candidates` - List of file paths to evaluate
 /// * `budget` - Maximum estimated token count for the returned results
+/// * `churn_pool_size` - Number of recent commits to scan for the churn signal
+/// * `excludes` - Glob patterns (e.g. `vendor/**`, `*.lock`) to drop from
+///   `candidates` before scoring, same syntax as `.gitignore` entries;
+///   always wins over `includes` and `include_ignored`
+/// * `includes` - Glob patterns that force-keep a matching candidate even
+///   if `.gitignore` rules would otherwise drop it (e.g. a prompt that
+///   names a generated `dist/index.ts` explicitly)
+/// * `include_ignored` - If `true`, skips the `.gitignore` filtering stage
+///   entirely, scoring every candidate not caught by `excludes`
+/// * `with_snippets` - If `true`, populates each result's
+///   [`RelevanceScore::snippets`] with the best-matching lines from the
+///   file's content (see [`Snippet`]); costs an extra file read per result
+///   and counts the snippet text against `budget`
 ///
 /// # Returns
 /// A vector of [`RelevanceScore`] instances, sorted by score in descending order,
 /// truncated to fit within the token budget.
 ///
 /// # Scoring Heuristics
+/// - **BM25 base**: candidate paths are tokenized as documents and ranked
+///   against `prompt`'s keywords with Okapi BM25 (`k1=1.2`, `b=0.75`; see
+///   [`bm25_scores`]), so rare terms and proportionally dense matches count
+///   for more than incidental hits in a long path
 /// - **+10.0**: Full path mentioned in prompt
 /// - **+5.0**: Filename mentioned in prompt
-/// - **+1.0**: Each keyword (3+ chars) found in path
 /// - **+0.5-2.5**: Recent git activity (up to 5 commits)
 /// - **+2.0**: File type matches prompt context (test, config, error files)
+/// - **+0-3.0**: Churn, i.e. how often the file changes relative to the
+///   hottest file in `churn_pool_size` commits ("high-churn" reason once
+///   the file is at least half as hot as the hottest)
+#[allow(clippy::too_many_arguments)]
 pub fn score_files_for_prompt(
     repo: &Repository,
     prompt: &str,
     candidates: &[String],
     budget: usize,
+    churn_pool_size: usize,
+    excludes: &[String],
+    includes: &[String],
+    include_ignored: bool,
+    with_snippets: bool,
 ) -> Result<Vec<RelevanceScore>> {
     let prompt_lower = prompt.to_lowercase();
-    let words: Vec<&str> = prompt_lower.split_whitespace().collect();
+    let query_terms = extract_keywords(prompt);
+
+    // Feed the scorer both loose keywords and any file paths the prompt
+    // names outright, so a mention like "src/main.rs" also counts toward
+    // the BM25 term set, not just the exact path/filename-match bonus below.
+    let mut scan_terms = query_terms.clone();
+    scan_terms.extend(extract_mentioned_files(&prompt_lower));
+    scan_terms.sort();
+    scan_terms.dedup();
+
+    // Word and word-span candidates for fuzzy stem matching (see
+    // `fuzzy_match_path`), so a typo'd or loosely-worded mention still
+    // earns a graded bonus instead of nothing. Skipped outright past
+    // `FUZZY_MAX_CANDIDATES` candidates rather than slowing every prompt
+    // down on huge monorepos.
+    let fuzzy_spans = if candidates.len() <= FUZZY_MAX_CANDIDATES {
+        fuzzy_query_spans(&prompt_lower)
+    } else {
+        Vec::new()
+    };
 
     let mut scores: Vec<RelevanceScore> = Vec::new();
 
     // Get recent file activity for recency scoring
-    let recent_activity = git::get_recent_file_activity(repo, 50).unwrap_or_default();
+    let recent_activity = git::CommitGraph::build(repo, git::DEFAULT_HISTORY_POOL_SIZE)
+        .map(|graph| git::get_recent_file_activity(&graph, 50))
+        .unwrap_or_default();
     let activity_map: HashMap<_, _> = recent_activity
         .iter()
         .map(|a| (a.path.clone(), a.commit_count))
         .collect();
 
-    for path in candidates {
+    // Get churn counts for the "changes constantly" signal, normalized
+    // against the hottest file seen in the pool.
+    let churn = git::get_file_churn(repo, churn_pool_size).unwrap_or_default();
+    let max_churn = churn.values().copied().max().unwrap_or(0).max(1) as f64;
+
+    let repo_root = repo.workdir().unwrap_or_else(|| Path::new("."));
+    let exclude_matcher = walker::build_exclude_matcher(repo_root, excludes);
+    let include_matcher = walker::build_include_matcher(repo_root, includes);
+
+    let bm25 = bm25_scores(candidates, &scan_terms)?;
+
+    for (i, path) in candidates.iter().enumerate() {
+        let candidate_path = Path::new(path);
+
+        if let Some(matcher) = &exclude_matcher {
+            if walker::is_excluded(matcher, candidate_path) {
+                continue;
+            }
+        }
+
+        let force_included = include_matcher
+            .as_ref()
+            .map(|matcher| walker::is_included(matcher, candidate_path))
+            .unwrap_or(false);
+
+        // Candidate lists are sometimes gathered from sources other than a
+        // `.gitignore`-respecting walk (e.g. git history), so re-check here
+        // rather than trusting every caller to have filtered already.
+        if !force_included && !include_ignored {
+            if repo.status_should_ignore(candidate_path).unwrap_or(false) {
+                continue;
+            }
+        }
+
         let mut score = 0.0;
         let mut reasons = Vec::new();
 
+        // BM25 base: how well this path's tokens match the prompt's
+        // keywords, normalized for document length and term rarity.
+        let (bm25_score, top_terms) = &bm25[i];
+        if *bm25_score > 0.0 {
+            score += *bm25_score;
+            for (term, idf) in top_terms {
+                reasons.push(format!("term '{}' (idf={:.2})", term, idf));
+            }
+        }
+
         // Check if file path is mentioned in prompt
         let path_lower = path.to_lowercase();
         let file_name = Path::new(path)
@@ -85,13 +193,11 @@ pub fn score_files_for_prompt(
         } else if prompt_lower.contains(&file_name) {
             score += 5.0;
             reasons.push("filename mentioned".to_string());
-        }
-
-        // Check for keyword matches in path
-        for word in &words {
-            if word.len() >= 3 && path_lower.contains(word) {
-                score += 1.0;
-            }
+        } else if let Some((similarity, _)) = fuzzy_match_path(path, &fuzzy_spans) {
+            // Graded version of the filename-mention bonus above, scaled by
+            // match quality rather than all-or-nothing.
+            score += similarity * 5.0;
+            reasons.push(format!("fuzzy match '{}' ({:.2})", file_name, similarity));
         }
 
         // Boost recently active files
@@ -109,11 +215,23 @@ pub fn score_files_for_prompt(
             reasons.push("relevant file type".to_string());
         }
 
+        // Boost files that change constantly, regardless of whether the
+        // most recent change was recent - a perpetually hot file is
+        // disproportionately likely to be relevant to a new task.
+        if let Some(&count) = churn.get(path) {
+            let normalized = count as f64 / max_churn;
+            score += normalized * 3.0;
+            if normalized >= 0.5 {
+                reasons.push("high-churn".to_string());
+            }
+        }
+
         if score > 0.0 {
             scores.push(RelevanceScore {
                 path: path.clone(),
                 score,
                 reasons,
+                snippets: None,
             });
         }
     }
@@ -121,23 +239,289 @@ pub fn score_files_for_prompt(
     // Sort by score descending
     scores.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
 
+    // Build once and reuse across every result's snippet extraction, same
+    // as the BM25 scanner above.
+    let snippet_scanner = if with_snippets {
+        KeywordScanner::new(&scan_terms).ok()
+    } else {
+        None
+    };
+
     // Estimate tokens and truncate to budget
     let mut token_count = 0;
     let mut result = Vec::new();
 
-    for scored in scores {
+    for mut scored in scores {
         // Rough estimate: 4 chars per token
         let estimated_tokens = scored.path.len() / 4 + 10;
         if token_count + estimated_tokens > budget {
             break;
         }
         token_count += estimated_tokens;
+
+        if let Some(scanner) = &snippet_scanner {
+            let snippets = extract_snippets(repo_root, &scored.path, scanner, SNIPPET_MAX_LINES);
+            let snippet_tokens: usize = snippets.iter().map(|s| s.text.len() / 4 + 2).sum();
+            // Drop the snippets (not the file) if they'd blow the budget -
+            // the path/reasons line is still worth keeping.
+            if !snippets.is_empty() && token_count + snippet_tokens <= budget {
+                token_count += snippet_tokens;
+                scored.snippets = Some(snippets);
+            }
+        }
+
         result.push(scored);
     }
 
     Ok(result)
 }
 
+/// Maximum number of [`Snippet`]s [`score_files_for_prompt`] extracts per
+/// file when `with_snippets` is set.
+const SNIPPET_MAX_LINES: usize = 3;
+
+/// Picks the best-matching lines of the file at `repo_root.join(rel_path)`
+/// for `scanner`'s keyword set, returning up to `max_lines` [`Snippet`]s in
+/// file order. Lines are ranked by how many keyword hits they contain;
+/// ties keep the earlier line. Returns an empty vector if the file can't
+/// be read (e.g. binary content, or it was deleted since being listed as a
+/// candidate) or no line matches.
+fn extract_snippets(repo_root: &Path, rel_path: &str, scanner: &KeywordScanner, max_lines: usize) -> Vec<Snippet> {
+    let content = match std::fs::read_to_string(repo_root.join(rel_path)) {
+        Ok(c) => c,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut matched_lines: Vec<(usize, &str, Vec<Range<usize>>)> = content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let highlights = scanner.find_ranges(line);
+            if highlights.is_empty() {
+                None
+            } else {
+                Some((idx + 1, line, highlights))
+            }
+        })
+        .collect();
+
+    matched_lines.sort_by(|a, b| b.2.len().cmp(&a.2.len()).then(a.0.cmp(&b.0)));
+    matched_lines.truncate(max_lines);
+    matched_lines.sort_by_key(|(line_no, _, _)| *line_no);
+
+    matched_lines
+        .into_iter()
+        .map(|(line_no, text, highlights)| Snippet {
+            line_no,
+            text: text.to_string(),
+            highlights,
+        })
+        .collect()
+}
+
+/// BM25 term-frequency saturation parameter: higher values let repeated term
+/// matches keep contributing for longer before diminishing returns kick in.
+const BM25_K1: f64 = 1.2;
+/// BM25 length-normalization parameter: 0 ignores document length entirely,
+/// 1 normalizes fully against `avgdl`.
+const BM25_B: f64 = 0.75;
+/// How many contributing terms to surface per file in [`RelevanceScore::reasons`].
+const BM25_TOP_TERMS: usize = 3;
+
+/// Splits a path into lowercase alphanumeric/underscore tokens, using the
+/// same delimiter rule as [`extract_keywords`] so path tokens and prompt
+/// keywords share one vocabulary.
+fn tokenize_path(path: &str) -> Vec<String> {
+    path.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Computes an Okapi BM25 score for every candidate against `query_terms`,
+/// treating each path as a tokenized document (see [`tokenize_path`]) for
+/// length normalization, with per-term frequencies tallied by a single
+/// [`KeywordScanner`] pass over each path rather than a nested
+/// `O(candidates * keywords)` loop.
+///
+/// For each query term `t`, `IDF(t) = ln((N - n(t) + 0.5)/(n(t) + 0.5) + 1)`
+/// where `N` is the candidate count and `n(t)` the number of candidates
+/// containing `t`. Each document's score accumulates
+/// `IDF(t) * (f(t,D)*(k1+1)) / (f(t,D) + k1*(1 - b + b*|D|/avgdl))` over
+/// query terms present in it.
+///
+/// Returns one `(score, top_terms)` pair per candidate, in `candidates`
+/// order, where `top_terms` lists the [`BM25_TOP_TERMS`] highest-IDF terms
+/// that actually matched, for [`RelevanceScore::reasons`].
+///
+/// Guards the two edge cases BM25 is sensitive to: an empty or
+/// all-empty-tokens candidate list (`avgdl == 0`) scores everything zero
+/// rather than dividing by zero, and query terms absent from every
+/// candidate are skipped rather than contributing a spurious IDF.
+fn bm25_scores(candidates: &[String], query_terms: &[String]) -> Result<Vec<(f64, Vec<(String, f64)>)>> {
+    let n_docs = candidates.len();
+    if n_docs == 0 || query_terms.is_empty() {
+        return Ok(vec![(0.0, Vec::new()); n_docs]);
+    }
+
+    let doc_lens: Vec<usize> = candidates.iter().map(|p| tokenize_path(p).len()).collect();
+    let avgdl = doc_lens.iter().sum::<usize>() as f64 / n_docs as f64;
+    if avgdl == 0.0 {
+        return Ok(vec![(0.0, Vec::new()); n_docs]);
+    }
+
+    let scanner = KeywordScanner::new(query_terms)?;
+    let term_freqs: Vec<HashMap<&str, usize>> = candidates
+        .iter()
+        .map(|p| scanner.scan(&p.to_lowercase()))
+        .collect();
+
+    let mut doc_freq: HashMap<&str, usize> = HashMap::new();
+    for freqs in &term_freqs {
+        for term in freqs.keys() {
+            *doc_freq.entry(term).or_insert(0) += 1;
+        }
+    }
+
+    let idf: HashMap<&str, f64> = query_terms
+        .iter()
+        .filter_map(|term| {
+            let n_t = *doc_freq.get(term.as_str()).unwrap_or(&0);
+            if n_t == 0 {
+                return None; // absent from every candidate; no signal to contribute
+            }
+            let weight = ((n_docs as f64 - n_t as f64 + 0.5) / (n_t as f64 + 0.5) + 1.0).ln();
+            Some((term.as_str(), weight))
+        })
+        .collect();
+
+    Ok(term_freqs
+        .iter()
+        .zip(doc_lens.iter())
+        .map(|(freqs, &dl)| {
+            let dl = dl as f64;
+            let mut score = 0.0;
+            let mut contributions: Vec<(String, f64)> = Vec::new();
+
+            for (&term, &weight) in &idf {
+                let f = *freqs.get(term).unwrap_or(&0) as f64;
+                if f == 0.0 {
+                    continue;
+                }
+                let denom = f + BM25_K1 * (1.0 - BM25_B + BM25_B * dl / avgdl);
+                score += weight * (f * (BM25_K1 + 1.0)) / denom;
+                contributions.push((term.to_string(), weight));
+            }
+
+            contributions.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+            contributions.truncate(BM25_TOP_TERMS);
+
+            (score, contributions)
+        })
+        .collect())
+}
+
+/// Minimum normalized similarity (`0.0..=1.0`) a [`fuzzy_match_path`] hit
+/// must clear to count; below this, near-misses are noise rather than
+/// signal (most unrelated short identifiers land somewhere around 0.3-0.5).
+const FUZZY_MIN_SIMILARITY: f64 = 0.72;
+/// How many of the prompt's longest word/word-span candidates
+/// [`fuzzy_query_spans`] keeps, bounding the per-candidate comparison cost.
+const FUZZY_MAX_QUERY_WORDS: usize = 12;
+/// Candidate-list size past which fuzzy matching is skipped outright (see
+/// its use in [`score_files_for_prompt`]).
+const FUZZY_MAX_CANDIDATES: usize = 20_000;
+
+/// Builds the word and short word-span candidates [`fuzzy_match_path`]
+/// tests against: every alphanumeric word at least 3 characters long, plus
+/// every adjacent pair joined with no separator (so "the relevance scorer"
+/// also tries "relevancescorer", matching a filename like
+/// "relevancescorer.rs" that a single word wouldn't). Kept to the
+/// [`FUZZY_MAX_QUERY_WORDS`] longest, most-specific spans.
+fn fuzzy_query_spans(prompt_lower: &str) -> Vec<String> {
+    let words: Vec<&str> = prompt_lower
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| w.len() >= 3)
+        .collect();
+
+    let mut spans: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+    for pair in words.windows(2) {
+        spans.push(format!("{}{}", pair[0], pair[1]));
+    }
+
+    spans.sort();
+    spans.dedup();
+    spans.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    spans.truncate(FUZZY_MAX_QUERY_WORDS);
+    spans
+}
+
+/// Levenshtein (single-character insert/delete/substitute) edit distance
+/// between `a` and `b`, computed with the standard two-row DP.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (n, m) = (a.len(), b.len());
+
+    let mut prev: Vec<usize> = (0..=m).collect();
+    let mut curr = vec![0usize; m + 1];
+
+    for i in 1..=n {
+        curr[0] = i;
+        for j in 1..=m {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[m]
+}
+
+/// Normalized edit-distance similarity in `0.0..=1.0`: 1.0 for identical
+/// strings, scaled down by [`levenshtein`] distance relative to the longer
+/// string's length, 0.0 for two empty strings (nothing to match).
+fn fuzzy_similarity(a: &str, b: &str) -> f64 {
+    let max_len = a.chars().count().max(b.chars().count());
+    if max_len == 0 {
+        return 0.0;
+    }
+    1.0 - (levenshtein(a, b) as f64 / max_len as f64)
+}
+
+/// Best fuzzy match of `path` against `query_spans`, anchored on the file
+/// stem (path minus directories and extension) rather than arbitrary
+/// interior subsequences — a user names a file by what it's called, not by
+/// its directory or extension. Returns `None` if nothing clears
+/// [`FUZZY_MIN_SIMILARITY`].
+fn fuzzy_match_path(path: &str, query_spans: &[String]) -> Option<(f64, String)> {
+    if query_spans.is_empty() {
+        return None;
+    }
+
+    let stem = Path::new(path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    if stem.is_empty() {
+        return None;
+    }
+
+    let mut best: Option<(f64, String)> = None;
+    for span in query_spans {
+        let similarity = fuzzy_similarity(&stem, span);
+        if similarity >= FUZZY_MIN_SIMILARITY
+            && best.as_ref().map(|(best_sim, _)| similarity > *best_sim).unwrap_or(true)
+        {
+            best = Some((similarity, span.clone()));
+        }
+    }
+    best
+}
+
 fn is_relevant_file_type(path: &str, prompt: &str) -> bool {
     let path_lower = path.to_lowercase();
 
@@ -236,7 +620,22 @@ pub fn extract_mentioned_files(prompt: &str) -> Vec<String> {
 /// // Returns: ["fix", "authentication", "bug", "user_service"]
 /// ```
 pub fn extract_keywords(prompt: &str) -> Vec<String> {
-    let stop_words = [
+    let prompt_lower = prompt.to_lowercase();
+    let words: Vec<String> = prompt_lower
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| w.len() >= 3)
+        .filter(|w| !stopwords::WORDS.contains(w))
+        .map(|s| s.to_string())
+        .collect();
+
+    // Deduplicate while preserving order
+    let mut seen = std::collections::HashSet::new();
+    words.into_iter().filter(|w| seen.insert(w.clone())).collect()
+}
+
+/// Stopword set shared by [`extract_keywords`] and [`extract_key_phrases`].
+pub mod stopwords {
+    pub const WORDS: &[&str] = &[
         "the", "a", "an", "is", "are", "was", "were", "be", "been", "being",
         "have", "has", "had", "do", "does", "did", "will", "would", "could",
         "should", "may", "might", "must", "can", "to", "of", "in", "for",
@@ -251,23 +650,157 @@ pub fn extract_keywords(prompt: &str) -> Vec<String> {
         "her", "they", "them", "their",
     ];
 
-    let prompt_lower = prompt.to_lowercase();
-    let words: Vec<String> = prompt_lower
-        .split(|c: char| !c.is_alphanumeric() && c != '_')
-        .filter(|w| w.len() >= 3)
-        .filter(|w| !stop_words.contains(w))
-        .map(|s| s.to_string())
-        .collect();
+    /// Whether `word` (already lowercased) should split a RAKE candidate
+    /// phrase — either a stopword or a bare number, since numbers rarely
+    /// carry useful topical meaning on their own.
+    pub fn is_delimiter(word: &str) -> bool {
+        WORDS.contains(&word) || word.chars().all(|c| c.is_ascii_digit())
+    }
+}
+
+/// The longest candidate phrase RAKE will consider, in words. Longer runs
+/// of non-stopwords are truncated to this length so a whole unpunctuated
+/// sentence doesn't dominate the ranking as one giant "phrase".
+const RAKE_MAX_PHRASE_WORDS: usize = 4;
+
+/// Extracts weighted multi-word key phrases from a prompt using RAKE
+/// (Rapid Automatic Keyword Extraction).
+///
+/// Unlike [`extract_keywords`], which returns isolated content words, this
+/// keeps adjacent content words together as phrases (e.g. "async
+/// connection pool" rather than "async", "connection", "pool"), which
+/// gives downstream file-relevance scoring a sharper signal to match on.
+///
+/// # Algorithm
+/// 1. Tokenize the prompt and split the token stream into candidate
+///    phrases at every stopword or punctuation delimiter (see
+///    [`stopwords::is_delimiter`]), capping each candidate to
+///    [`RAKE_MAX_PHRASE_WORDS`].
+/// 2. For each word `w`, accumulate `freq[w]` (phrases containing `w`) and
+///    `degree[w]` (sum of the lengths of those phrases).
+/// 3. Score each word as `degree[w] / freq[w]`, and each phrase as the sum
+///    of its words' scores.
+/// 4. Sort phrases by score descending, dedup case-insensitively (keeping
+///    the first-seen casing for display), and return the top N.
+///
+/// # Returns
+/// `(score, phrase)` pairs, highest score first, with original casing
+/// preserved for display.
+pub fn extract_key_phrases(prompt: &str) -> Vec<(f32, String)> {
+    const MAX_PHRASES: usize = 10;
+
+    // `None` marks punctuation between words, which ends a phrase just as a
+    // stopword does.
+    let mut tokens: Vec<Option<&str>> = Vec::new();
+    for chunk in prompt.split_whitespace() {
+        for (i, word) in chunk.split(|c: char| !c.is_alphanumeric() && c != '_').enumerate() {
+            if i > 0 {
+                tokens.push(None);
+            }
+            if !word.is_empty() {
+                tokens.push(Some(word));
+            }
+        }
+    }
+
+    let mut phrases: Vec<Vec<&str>> = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    for token in tokens {
+        let Some(token) = token.filter(|t| !stopwords::is_delimiter(&t.to_lowercase())) else {
+            if !current.is_empty() {
+                phrases.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        current.push(token);
+        if current.len() == RAKE_MAX_PHRASE_WORDS {
+            phrases.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        phrases.push(current);
+    }
+
+    let mut freq: HashMap<String, u32> = HashMap::new();
+    let mut degree: HashMap<String, u32> = HashMap::new();
+    for phrase in &phrases {
+        let len = phrase.len() as u32;
+        let mut counted = std::collections::HashSet::new();
+        for word in phrase {
+            let lower = word.to_lowercase();
+            if counted.insert(lower.clone()) {
+                *freq.entry(lower.clone()).or_insert(0) += 1;
+                *degree.entry(lower).or_insert(0) += len;
+            }
+        }
+    }
+
+    let word_score = |word: &str| -> f32 {
+        let f = *freq.get(word).unwrap_or(&1) as f32;
+        let d = *degree.get(word).unwrap_or(&0) as f32;
+        d / f
+    };
 
-    // Deduplicate while preserving order
     let mut seen = std::collections::HashSet::new();
-    words.into_iter().filter(|w| seen.insert(w.clone())).collect()
+    let mut scored: Vec<(f32, String)> = Vec::new();
+    for phrase in &phrases {
+        let score: f32 = phrase.iter().map(|w| word_score(&w.to_lowercase())).sum();
+        let display = phrase.join(" ");
+        if seen.insert(display.to_lowercase()) {
+            scored.push((score, display));
+        }
+    }
+
+    scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+    scored.truncate(MAX_PHRASES);
+    scored
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_extract_key_phrases_keeps_multi_word_phrases_together() {
+        let phrases = extract_key_phrases("refactor the async connection pool and fix the retry logic");
+        let texts: Vec<&str> = phrases.iter().map(|(_, p)| p.as_str()).collect();
+
+        assert!(texts.contains(&"async connection pool"));
+        assert!(texts.contains(&"retry logic"));
+        // "refactor"/"fix" are their own one-word phrases, split off by stopwords.
+        assert!(texts.contains(&"refactor"));
+        assert!(texts.contains(&"fix"));
+    }
+
+    #[test]
+    fn test_extract_key_phrases_ranks_by_score_descending() {
+        let phrases = extract_key_phrases("async connection pool retry logic and retry logic again");
+        for pair in phrases.windows(2) {
+            assert!(pair[0].0 >= pair[1].0);
+        }
+    }
+
+    #[test]
+    fn test_extract_key_phrases_dedups_case_insensitively() {
+        let phrases =
+            extract_key_phrases("the Connection Pool is broken and the connection pool is slow");
+        let count = phrases.iter().filter(|(_, p)| p.to_lowercase() == "connection pool").count();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_extract_key_phrases_treats_numbers_as_delimiters() {
+        let phrases = extract_key_phrases("retry 3 times then backoff");
+        let texts: Vec<&str> = phrases.iter().map(|(_, p)| p.as_str()).collect();
+        assert!(!texts.iter().any(|p| p.contains('3')));
+    }
+
+    #[test]
+    fn test_extract_key_phrases_caps_phrase_length() {
+        let phrases = extract_key_phrases("async connection pool retry logic timeout handler cleanup");
+        assert!(phrases.iter().all(|(_, p)| p.split_whitespace().count() <= RAKE_MAX_PHRASE_WORDS));
+    }
+
     #[test]
     fn test_extract_keywords() {
         // Test basic keyword extraction
@@ -458,4 +991,42 @@ mod tests {
         assert!(!is_relevant_file_type("src/main.rs", "implement new feature"));
         assert!(!is_relevant_file_type("lib.rs", "add functionality"));
     }
+
+    #[test]
+    fn test_bm25_scores_ranks_exact_term_match_above_no_match() {
+        let candidates = vec!["src/auth/login.rs".to_string(), "src/unrelated/thing.rs".to_string()];
+        let scores = bm25_scores(&candidates, &["auth".to_string(), "login".to_string()]).unwrap();
+
+        assert!(scores[0].0 > scores[1].0);
+        assert_eq!(scores[1].0, 0.0);
+    }
+
+    #[test]
+    fn test_bm25_scores_empty_candidates_returns_empty() {
+        let scores = bm25_scores(&[], &["auth".to_string()]).unwrap();
+        assert!(scores.is_empty());
+    }
+
+    #[test]
+    fn test_bm25_scores_empty_query_terms_scores_everything_zero() {
+        let candidates = vec!["src/auth/login.rs".to_string()];
+        let scores = bm25_scores(&candidates, &[]).unwrap();
+        assert_eq!(scores, vec![(0.0, Vec::new())]);
+    }
+
+    #[test]
+    fn test_bm25_scores_term_absent_from_every_candidate_contributes_nothing() {
+        let candidates = vec!["src/auth/login.rs".to_string(), "src/auth/logout.rs".to_string()];
+        let scores = bm25_scores(&candidates, &["nonexistentterm".to_string()]).unwrap();
+        assert!(scores.iter().all(|(score, terms)| *score == 0.0 && terms.is_empty()));
+    }
+
+    #[test]
+    fn test_bm25_scores_reports_matched_top_terms() {
+        let candidates = vec!["src/auth/login.rs".to_string()];
+        let scores = bm25_scores(&candidates, &["auth".to_string(), "login".to_string()]).unwrap();
+        let matched: Vec<&str> = scores[0].1.iter().map(|(term, _)| term.as_str()).collect();
+        assert!(matched.contains(&"auth"));
+        assert!(matched.contains(&"login"));
+    }
 }
@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Result;
+
+use super::walker;
+
+/// Line-count breakdown of a source file: code, comment, and blank lines.
+///
+/// Unlike [`symbols::LineBreakdown`](super::symbols::LineBreakdown), which
+/// classifies lines from a parsed syntax tree, this is computed with a
+/// tokei-style streaming scan over a comment-syntax table, so it works for
+/// every language in [`comment_syntax_for_language`] without a tree-sitter
+/// grammar or parse step.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LineStats {
+    pub code: usize,
+    pub comments: usize,
+    pub blanks: usize,
+}
+
+impl LineStats {
+    fn add(&mut self, other: LineStats) {
+        self.code += other.code;
+        self.comments += other.comments;
+        self.blanks += other.blanks;
+    }
+}
+
+/// Per-language line statistics for a whole project, keyed by the same
+/// language name strings as [`treesitter::detect_project_type`](super::treesitter::detect_project_type)
+/// (e.g. `"rust"`, `"python"`).
+#[derive(Debug, Clone, Default)]
+pub struct ProjectStats {
+    pub by_language: HashMap<String, LineStats>,
+}
+
+impl ProjectStats {
+    /// Languages and their totals, sorted by code line count descending, so
+    /// callers rendering a budget-limited summary show the dominant
+    /// language(s) first.
+    pub fn by_language_sorted(&self) -> Vec<(&str, LineStats)> {
+        let mut langs: Vec<(&str, LineStats)> =
+            self.by_language.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+        langs.sort_by(|a, b| b.1.code.cmp(&a.1.code));
+        langs
+    }
+}
+
+/// Single-line comment prefixes and multi-line comment delimiter pairs for a
+/// language, used to drive the tokei-style line classifier.
+struct CommentSyntax {
+    line: &'static [&'static str],
+    block: &'static [(&'static str, &'static str)],
+}
+
+/// Comment syntax for every language [`language_for_path`] can detect,
+/// keyed by the same name strings `detect_project_type` uses.
+fn comment_syntax_for_language(language: &str) -> Option<CommentSyntax> {
+    match language {
+        "rust" | "go" | "java" | "c/cpp" | "javascript" | "typescript" => Some(CommentSyntax {
+            line: &["//"],
+            block: &[("/*", "*/")],
+        }),
+        "python" => Some(CommentSyntax {
+            line: &["#"],
+            block: &[("\"\"\"", "\"\"\""), ("'''", "'''")],
+        }),
+        "make" => Some(CommentSyntax {
+            line: &["#"],
+            block: &[],
+        }),
+        _ => None,
+    }
+}
+
+/// Detects a file's language from its extension or filename, using the same
+/// name strings as `detect_project_type` so both can share one comment-syntax
+/// table.
+pub fn language_for_path(path: &Path) -> Option<&'static str> {
+    if path.file_name().and_then(|f| f.to_str()) == Some("Makefile") {
+        return Some("make");
+    }
+
+    match path.extension().and_then(|e| e.to_str())? {
+        "rs" => Some("rust"),
+        "py" | "pyi" => Some("python"),
+        "js" | "jsx" | "mjs" | "cjs" => Some("javascript"),
+        "ts" | "tsx" => Some("typescript"),
+        "go" => Some("go"),
+        "java" => Some("java"),
+        "c" | "h" | "cpp" | "hpp" | "cc" | "cxx" | "hh" => Some("c/cpp"),
+        _ => None,
+    }
+}
+
+/// Tracks whether a multi-line comment delimiter is still open going into
+/// the next line, and which pair opened it (so a nested/stacked open of the
+/// *same* pair, e.g. Rust's `/* /* */ */`, is tracked by depth rather than
+/// closing early on the first `*/`).
+#[derive(Default)]
+struct BlockCommentState {
+    active_pair: Option<usize>,
+    depth: usize,
+}
+
+/// Classifies every line of `source` as code, comment, or blank using
+/// `syntax`'s line-comment prefixes and block-comment delimiter pairs.
+///
+/// A line with no non-whitespace bytes is blank. Otherwise, a line is code
+/// if any of its bytes fall outside every comment span (so `let x = 1; //
+/// note` counts as code, matching tools like `tokei`), and a comment
+/// otherwise. Block-comment state carries across lines, and reopening the
+/// active pair before it closes (nesting) is tracked by depth rather than
+/// unwound on the first closing delimiter.
+fn classify_lines(source: &str, syntax: &CommentSyntax) -> LineStats {
+    let mut stats = LineStats::default();
+    let mut state = BlockCommentState::default();
+
+    for line in source.lines() {
+        if line.trim().is_empty() {
+            stats.blanks += 1;
+            continue;
+        }
+
+        let (has_code, has_comment) = scan_line(line, syntax, &mut state);
+        if has_code {
+            stats.code += 1;
+        } else if has_comment {
+            stats.comments += 1;
+        } else {
+            stats.code += 1;
+        }
+    }
+
+    stats
+}
+
+/// Scans a single non-blank line against `syntax`, advancing `state` across
+/// the line's open/close delimiters. Returns `(has_code, has_comment)`.
+fn scan_line(line: &str, syntax: &CommentSyntax, state: &mut BlockCommentState) -> (bool, bool) {
+    let mut has_code = false;
+    let mut has_comment = false;
+    let mut pos = 0;
+
+    while pos < line.len() {
+        if let Some(pair_idx) = state.active_pair {
+            let (open, close) = syntax.block[pair_idx];
+            let rest = &line[pos..];
+            let open_pos = if open != close { rest.find(open) } else { None };
+            let close_pos = rest.find(close);
+
+            match (open_pos, close_pos) {
+                (Some(o), Some(c)) if o < c => {
+                    has_comment = true;
+                    state.depth += 1;
+                    pos += o + open.len();
+                }
+                (_, Some(c)) => {
+                    has_comment = true;
+                    state.depth -= 1;
+                    pos += c + close.len();
+                    if state.depth == 0 {
+                        state.active_pair = None;
+                    }
+                }
+                _ => {
+                    has_comment = true;
+                    break;
+                }
+            }
+            continue;
+        }
+
+        let rest = &line[pos..];
+        let mut earliest: Option<(usize, Option<usize>)> = None;
+
+        for lc in syntax.line {
+            if let Some(p) = rest.find(lc) {
+                if earliest.is_none_or(|(ep, _)| p < ep) {
+                    earliest = Some((p, None));
+                }
+            }
+        }
+        for (idx, (open, _)) in syntax.block.iter().enumerate() {
+            if let Some(p) = rest.find(open) {
+                if earliest.is_none_or(|(ep, _)| p < ep) {
+                    earliest = Some((p, Some(idx)));
+                }
+            }
+        }
+
+        match earliest {
+            None => {
+                if !rest.trim().is_empty() {
+                    has_code = true;
+                }
+                break;
+            }
+            Some((p, None)) => {
+                if !rest[..p].trim().is_empty() {
+                    has_code = true;
+                }
+                has_comment = true;
+                break;
+            }
+            Some((p, Some(idx))) => {
+                if !rest[..p].trim().is_empty() {
+                    has_code = true;
+                }
+                has_comment = true;
+                let (open, _) = syntax.block[idx];
+                state.active_pair = Some(idx);
+                state.depth = 1;
+                pos += p + open.len();
+            }
+        }
+    }
+
+    (has_code, has_comment)
+}
+
+/// Computes [`LineStats`] for a single file, or `None` if its language has
+/// no entry in [`comment_syntax_for_language`].
+pub fn stats_for_file(path: &Path) -> Result<Option<LineStats>> {
+    let Some(language) = language_for_path(path) else {
+        return Ok(None);
+    };
+    let Some(syntax) = comment_syntax_for_language(language) else {
+        return Ok(None);
+    };
+
+    let source = std::fs::read_to_string(path)?;
+    Ok(Some(classify_lines(&source, &syntax)))
+}
+
+/// Walks `root` (respecting `.gitignore`, the walker's default ignores, and
+/// any `excludes` globs) and aggregates [`LineStats`] per language, for a
+/// project-wide tokei-style summary.
+pub fn collect_project_stats(root: &Path, excludes: &[String]) -> ProjectStats {
+    let mut project = ProjectStats::default();
+    let file_walker = walker::create_walker_with_extra_ignores(root, excludes).build();
+
+    for entry in file_walker.flatten() {
+        if !entry.path().is_file() {
+            continue;
+        }
+        let Some(language) = language_for_path(entry.path()) else {
+            continue;
+        };
+        let Some(syntax) = comment_syntax_for_language(language) else {
+            continue;
+        };
+        let Ok(source) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+
+        project
+            .by_language
+            .entry(language.to_string())
+            .or_default()
+            .add(classify_lines(&source, &syntax));
+    }
+
+    project
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rust_syntax() -> CommentSyntax {
+        comment_syntax_for_language("rust").unwrap()
+    }
+
+    #[test]
+    fn test_classify_blank_and_code() {
+        let stats = classify_lines("fn main() {}\n\n    \n", &rust_syntax());
+        assert_eq!(stats.code, 1);
+        assert_eq!(stats.blanks, 2);
+        assert_eq!(stats.comments, 0);
+    }
+
+    #[test]
+    fn test_classify_line_comment() {
+        let stats = classify_lines("// a note\nlet x = 1;\n", &rust_syntax());
+        assert_eq!(stats.comments, 1);
+        assert_eq!(stats.code, 1);
+    }
+
+    #[test]
+    fn test_code_with_trailing_comment_counts_as_code() {
+        let stats = classify_lines("let x = 1; // note\n", &rust_syntax());
+        assert_eq!(stats.code, 1);
+        assert_eq!(stats.comments, 0);
+    }
+
+    #[test]
+    fn test_multi_line_block_comment() {
+        let source = "/* start\nstill going\nend */\nlet x = 1;\n";
+        let stats = classify_lines(source, &rust_syntax());
+        assert_eq!(stats.comments, 3);
+        assert_eq!(stats.code, 1);
+    }
+
+    #[test]
+    fn test_nested_block_comment() {
+        // The inner `/*` should push depth to 2, so the first `*/` only
+        // closes the inner comment, not the whole thing.
+        let source = "/* outer /* inner */ still in outer */\nlet x = 1;\n";
+        let stats = classify_lines(source, &rust_syntax());
+        assert_eq!(stats.comments, 1);
+        assert_eq!(stats.code, 1);
+    }
+
+    #[test]
+    fn test_python_triple_quote_docstring() {
+        let syntax = comment_syntax_for_language("python").unwrap();
+        let source = "def f():\n    \"\"\"Docstring.\n    More.\n    \"\"\"\n    return 1\n";
+        let stats = classify_lines(source, &syntax);
+        assert_eq!(stats.code, 2);
+        assert_eq!(stats.comments, 3);
+    }
+
+    #[test]
+    fn test_language_for_path() {
+        assert_eq!(language_for_path(Path::new("src/main.rs")), Some("rust"));
+        assert_eq!(language_for_path(Path::new("script.py")), Some("python"));
+        assert_eq!(language_for_path(Path::new("Makefile")), Some("make"));
+        assert_eq!(language_for_path(Path::new("README.md")), None);
+    }
+}
@@ -0,0 +1,331 @@
+//! A fuzzy-searchable, project-wide index of [`Symbol`]s.
+//!
+//! [`extract_symbols`](super::symbols::extract_symbols) only sees one file
+//! at a time, so there's no way to ask "where is `parseReq` defined?"
+//! across a whole project without re-walking and re-parsing everything.
+//! [`SymbolIndex`] aggregates symbols from every indexed file, tagging
+//! each with its originating path, and builds an `fst::Map` over their
+//! lowercased names so approximate queries still turn up the right symbol:
+//! a name matches when it contains the query's letters in order (`preq`
+//! finds `parseReq`) or is within a small edit distance of the query (one
+//! or two typos, depending on the query's length).
+//!
+//! The `fst::Map` itself is immutable once built, so [`SymbolIndex::index_file`]
+//! only re-extracts symbols for the one file that changed and marks the
+//! map stale; it's rebuilt from the in-memory postings table (cheap — no
+//! re-parsing) the next time [`SymbolIndex::search`] runs.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use fst::automaton::{Automaton, Levenshtein, Subsequence};
+use fst::{IntoStreamer, Map, MapBuilder, Streamer};
+
+use super::symbols::{self, Symbol, SymbolKind};
+use super::treesitter::{self, LanguageRegistry};
+
+/// A [`Symbol`] together with the file it was parsed from.
+#[derive(Debug, Clone)]
+pub struct IndexedSymbol {
+    pub symbol: Symbol,
+    pub file: PathBuf,
+}
+
+/// A single fuzzy-search hit: the matched symbol plus how close the
+/// match was to the query.
+#[derive(Debug, Clone)]
+pub struct SymbolMatch {
+    pub symbol: Symbol,
+    pub file: PathBuf,
+    pub distance: u32,
+    /// Relevance score from [`match_score`]; higher means more relevant.
+    /// Results are sorted by this descending.
+    pub score: f32,
+}
+
+/// Fuzzy-searchable index of every symbol in a project, backed by an
+/// `fst::Map` keyed on lowercased symbol name.
+///
+/// Build it by calling [`index_file`](Self::index_file) for each source
+/// file (see `ctx find`), then [`search`](Self::search) as many times as
+/// needed. Re-indexing a changed file only touches that file's entries.
+#[derive(Default)]
+pub struct SymbolIndex {
+    /// Every indexed symbol, keyed by a stable id assigned at insertion.
+    symbols: HashMap<u64, IndexedSymbol>,
+    /// Ids contributed by each file, so a file can be re-indexed without
+    /// touching any other file's entries.
+    files: HashMap<PathBuf, Vec<u64>>,
+    /// All symbol ids sharing a given lowercased name. The `fst::Map` can
+    /// only store one value per key, so the full id list lives here and
+    /// is looked up once a name matches.
+    postings: HashMap<String, Vec<u64>>,
+    next_id: u64,
+    /// Lazily (re)built from `postings`; `None` after a mutation until
+    /// the next `search` rebuilds it.
+    map: Option<Map<Vec<u8>>>,
+}
+
+impl SymbolIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// (Re-)indexes a single file: drops any symbols previously
+    /// contributed by `path`, re-parses it, and adds the freshly
+    /// extracted symbols. Other files' entries are untouched.
+    pub fn index_file(&mut self, path: &Path, registry: &LanguageRegistry) -> Result<()> {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let Some(lang) = registry.detect(path, source.lines().next()) else {
+            self.load_symbols(path, Vec::new());
+            return Ok(());
+        };
+        let Some(tree) = treesitter::parse_with_language(&source, &lang)? else {
+            self.load_symbols(path, Vec::new());
+            return Ok(());
+        };
+
+        self.load_symbols(path, symbols::extract_symbols(&tree, &source, &lang));
+        Ok(())
+    }
+
+    /// (Re-)indexes a single file from already-extracted symbols, skipping
+    /// the read-and-parse step [`index_file`](Self::index_file) does — used
+    /// by the on-disk symbol cache to restore a file's entries without
+    /// reparsing files whose mtime hasn't changed.
+    pub fn load_symbols(&mut self, path: &Path, symbols: Vec<Symbol>) {
+        self.remove_file(path);
+
+        let mut ids = Vec::new();
+        for symbol in symbols {
+            let id = self.next_id;
+            self.next_id += 1;
+            self.postings
+                .entry(symbol.name.to_lowercase())
+                .or_default()
+                .push(id);
+            self.symbols.insert(
+                id,
+                IndexedSymbol {
+                    symbol,
+                    file: path.to_path_buf(),
+                },
+            );
+            ids.push(id);
+        }
+
+        self.files.insert(path.to_path_buf(), ids);
+        self.map = None;
+    }
+
+    /// Drops every symbol previously indexed for `path`, if any.
+    fn remove_file(&mut self, path: &Path) {
+        let Some(ids) = self.files.remove(path) else {
+            return;
+        };
+
+        for id in ids {
+            if let Some(indexed) = self.symbols.remove(&id) {
+                let name_lower = indexed.symbol.name.to_lowercase();
+                if let Some(remaining) = self.postings.get_mut(&name_lower) {
+                    remaining.retain(|existing| *existing != id);
+                    if remaining.is_empty() {
+                        self.postings.remove(&name_lower);
+                    }
+                }
+            }
+        }
+        self.map = None;
+    }
+
+    /// Rebuilds the `fst::Map` from the current postings table, if stale.
+    fn ensure_built(&mut self) -> Result<()> {
+        if self.map.is_some() {
+            return Ok(());
+        }
+
+        let mut names: Vec<&String> = self.postings.keys().collect();
+        names.sort();
+
+        let mut builder = MapBuilder::memory();
+        for name in names {
+            // The value isn't used for retrieval (the matched key already
+            // gives us the name); store the lowest id as a stable,
+            // otherwise-unused tiebreaker.
+            let representative = self.postings[name].iter().copied().min().unwrap_or(0);
+            builder.insert(name, representative)?;
+        }
+
+        let bytes = builder.into_inner()?;
+        self.map = Some(Map::new(bytes)?);
+        Ok(())
+    }
+
+    /// Fuzzy-searches the index for `query`, returning up to `limit`
+    /// matches ranked by descending [`match_score`] (ties broken by symbol
+    /// kind — types/functions before methods/variables).
+    pub fn search(&mut self, query: &str, limit: usize) -> Result<Vec<SymbolMatch>> {
+        self.ensure_built()?;
+        let map = self.map.as_ref().expect("ensure_built just populated this");
+
+        let query_lower = query.to_lowercase();
+        let max_distance = if query_lower.chars().count() <= 4 { 1 } else { 2 };
+
+        let subsequence = Subsequence::new(&query_lower);
+        let levenshtein = Levenshtein::new(&query_lower, max_distance)
+            .with_context(|| format!("query too large for fuzzy matching: {query:?}"))?;
+        let automaton = subsequence.union(levenshtein);
+
+        let mut matches = Vec::new();
+        let mut stream = map.search(automaton).into_stream();
+        while let Some((key, _value)) = stream.next() {
+            let name = String::from_utf8_lossy(key).into_owned();
+            let distance = edit_distance(&query_lower, &name) as u32;
+            let Some(ids) = self.postings.get(&name) else {
+                continue;
+            };
+            for &id in ids {
+                if let Some(indexed) = self.symbols.get(&id) {
+                    matches.push(SymbolMatch {
+                        score: match_score(&indexed.symbol.name, query, distance),
+                        symbol: indexed.symbol.clone(),
+                        file: indexed.file.clone(),
+                        distance,
+                    });
+                }
+            }
+        }
+
+        matches.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| kind_priority(a.symbol.kind).cmp(&kind_priority(b.symbol.kind)))
+        });
+        matches.truncate(limit);
+
+        Ok(matches)
+    }
+
+    /// Finds the single closest indexed symbol name to `query` by edit
+    /// distance, for a "did you mean" suggestion when a search comes back
+    /// empty. Scans every indexed name rather than the `fst::Map`'s
+    /// bounded-distance automaton, since a query with no matches within
+    /// the automaton's radius is exactly the case this is for.
+    pub fn suggest(&self, query: &str) -> Option<String> {
+        let query_lower = query.to_lowercase();
+        self.postings
+            .keys()
+            .min_by_key(|name| edit_distance(&query_lower, name))
+            .cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+/// Scores how relevant `name` is to `query`, for ranking fuzzy-search
+/// results the way an editor's symbol picker would rather than dumping
+/// every hit in walk order: greedily aligns `query`'s characters against
+/// `name` (case-insensitively), rewarding a contiguous run of matched
+/// characters and matches that land on a word/camelCase/snake_case
+/// boundary, penalizing the gaps the alignment has to skip over, adding a
+/// flat bonus for an outright prefix match, and finally subtracting the
+/// already-computed edit `distance` so near-exact names still win over
+/// looser alignments with a similar shape.
+fn match_score(name: &str, query: &str, distance: u32) -> f32 {
+    let name_chars: Vec<char> = name.chars().collect();
+    let name_lower: Vec<char> = name.to_lowercase().chars().collect();
+    let query_lower: Vec<char> = query.to_lowercase().chars().collect();
+
+    let mut score = 0.0f32;
+    let mut qi = 0;
+    let mut last_match: Option<usize> = None;
+    let mut run_len = 0u32;
+
+    for (ni, &nc) in name_lower.iter().enumerate() {
+        if qi >= query_lower.len() {
+            break;
+        }
+        if nc != query_lower[qi] {
+            continue;
+        }
+
+        let is_boundary = ni == 0
+            || matches!(name_chars[ni - 1], '_' | '-' | '.')
+            || (name_chars[ni].is_uppercase() && !name_chars[ni - 1].is_uppercase());
+        if is_boundary {
+            score += 3.0;
+        }
+
+        match last_match {
+            Some(prev) if prev + 1 == ni => {
+                run_len += 1;
+                score += 2.0 * run_len as f32;
+            }
+            Some(prev) => {
+                score -= (ni - prev - 1) as f32 * 0.5;
+                run_len = 0;
+            }
+            None => {
+                score -= ni as f32 * 0.2;
+            }
+        }
+
+        last_match = Some(ni);
+        qi += 1;
+    }
+
+    if name.to_lowercase().starts_with(&query.to_lowercase()) {
+        score += 5.0;
+    }
+
+    score - distance as f32 * 1.5
+}
+
+/// Lower sorts first: types and functions are usually what a name search
+/// is after, so they outrank methods/modules, then consts/variables, then
+/// fields/variants, at the same [`match_score`].
+fn kind_priority(kind: SymbolKind) -> u8 {
+    match kind {
+        SymbolKind::Struct
+        | SymbolKind::Class
+        | SymbolKind::Enum
+        | SymbolKind::Interface
+        | SymbolKind::Trait
+        | SymbolKind::Type
+        | SymbolKind::Function => 0,
+        SymbolKind::Method | SymbolKind::Module | SymbolKind::Impl => 1,
+        SymbolKind::Const | SymbolKind::Variable | SymbolKind::Import => 2,
+        SymbolKind::Field | SymbolKind::Variant => 3,
+    }
+}
+
+/// Classic Levenshtein edit distance between two strings, used to rank
+/// `fst` matches precisely (the automaton only says whether a name
+/// matched, it doesn't expose the actual distance of a match).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+
+    for (i, &ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
@@ -1,4 +1,6 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use tree_sitter::{Node, Tree};
 
 use super::treesitter::SupportedLanguage;
@@ -15,8 +17,12 @@ use super::treesitter::SupportedLanguage;
 /// * `kind` - The type of symbol (function, class, etc.)
 /// * `line` - The 1-indexed line number where the symbol is defined
 /// * `signature` - Optional function/method signature (for callable symbols)
-/// * `doc_comment` - Optional documentation comment extracted from source
-#[derive(Debug, Clone, Serialize)]
+/// * `doc_summary` - Optional first line of the symbol's documentation
+/// * `doc_full` - Optional full text of the symbol's documentation
+/// * `parsed_signature` - Optional structured breakdown of `signature`
+/// * `parent` - Optional name of the type this symbol is a member of
+///   (a struct/enum field, or a TS interface member)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Symbol {
     /// The identifier name of the symbol (e.g., function name, class name).
     pub name: String,
@@ -27,9 +33,52 @@ pub struct Symbol {
     /// The function or method signature, if applicable. Contains the full
     /// declaration line up to (but not including) the body.
     pub signature: Option<String>,
+    /// First line of [`doc_full`](Symbol::doc_full), for compact display
+    /// (e.g. skeleton output).
+    pub doc_summary: Option<String>,
     /// Documentation comment extracted from the source, if present.
-    /// For Rust, this is `///` or `//!` comments. For Python, docstrings.
-    pub doc_comment: Option<String>,
+    /// For Rust, this is `///`/`//!` or `/** */` comments. For Python,
+    /// docstrings. For JS/TS, `/** */` JSDoc blocks.
+    pub doc_full: Option<String>,
+    /// Structured, tree-sitter-field-aware breakdown of `signature` for
+    /// callable symbols — correct for default arguments containing braces,
+    /// multiline declarations, and return types the naive brace/colon
+    /// slice used by `signature` gets wrong. `None` for non-callable
+    /// symbols and for languages without a dedicated parser yet.
+    pub parsed_signature: Option<Signature>,
+    /// The owning type's name, for a symbol nested inside a struct, enum,
+    /// or interface (a field, variant, or member) — `None` for symbols that
+    /// stand on their own, including top-level types and impl/class methods.
+    pub parent: Option<String>,
+}
+
+/// A single function/method parameter, as parsed from its declaration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
+pub struct Param {
+    /// The parameter's binding name (e.g. `self`, `name`, `*args`).
+    pub name: String,
+    /// The parameter's declared type, if the language/declaration has one.
+    pub ty: Option<String>,
+    /// The parameter's default value expression, as source text, if any
+    /// (Python `= value`, JS/TS `= value`). Rust has no default parameters.
+    pub default: Option<String>,
+}
+
+/// A structured function/method signature, built by walking the named
+/// fields of a tree-sitter function node rather than slicing source text
+/// up to its first `{`/`:` — see [`Symbol::parsed_signature`] for why that
+/// matters.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct Signature {
+    /// Visibility modifier text (e.g. `pub`, `pub(crate)`), Rust only.
+    pub visibility: Option<String>,
+    /// Generic parameter list text (e.g. `<T: Clone>`), Rust only.
+    pub generics: Option<String>,
+    pub name: String,
+    pub params: Vec<Param>,
+    /// The declared return type, if any, without its leading `->`/`:`.
+    pub return_type: Option<String>,
+    pub is_async: bool,
 }
 
 /// Classification of code symbols by their semantic role.
@@ -40,7 +89,7 @@ pub struct Symbol {
 ///
 /// # Serialization
 /// Variants are serialized to lowercase strings (e.g., `Function` -> `"function"`).
-#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum SymbolKind {
     /// A standalone function (not associated with a type).
@@ -65,9 +114,19 @@ pub enum SymbolKind {
     Type,
     /// A module declaration (Rust `mod` items).
     Module,
-    /// An import statement (currently unused but reserved for future use).
-    #[allow(dead_code)]
+    /// An import or re-export statement (`use`, `import`, `from ... import`).
+    /// `name` is the imported path; `signature` holds the bound name(s) or
+    /// alias. See [`extract_symbols`].
     Import,
+    /// A Rust `impl` block. Only produced by [`extract_symbol_tree`], as a
+    /// container for its methods — the flat [`extract_symbols`] has no
+    /// equivalent, since impl blocks contribute their methods directly.
+    Impl,
+    /// A struct/tuple-struct field, a class attribute, or a TS interface
+    /// member. `parent` holds the owning type's name.
+    Field,
+    /// A Rust enum variant. `parent` holds the owning enum's name.
+    Variant,
 }
 
 impl std::fmt::Display for SymbolKind {
@@ -85,6 +144,9 @@ impl std::fmt::Display for SymbolKind {
             SymbolKind::Type => write!(f, "type"),
             SymbolKind::Module => write!(f, "mod"),
             SymbolKind::Import => write!(f, "import"),
+            SymbolKind::Impl => write!(f, "impl"),
+            SymbolKind::Field => write!(f, "field"),
+            SymbolKind::Variant => write!(f, "variant"),
         }
     }
 }
@@ -119,8 +181,14 @@ pub fn extract_symbols(tree: &Tree, source: &str, lang: &SupportedLanguage) -> V
         SupportedLanguage::JavaScript | SupportedLanguage::TypeScript => {
             extract_js_symbols(&root, source, &mut symbols)
         }
+        // Dynamically loaded grammars don't have hand-written query logic yet;
+        // they still parse and can be searched/skeletonized generically.
+        SupportedLanguage::Dynamic(_) => {}
     }
 
+    // Fields, variants and class attributes are collected before the type
+    // that owns them; restore file order, owner first on a shared line.
+    symbols.sort_by_key(|s| (s.line, s.parent.is_some()));
     symbols
 }
 
@@ -133,58 +201,91 @@ fn extract_rust_symbols(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
                 if let Some(name_node) = child.child_by_field_name("name") {
                     let name = get_text(&name_node, source);
                     let signature = get_function_signature(&child, source);
-                    let doc = get_preceding_doc_comment(&child, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Rust,
+                    ));
                     symbols.push(Symbol {
                         name,
                         kind: SymbolKind::Function,
                         line: child.start_position().row + 1,
                         signature: Some(signature),
-                        doc_comment: doc,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: Some(parse_rust_signature(&child, source)),
+                        parent: None,
                     });
                 }
             }
             "struct_item" => {
                 if let Some(name_node) = child.child_by_field_name("name") {
                     let name = get_text(&name_node, source);
-                    let doc = get_preceding_doc_comment(&child, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Rust,
+                    ));
+                    extract_rust_struct_fields(&child, &name, source, symbols);
                     symbols.push(Symbol {
                         name,
                         kind: SymbolKind::Struct,
                         line: child.start_position().row + 1,
                         signature: None,
-                        doc_comment: doc,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
                     });
                 }
             }
             "enum_item" => {
                 if let Some(name_node) = child.child_by_field_name("name") {
                     let name = get_text(&name_node, source);
-                    let doc = get_preceding_doc_comment(&child, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Rust,
+                    ));
+                    extract_rust_enum_variants(&child, &name, source, symbols);
                     symbols.push(Symbol {
                         name,
                         kind: SymbolKind::Enum,
                         line: child.start_position().row + 1,
                         signature: None,
-                        doc_comment: doc,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
                     });
                 }
             }
             "trait_item" => {
                 if let Some(name_node) = child.child_by_field_name("name") {
                     let name = get_text(&name_node, source);
-                    let doc = get_preceding_doc_comment(&child, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Rust,
+                    ));
                     symbols.push(Symbol {
                         name,
                         kind: SymbolKind::Trait,
                         line: child.start_position().row + 1,
                         signature: None,
-                        doc_comment: doc,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
                     });
                 }
             }
             "impl_item" => {
                 extract_rust_impl_methods(&child, source, symbols);
             }
+            "use_declaration" => {
+                extract_rust_use(&child, source, symbols);
+            }
             "const_item" => {
                 if let Some(name_node) = child.child_by_field_name("name") {
                     let name = get_text(&name_node, source);
@@ -193,7 +294,10 @@ fn extract_rust_symbols(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
                         kind: SymbolKind::Const,
                         line: child.start_position().row + 1,
                         signature: None,
-                        doc_comment: None,
+                        doc_summary: None,
+                        doc_full: None,
+                        parsed_signature: None,
+                        parent: None,
                     });
                 }
             }
@@ -205,7 +309,10 @@ fn extract_rust_symbols(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
                         kind: SymbolKind::Type,
                         line: child.start_position().row + 1,
                         signature: None,
-                        doc_comment: None,
+                        doc_summary: None,
+                        doc_full: None,
+                        parsed_signature: None,
+                        parent: None,
                     });
                 }
             }
@@ -217,7 +324,10 @@ fn extract_rust_symbols(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
                         kind: SymbolKind::Module,
                         line: child.start_position().row + 1,
                         signature: None,
-                        doc_comment: None,
+                        doc_summary: None,
+                        doc_full: None,
+                        parsed_signature: None,
+                        parent: None,
                     });
                 }
             }
@@ -238,13 +348,20 @@ fn extract_rust_impl_methods(node: &Node, source: &str, symbols: &mut Vec<Symbol
                     if let Some(name_node) = item.child_by_field_name("name") {
                         let name = get_text(&name_node, source);
                         let signature = get_function_signature(&item, source);
-                        let doc = get_preceding_doc_comment(&item, source);
+                        let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                            &item,
+                            source,
+                            &SupportedLanguage::Rust,
+                        ));
                         symbols.push(Symbol {
                             name,
                             kind: SymbolKind::Method,
                             line: item.start_position().row + 1,
                             signature: Some(signature),
-                            doc_comment: doc,
+                            doc_summary,
+                            doc_full,
+                            parsed_signature: Some(parse_rust_signature(&item, source)),
+                            parent: None,
                         });
                     }
                 }
@@ -253,6 +370,94 @@ fn extract_rust_impl_methods(node: &Node, source: &str, symbols: &mut Vec<Symbol
     }
 }
 
+/// Emits a [`SymbolKind::Field`] for each named field of a struct and each
+/// positional field of a tuple struct (named by index, since that's the
+/// only handle a tuple-struct field has).
+fn extract_rust_struct_fields(node: &Node, parent_name: &str, source: &str, symbols: &mut Vec<Symbol>) {
+    let Some(body) = node.child_by_field_name("body") else {
+        return;
+    };
+
+    match body.kind() {
+        "field_declaration_list" => {
+            let mut cursor = body.walk();
+            for field in body.children(&mut cursor) {
+                if field.kind() != "field_declaration" {
+                    continue;
+                }
+                let Some(name_node) = field.child_by_field_name("name") else {
+                    continue;
+                };
+                let name = get_text(&name_node, source);
+                let signature = field.child_by_field_name("type").map(|n| get_text(&n, source));
+                let (doc_summary, doc_full) =
+                    doc_fields(get_doc_comment(&field, source, &SupportedLanguage::Rust));
+                symbols.push(Symbol {
+                    name,
+                    kind: SymbolKind::Field,
+                    line: field.start_position().row + 1,
+                    signature,
+                    doc_summary,
+                    doc_full,
+                    parsed_signature: None,
+                    parent: Some(parent_name.to_string()),
+                });
+            }
+        }
+        "ordered_field_declaration_list" => {
+            let mut cursor = body.walk();
+            let fields = body.children(&mut cursor).filter(|n| n.kind() == "ordered_field_declaration");
+            for (index, field) in fields.enumerate() {
+                let signature = field.child_by_field_name("type").map(|n| get_text(&n, source));
+                let (doc_summary, doc_full) =
+                    doc_fields(get_doc_comment(&field, source, &SupportedLanguage::Rust));
+                symbols.push(Symbol {
+                    name: index.to_string(),
+                    kind: SymbolKind::Field,
+                    line: field.start_position().row + 1,
+                    signature,
+                    doc_summary,
+                    doc_full,
+                    parsed_signature: None,
+                    parent: Some(parent_name.to_string()),
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Emits a [`SymbolKind::Variant`] for each member of an enum's variant
+/// list.
+fn extract_rust_enum_variants(node: &Node, parent_name: &str, source: &str, symbols: &mut Vec<Symbol>) {
+    let Some(body) = node.child_by_field_name("body") else {
+        return;
+    };
+
+    let mut cursor = body.walk();
+    for variant in body.children(&mut cursor) {
+        if variant.kind() != "enum_variant" {
+            continue;
+        }
+        let Some(name_node) = variant.child_by_field_name("name") else {
+            continue;
+        };
+        let name = get_text(&name_node, source);
+        let (doc_summary, doc_full) =
+            doc_fields(get_doc_comment(&variant, source, &SupportedLanguage::Rust));
+        symbols.push(Symbol {
+            name,
+            kind: SymbolKind::Variant,
+            line: variant.start_position().row + 1,
+            signature: None,
+            doc_summary,
+            doc_full,
+            parsed_signature: None,
+            parent: Some(parent_name.to_string()),
+        });
+    }
+}
+
 fn extract_python_symbols(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
     let mut cursor = node.walk();
 
@@ -262,30 +467,48 @@ fn extract_python_symbols(node: &Node, source: &str, symbols: &mut Vec<Symbol>)
                 if let Some(name_node) = child.child_by_field_name("name") {
                     let name = get_text(&name_node, source);
                     let signature = get_python_function_signature(&child, source);
-                    let doc = get_python_docstring(&child, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Python,
+                    ));
                     symbols.push(Symbol {
                         name,
                         kind: SymbolKind::Function,
                         line: child.start_position().row + 1,
                         signature: Some(signature),
-                        doc_comment: doc,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: Some(parse_python_signature(&child, source)),
+                        parent: None,
                     });
                 }
             }
             "class_definition" => {
                 if let Some(name_node) = child.child_by_field_name("name") {
                     let name = get_text(&name_node, source);
-                    let doc = get_python_docstring(&child, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Python,
+                    ));
+                    extract_python_class_attributes(&child, &name, source, symbols);
                     symbols.push(Symbol {
                         name,
                         kind: SymbolKind::Class,
                         line: child.start_position().row + 1,
                         signature: None,
-                        doc_comment: doc,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
                     });
                 }
                 extract_python_class_methods(&child, source, symbols);
             }
+            "import_statement" | "import_from_statement" => {
+                extract_python_import(&child, source, symbols);
+            }
             _ => {
                 extract_python_symbols(&child, source, symbols);
             }
@@ -303,13 +526,20 @@ fn extract_python_class_methods(node: &Node, source: &str, symbols: &mut Vec<Sym
                     if let Some(name_node) = item.child_by_field_name("name") {
                         let name = get_text(&name_node, source);
                         let signature = get_python_function_signature(&item, source);
-                        let doc = get_python_docstring(&item, source);
+                        let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                            &item,
+                            source,
+                            &SupportedLanguage::Python,
+                        ));
                         symbols.push(Symbol {
                             name,
                             kind: SymbolKind::Method,
                             line: item.start_position().row + 1,
                             signature: Some(signature),
-                            doc_comment: doc,
+                            doc_summary,
+                            doc_full,
+                            parsed_signature: Some(parse_python_signature(&item, source)),
+                            parent: None,
                         });
                     }
                 }
@@ -318,6 +548,48 @@ fn extract_python_class_methods(node: &Node, source: &str, symbols: &mut Vec<Sym
     }
 }
 
+/// Emits a [`SymbolKind::Field`] for each attribute assigned directly in a
+/// class body (`name = ...` or `name: Type = ...`) — not attributes only
+/// set on `self` inside `__init__` or other methods, which aren't visible
+/// without evaluating the method bodies.
+fn extract_python_class_attributes(node: &Node, parent_name: &str, source: &str, symbols: &mut Vec<Symbol>) {
+    let Some(body) = node.child_by_field_name("body") else {
+        return;
+    };
+
+    let mut cursor = body.walk();
+    for item in body.children(&mut cursor) {
+        if item.kind() != "expression_statement" {
+            continue;
+        }
+        let Some(assignment) = item.named_child(0) else {
+            continue;
+        };
+        if assignment.kind() != "assignment" {
+            continue;
+        }
+        let Some(left) = assignment.child_by_field_name("left") else {
+            continue;
+        };
+        if left.kind() != "identifier" {
+            continue;
+        }
+
+        let name = get_text(&left, source);
+        let signature = assignment.child_by_field_name("type").map(|n| get_text(&n, source));
+        symbols.push(Symbol {
+            name,
+            kind: SymbolKind::Field,
+            line: item.start_position().row + 1,
+            signature,
+            doc_summary: None,
+            doc_full: None,
+            parsed_signature: None,
+            parent: Some(parent_name.to_string()),
+        });
+    }
+}
+
 fn extract_js_symbols(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
     let mut cursor = node.walk();
 
@@ -327,24 +599,40 @@ fn extract_js_symbols(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
                 if let Some(name_node) = child.child_by_field_name("name") {
                     let name = get_text(&name_node, source);
                     let signature = get_js_function_signature(&child, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::JavaScript,
+                    ));
                     symbols.push(Symbol {
                         name,
                         kind: SymbolKind::Function,
                         line: child.start_position().row + 1,
                         signature: Some(signature),
-                        doc_comment: None,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: Some(parse_js_signature(&child, source)),
+                        parent: None,
                     });
                 }
             }
             "class_declaration" => {
                 if let Some(name_node) = child.child_by_field_name("name") {
                     let name = get_text(&name_node, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::JavaScript,
+                    ));
                     symbols.push(Symbol {
                         name,
                         kind: SymbolKind::Class,
                         line: child.start_position().row + 1,
                         signature: None,
-                        doc_comment: None,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
                     });
                 }
                 extract_js_class_methods(&child, source, symbols);
@@ -352,31 +640,55 @@ fn extract_js_symbols(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
             "interface_declaration" => {
                 if let Some(name_node) = child.child_by_field_name("name") {
                     let name = get_text(&name_node, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::JavaScript,
+                    ));
+                    extract_ts_interface_members(&child, &name, source, symbols);
                     symbols.push(Symbol {
                         name,
                         kind: SymbolKind::Interface,
                         line: child.start_position().row + 1,
                         signature: None,
-                        doc_comment: None,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
                     });
                 }
             }
             "type_alias_declaration" => {
                 if let Some(name_node) = child.child_by_field_name("name") {
                     let name = get_text(&name_node, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::JavaScript,
+                    ));
                     symbols.push(Symbol {
                         name,
                         kind: SymbolKind::Type,
                         line: child.start_position().row + 1,
                         signature: None,
-                        doc_comment: None,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
                     });
                 }
             }
             "lexical_declaration" | "variable_declaration" => {
                 extract_js_variables(&child, source, symbols);
             }
+            "import_statement" => {
+                extract_js_import_or_export(&child, source, symbols);
+            }
             "export_statement" => {
+                // Re-exports (`export { x } from './x'`, `export * from './x'`)
+                // carry a `source`; plain `export function foo() {}` doesn't
+                // and falls through to the recursive case below.
+                extract_js_import_or_export(&child, source, symbols);
                 extract_js_symbols(&child, source, symbols);
             }
             _ => {
@@ -395,12 +707,20 @@ fn extract_js_class_methods(node: &Node, source: &str, symbols: &mut Vec<Symbol>
                 if item.kind() == "method_definition" {
                     if let Some(name_node) = item.child_by_field_name("name") {
                         let name = get_text(&name_node, source);
+                        let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                            &item,
+                            source,
+                            &SupportedLanguage::JavaScript,
+                        ));
                         symbols.push(Symbol {
                             name,
                             kind: SymbolKind::Method,
                             line: item.start_position().row + 1,
                             signature: None,
-                            doc_comment: None,
+                            doc_summary,
+                            doc_full,
+                            parsed_signature: Some(parse_js_signature(&item, source)),
+                            parent: None,
                         });
                     }
                 }
@@ -409,6 +729,40 @@ fn extract_js_class_methods(node: &Node, source: &str, symbols: &mut Vec<Symbol>
     }
 }
 
+/// Emits a [`SymbolKind::Field`] for each property or method signature in a
+/// TS `interface_body`.
+fn extract_ts_interface_members(node: &Node, parent_name: &str, source: &str, symbols: &mut Vec<Symbol>) {
+    let Some(body) = node.child_by_field_name("body") else {
+        return;
+    };
+
+    let mut cursor = body.walk();
+    for member in body.children(&mut cursor) {
+        if !matches!(member.kind(), "property_signature" | "method_signature") {
+            continue;
+        }
+        let Some(name_node) = member.child_by_field_name("name") else {
+            continue;
+        };
+        let name = get_text(&name_node, source);
+        let signature = member
+            .child_by_field_name("type")
+            .map(|n| clean_type_annotation(&n, source));
+        let (doc_summary, doc_full) =
+            doc_fields(get_doc_comment(&member, source, &SupportedLanguage::JavaScript));
+        symbols.push(Symbol {
+            name,
+            kind: SymbolKind::Field,
+            line: member.start_position().row + 1,
+            signature,
+            doc_summary,
+            doc_full,
+            parsed_signature: None,
+            parent: Some(parent_name.to_string()),
+        });
+    }
+}
+
 fn extract_js_variables(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
@@ -421,12 +775,17 @@ fn extract_js_variables(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
                         "arrow_function" | "function" => SymbolKind::Function,
                         _ => SymbolKind::Variable,
                     };
+                    let parsed_signature = matches!(value.kind(), "arrow_function" | "function")
+                        .then(|| parse_js_value_signature(&name, &value, source));
                     symbols.push(Symbol {
                         name,
                         kind,
                         line: child.start_position().row + 1,
                         signature: None,
-                        doc_comment: None,
+                        doc_summary: None,
+                        doc_full: None,
+                        parsed_signature,
+                        parent: None,
                     });
                 }
             }
@@ -434,6 +793,267 @@ fn extract_js_variables(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
     }
 }
 
+fn extract_rust_use(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
+    let line = node.start_position().row + 1;
+    if let Some(argument) = node.child_by_field_name("argument") {
+        walk_rust_use_tree(&argument, "", source, line, symbols);
+    }
+}
+
+/// Recursively expands a `use` argument tree — a plain path, `as` alias,
+/// `{...}` group (possibly nested), or `*` wildcard — into one [`Symbol`]
+/// per leaf import, joining path segments as it descends.
+fn walk_rust_use_tree(node: &Node, prefix: &str, source: &str, line: usize, symbols: &mut Vec<Symbol>) {
+    match node.kind() {
+        "use_as_clause" => {
+            if let Some(path) = node.child_by_field_name("path") {
+                let alias = node.child_by_field_name("alias").map(|n| get_text(&n, source));
+                let full = format!("{prefix}{}", get_text(&path, source));
+                push_rust_import(full, alias, line, symbols);
+            }
+        }
+        "scoped_use_list" => {
+            if let (Some(path), Some(list)) = (
+                node.child_by_field_name("path"),
+                node.child_by_field_name("list"),
+            ) {
+                let new_prefix = format!("{prefix}{}::", get_text(&path, source));
+                walk_rust_use_tree(&list, &new_prefix, source, line, symbols);
+            }
+        }
+        "use_list" => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                if matches!(child.kind(), "{" | "}" | ",") {
+                    continue;
+                }
+                walk_rust_use_tree(&child, prefix, source, line, symbols);
+            }
+        }
+        "use_wildcard" => {
+            let mut cursor = node.walk();
+            let path_text = node
+                .children(&mut cursor)
+                .find(|c| !matches!(c.kind(), "::" | "*"))
+                .map(|n| get_text(&n, source))
+                .unwrap_or_default();
+            symbols.push(Symbol {
+                name: format!("{prefix}{path_text}::*"),
+                kind: SymbolKind::Import,
+                line,
+                signature: None,
+                doc_summary: None,
+                doc_full: None,
+                parsed_signature: None,
+                parent: None,
+            });
+        }
+        "self" => {
+            // Bare `self` in a `{...}` group imports the prefix module itself.
+            let full = prefix.strip_suffix("::").unwrap_or(prefix).to_string();
+            push_rust_import(full, None, line, symbols);
+        }
+        // A leaf: `identifier`, `scoped_identifier`, `crate`, `super`.
+        _ => {
+            let full = format!("{prefix}{}", get_text(node, source));
+            push_rust_import(full, None, line, symbols);
+        }
+    }
+}
+
+fn push_rust_import(full_path: String, alias: Option<String>, line: usize, symbols: &mut Vec<Symbol>) {
+    let bound =
+        alias.unwrap_or_else(|| full_path.rsplit("::").next().unwrap_or(&full_path).to_string());
+    symbols.push(Symbol {
+        name: full_path,
+        kind: SymbolKind::Import,
+        line,
+        signature: Some(bound),
+        doc_summary: None,
+        doc_full: None,
+        parsed_signature: None,
+        parent: None,
+    });
+}
+
+fn extract_python_import(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
+    let line = node.start_position().row + 1;
+    let module_name_node = node.child_by_field_name("module_name");
+    let module = module_name_node.map(|n| get_text(&n, source)).unwrap_or_default();
+
+    // Only the first imported name in a comma-separated list gets tagged
+    // with the "name" field; later siblings (and ones inside a `(...)`
+    // group) are unlabeled, so we match on node kind instead.
+    let mut cursor = node.walk();
+    let mut saw_name = false;
+    for child in node.children(&mut cursor) {
+        if Some(child.start_byte()) == module_name_node.map(|n| n.start_byte()) {
+            continue;
+        }
+        match child.kind() {
+            "dotted_name" | "aliased_import" => {
+                saw_name = true;
+                push_python_import_name(&child, &module, source, line, symbols);
+            }
+            "wildcard_import" => {
+                saw_name = true;
+                symbols.push(Symbol {
+                    name: join_python_module_path(&module, "*"),
+                    kind: SymbolKind::Import,
+                    line,
+                    signature: None,
+                    doc_summary: None,
+                    doc_full: None,
+                    parsed_signature: None,
+                    parent: None,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    // Unrecognized shape (e.g. a future grammar addition); at least record
+    // that something was imported from this module.
+    if !saw_name && node.kind() == "import_from_statement" {
+        symbols.push(Symbol {
+            name: join_python_module_path(&module, "*"),
+            kind: SymbolKind::Import,
+            line,
+            signature: None,
+            doc_summary: None,
+            doc_full: None,
+            parsed_signature: None,
+            parent: None,
+        });
+    }
+}
+
+/// Joins a (possibly relative, dot-prefixed) module path with an imported
+/// name. A relative `module_name` like `.` or `..pkg` already ends in the
+/// dots separating it from what follows, so only a plain `pkg.mod`-style
+/// absolute module needs an explicit `.` inserted before `name`.
+fn join_python_module_path(module: &str, name: &str) -> String {
+    if module.ends_with('.') {
+        format!("{module}{name}")
+    } else {
+        format!("{module}.{name}")
+    }
+}
+
+fn push_python_import_name(
+    name_node: &Node,
+    module: &str,
+    source: &str,
+    line: usize,
+    symbols: &mut Vec<Symbol>,
+) {
+    let (base, alias) = if name_node.kind() == "aliased_import" {
+        let base = name_node
+            .child_by_field_name("name")
+            .map(|n| get_text(&n, source))
+            .unwrap_or_default();
+        let alias = name_node.child_by_field_name("alias").map(|n| get_text(&n, source));
+        (base, alias)
+    } else {
+        (get_text(name_node, source), None)
+    };
+
+    let full = if module.is_empty() {
+        base.clone()
+    } else {
+        join_python_module_path(module, &base)
+    };
+    let bound = alias.unwrap_or_else(|| base.rsplit('.').next().unwrap_or(&base).to_string());
+
+    symbols.push(Symbol {
+        name: full,
+        kind: SymbolKind::Import,
+        line,
+        signature: Some(bound),
+        doc_summary: None,
+        doc_full: None,
+        parsed_signature: None,
+        parent: None,
+    });
+}
+
+fn extract_js_import_or_export(node: &Node, source: &str, symbols: &mut Vec<Symbol>) {
+    // Plain `export function foo() {}` has no `source`; the wrapped
+    // declaration is handled by the caller's recursive descent instead.
+    let Some(source_node) = node.child_by_field_name("source") else {
+        return;
+    };
+    let module = js_string_literal_text(&source_node, source);
+    let line = node.start_position().row + 1;
+
+    let mut cursor = node.walk();
+    let mut bound_names = Vec::new();
+    let mut is_bare_star = false;
+    for child in node.children(&mut cursor) {
+        if child.kind() == "*" {
+            is_bare_star = true;
+        } else {
+            collect_js_bound_names(&child, source, &mut bound_names);
+        }
+    }
+
+    let signature = if is_bare_star {
+        Some("*".to_string())
+    } else if bound_names.is_empty() {
+        None
+    } else {
+        Some(bound_names.join(", "))
+    };
+
+    symbols.push(Symbol {
+        name: module,
+        kind: SymbolKind::Import,
+        line,
+        signature,
+        doc_summary: None,
+        doc_full: None,
+        parsed_signature: None,
+        parent: None,
+    });
+}
+
+/// Collects the names an import/export clause binds locally: a default
+/// import's identifier, a namespace import's alias, or each named
+/// specifier's alias (falling back to its original name).
+fn collect_js_bound_names(node: &Node, source: &str, names: &mut Vec<String>) {
+    match node.kind() {
+        "identifier" => names.push(get_text(node, source)),
+        "namespace_import" => {
+            let mut cursor = node.walk();
+            let id = node.children(&mut cursor).find(|c| c.kind() == "identifier");
+            if let Some(id) = id {
+                names.push(get_text(&id, source));
+            }
+        }
+        "import_clause" | "named_imports" | "export_clause" => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                collect_js_bound_names(&child, source, names);
+            }
+        }
+        "import_specifier" | "export_specifier" => {
+            if let Some(bound) = node
+                .child_by_field_name("alias")
+                .or_else(|| node.child_by_field_name("name"))
+            {
+                names.push(get_text(&bound, source));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn js_string_literal_text(node: &Node, source: &str) -> String {
+    get_text(node, source)
+        .trim_matches(|c| c == '\'' || c == '"' || c == '`')
+        .to_string()
+}
+
 fn get_text(node: &Node, source: &str) -> String {
     source[node.byte_range()].to_string()
 }
@@ -478,29 +1098,293 @@ fn get_js_function_signature(node: &Node, source: &str) -> String {
     text.lines().next().unwrap_or("").to_string()
 }
 
-fn get_preceding_doc_comment(node: &Node, source: &str) -> Option<String> {
+/// Builds a structured [`Signature`] for a Rust `function_item` by walking
+/// its named fields, instead of slicing source text up to the first `{`
+/// (which breaks on default-argument braces, `where` clauses, and
+/// multiline declarations).
+fn parse_rust_signature(node: &Node, source: &str) -> Signature {
+    let name = node
+        .child_by_field_name("name")
+        .map(|n| get_text(&n, source))
+        .unwrap_or_default();
+    let generics = node
+        .child_by_field_name("type_parameters")
+        .map(|n| get_text(&n, source));
+    let return_type = node
+        .child_by_field_name("return_type")
+        .map(|n| get_text(&n, source));
+    let params = node
+        .child_by_field_name("parameters")
+        .map(|p| rust_params(&p, source))
+        .unwrap_or_default();
+
+    // `pub`/`async` aren't named fields in the Rust grammar — they're
+    // anonymous modifier children that precede `fn`.
+    let mut visibility = None;
+    let mut is_async = false;
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "visibility_modifier" => visibility = Some(get_text(&child, source)),
+            "function_modifiers" => is_async = get_text(&child, source).contains("async"),
+            _ => {}
+        }
+    }
+
+    Signature {
+        visibility,
+        generics,
+        name,
+        params,
+        return_type,
+        is_async,
+    }
+}
+
+fn rust_params(param_list: &Node, source: &str) -> Vec<Param> {
+    let mut params = Vec::new();
+    let mut cursor = param_list.walk();
+    for child in param_list.children(&mut cursor) {
+        match child.kind() {
+            "self_parameter" => params.push(Param {
+                name: get_text(&child, source),
+                ty: None,
+                default: None,
+            }),
+            "parameter" | "variadic_parameter" => {
+                let name = child
+                    .child_by_field_name("pattern")
+                    .map(|n| get_text(&n, source))
+                    .unwrap_or_else(|| get_text(&child, source));
+                let ty = child.child_by_field_name("type").map(|n| get_text(&n, source));
+                params.push(Param { name, ty, default: None });
+            }
+            _ => {}
+        }
+    }
+    params
+}
+
+/// Builds a structured [`Signature`] for a Python `function_definition` by
+/// walking its named fields, capturing the full parameter list (including
+/// defaults, `*args`, and `**kwargs`) rather than slicing up to the first
+/// `:` (which breaks on default values or annotations containing one).
+fn parse_python_signature(node: &Node, source: &str) -> Signature {
+    let name = node
+        .child_by_field_name("name")
+        .map(|n| get_text(&n, source))
+        .unwrap_or_default();
+    let return_type = node
+        .child_by_field_name("return_type")
+        .map(|n| get_text(&n, source));
+    let params = node
+        .child_by_field_name("parameters")
+        .map(|p| python_params(&p, source))
+        .unwrap_or_default();
+    let mut cursor = node.walk();
+    let is_async = node.children(&mut cursor).any(|c| c.kind() == "async");
+
+    Signature {
+        visibility: None,
+        generics: None,
+        name,
+        params,
+        return_type,
+        is_async,
+    }
+}
+
+fn python_params(param_list: &Node, source: &str) -> Vec<Param> {
+    let mut params = Vec::new();
+    let mut cursor = param_list.walk();
+    for child in param_list.children(&mut cursor) {
+        match child.kind() {
+            "identifier" => params.push(Param {
+                name: get_text(&child, source),
+                ty: None,
+                default: None,
+            }),
+            "typed_parameter" => {
+                let name = child
+                    .child(0)
+                    .map(|n| get_text(&n, source))
+                    .unwrap_or_default();
+                let ty = child.child_by_field_name("type").map(|n| get_text(&n, source));
+                params.push(Param { name, ty, default: None });
+            }
+            "default_parameter" | "typed_default_parameter" => {
+                let name = child
+                    .child_by_field_name("name")
+                    .map(|n| get_text(&n, source))
+                    .unwrap_or_default();
+                let ty = child.child_by_field_name("type").map(|n| get_text(&n, source));
+                let default = child.child_by_field_name("value").map(|n| get_text(&n, source));
+                params.push(Param { name, ty, default });
+            }
+            "list_splat_pattern" => {
+                if let Some(n) = child.child(1) {
+                    params.push(Param {
+                        name: format!("*{}", get_text(&n, source)),
+                        ty: None,
+                        default: None,
+                    });
+                }
+            }
+            "dictionary_splat_pattern" => {
+                if let Some(n) = child.child(1) {
+                    params.push(Param {
+                        name: format!("**{}", get_text(&n, source)),
+                        ty: None,
+                        default: None,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    params
+}
+
+/// Builds a structured [`Signature`] for a JS/TS `function_declaration` or
+/// `method_definition` by walking its named fields.
+fn parse_js_signature(node: &Node, source: &str) -> Signature {
+    let name = node
+        .child_by_field_name("name")
+        .map(|n| get_text(&n, source))
+        .unwrap_or_default();
+    parse_js_callable(node, &name, source)
+}
+
+/// Like [`parse_js_signature`], but for a `variable_declarator` whose
+/// value is a `function`/`arrow_function` expression — those don't carry
+/// their own name, so it's supplied from the declarator instead.
+fn parse_js_value_signature(name: &str, value_node: &Node, source: &str) -> Signature {
+    parse_js_callable(value_node, name, source)
+}
+
+fn parse_js_callable(node: &Node, name: &str, source: &str) -> Signature {
+    let return_type = node
+        .child_by_field_name("return_type")
+        .map(|n| clean_type_annotation(&n, source));
+    let params = node
+        .child_by_field_name("parameters")
+        .map(|p| js_params(&p, source))
+        .unwrap_or_default();
+    let mut cursor = node.walk();
+    let is_async = node.children(&mut cursor).any(|c| c.kind() == "async");
+
+    Signature {
+        visibility: None,
+        generics: None,
+        name: name.to_string(),
+        params,
+        return_type,
+        is_async,
+    }
+}
+
+/// Strips the leading `:` a TypeScript type-annotation node includes in
+/// its text (e.g. `": string"`), leaving just `"string"`.
+fn clean_type_annotation(node: &Node, source: &str) -> String {
+    get_text(node, source).trim_start_matches(':').trim().to_string()
+}
+
+fn js_params(param_list: &Node, source: &str) -> Vec<Param> {
+    let mut params = Vec::new();
+    let mut cursor = param_list.walk();
+    for child in param_list.children(&mut cursor) {
+        match child.kind() {
+            "identifier" => params.push(Param {
+                name: get_text(&child, source),
+                ty: None,
+                default: None,
+            }),
+            "required_parameter" | "optional_parameter" => {
+                let name = child
+                    .child_by_field_name("pattern")
+                    .map(|n| get_text(&n, source))
+                    .unwrap_or_default();
+                let ty = child
+                    .child_by_field_name("type")
+                    .map(|n| clean_type_annotation(&n, source));
+                let default = child.child_by_field_name("value").map(|n| get_text(&n, source));
+                params.push(Param { name, ty, default });
+            }
+            "assignment_pattern" => {
+                let name = child
+                    .child_by_field_name("left")
+                    .map(|n| get_text(&n, source))
+                    .unwrap_or_default();
+                let default = child.child_by_field_name("right").map(|n| get_text(&n, source));
+                params.push(Param { name, ty: None, default });
+            }
+            "rest_pattern" => {
+                if let Some(n) = child.named_child(0) {
+                    params.push(Param {
+                        name: format!("...{}", get_text(&n, source)),
+                        ty: None,
+                        default: None,
+                    });
+                }
+            }
+            "object_pattern" | "array_pattern" => params.push(Param {
+                name: get_text(&child, source),
+                ty: None,
+                default: None,
+            }),
+            _ => {}
+        }
+    }
+    params
+}
+
+/// Extracts the documentation attached to `node`, dispatching by language.
+/// Returns the full, un-truncated text; see [`doc_fields`] for splitting it
+/// into the `doc_summary`/`doc_full` pair stored on [`Symbol`].
+fn get_doc_comment(node: &Node, source: &str, lang: &SupportedLanguage) -> Option<String> {
+    match lang {
+        SupportedLanguage::Rust => get_rust_doc_comment(node, source),
+        SupportedLanguage::Python => get_python_docstring(node, source),
+        SupportedLanguage::JavaScript | SupportedLanguage::TypeScript => {
+            get_js_doc_comment(node, source)
+        }
+        SupportedLanguage::Dynamic(_) => None,
+    }
+}
+
+/// Splits a doc comment into its first line (`doc_summary`) and full text
+/// (`doc_full`), or `(None, None)` if there wasn't one.
+fn doc_fields(doc: Option<String>) -> (Option<String>, Option<String>) {
+    match doc {
+        Some(text) => {
+            let summary = text.lines().next().unwrap_or("").to_string();
+            (Some(summary), Some(text))
+        }
+        None => (None, None),
+    }
+}
+
+/// Walks backward through `node`'s preceding siblings collecting the run of
+/// `///`/`//!` line comments and `/** */` block comments directly above it
+/// (plain `//` comments in the run are skipped, not collected), then
+/// reassembles them top-to-bottom so multi-line doc comments keep their
+/// paragraph breaks instead of collapsing to the single closest line.
+fn get_rust_doc_comment(node: &Node, source: &str) -> Option<String> {
     let mut prev = node.prev_sibling();
+    let mut lines = Vec::new();
 
     while let Some(sibling) = prev {
         match sibling.kind() {
             "line_comment" => {
                 let text = get_text(&sibling, source);
                 if text.starts_with("///") || text.starts_with("//!") {
-                    return Some(text[3..].trim().to_string());
+                    lines.push(text[3..].trim().to_string());
                 }
             }
             "block_comment" => {
                 let text = get_text(&sibling, source);
                 if text.starts_with("/**") {
-                    // Extract doc comment content
-                    let content = text
-                        .trim_start_matches("/**")
-                        .trim_end_matches("*/")
-                        .lines()
-                        .map(|l| l.trim().trim_start_matches('*').trim())
-                        .collect::<Vec<_>>()
-                        .join(" ");
-                    return Some(content);
+                    lines.push(clean_block_comment(&text));
                 }
             }
             _ => break,
@@ -508,7 +1392,27 @@ fn get_preceding_doc_comment(node: &Node, source: &str) -> Option<String> {
         prev = sibling.prev_sibling();
     }
 
-    None
+    if lines.is_empty() {
+        return None;
+    }
+    lines.reverse();
+    Some(lines.join("\n"))
+}
+
+/// Strips the `/** */` delimiters and each line's leading `*` from a block
+/// doc comment, trimming the blank lines that the opening/closing markers
+/// usually leave at the start and end while preserving blank lines (and
+/// indentation breaks) in between.
+fn clean_block_comment(text: &str) -> String {
+    let lines: Vec<&str> = text
+        .trim_start_matches("/**")
+        .trim_end_matches("*/")
+        .lines()
+        .map(|l| l.trim().trim_start_matches('*').trim())
+        .collect();
+    let start = lines.iter().position(|l| !l.is_empty()).unwrap_or(0);
+    let end = lines.iter().rposition(|l| !l.is_empty()).map_or(0, |i| i + 1);
+    lines[start..end].join("\n")
 }
 
 fn get_python_docstring(node: &Node, source: &str) -> Option<String> {
@@ -522,15 +1426,31 @@ fn get_python_docstring(node: &Node, source: &str) -> Option<String> {
                     for expr in item.children(&mut expr_cursor) {
                         if expr.kind() == "string" {
                             let text = get_text(&expr, source);
-                            // Clean up the docstring
-                            let content = text
-                                .trim_start_matches("\"\"\"")
-                                .trim_start_matches("'''")
-                                .trim_end_matches("\"\"\"")
-                                .trim_end_matches("'''")
-                                .trim();
+                            // Strip whichever quote style wraps the string -
+                            // triple-quoted is the convention, but a plain
+                            // single/double-quoted docstring is valid Python too.
+                            let content = if let Some(stripped) =
+                                text.strip_prefix("\"\"\"").and_then(|s| s.strip_suffix("\"\"\""))
+                            {
+                                stripped
+                            } else if let Some(stripped) =
+                                text.strip_prefix("'''").and_then(|s| s.strip_suffix("'''"))
+                            {
+                                stripped
+                            } else if let Some(stripped) =
+                                text.strip_prefix('"').and_then(|s| s.strip_suffix('"'))
+                            {
+                                stripped
+                            } else if let Some(stripped) =
+                                text.strip_prefix('\'').and_then(|s| s.strip_suffix('\''))
+                            {
+                                stripped
+                            } else {
+                                text.as_str()
+                            }
+                            .trim();
                             if !content.is_empty() {
-                                return Some(content.lines().next().unwrap_or("").to_string());
+                                return Some(content.to_string());
                             }
                         }
                     }
@@ -542,6 +1462,30 @@ fn get_python_docstring(node: &Node, source: &str) -> Option<String> {
     None
 }
 
+/// Extracts a JSDoc `/** ... */` block immediately preceding `node`. JS/TS
+/// grammars tag both line and block comments with the same `comment` node
+/// kind, so the JSDoc marker has to be checked on the text itself rather
+/// than distinguished by node kind the way Rust's `line_comment`/
+/// `block_comment` split allows.
+fn get_js_doc_comment(node: &Node, source: &str) -> Option<String> {
+    let mut prev = node.prev_sibling();
+
+    while let Some(sibling) = prev {
+        match sibling.kind() {
+            "comment" => {
+                let text = get_text(&sibling, source);
+                if text.starts_with("/**") {
+                    return Some(clean_block_comment(&text));
+                }
+            }
+            _ => break,
+        }
+        prev = sibling.prev_sibling();
+    }
+
+    None
+}
+
 /// Generates a skeleton (outline) view of the source code structure.
 ///
 /// This function creates a condensed representation of the code that shows
@@ -577,6 +1521,7 @@ pub fn get_skeleton(tree: &Tree, source: &str, lang: &SupportedLanguage) -> Stri
         SupportedLanguage::JavaScript | SupportedLanguage::TypeScript => {
             get_js_skeleton(&root, source, &mut result, 0)
         }
+        SupportedLanguage::Dynamic(_) => {}
     }
 
     result
@@ -589,7 +1534,7 @@ fn get_rust_skeleton(node: &Node, source: &str, result: &mut String, indent: usi
     for child in node.children(&mut cursor) {
         match child.kind() {
             "function_item" => {
-                let sig = get_function_signature(&child, source);
+                let sig = render_rust_signature(&parse_rust_signature(&child, source));
                 result.push_str(&format!("{}{} {{ ... }}\n", indent_str, sig));
             }
             "struct_item" | "enum_item" | "trait_item" => {
@@ -614,7 +1559,7 @@ fn get_rust_skeleton(node: &Node, source: &str, result: &mut String, indent: usi
                 let mut inner_cursor = child.walk();
                 for item in child.children(&mut inner_cursor) {
                     if item.kind() == "function_item" {
-                        let sig = get_function_signature(&item, source);
+                        let sig = render_rust_signature(&parse_rust_signature(&item, source));
                         result.push_str(&format!("{}{} {{ ... }}\n", indent_str, sig));
                     }
                 }
@@ -631,7 +1576,7 @@ fn get_python_skeleton(node: &Node, source: &str, result: &mut String, indent: u
     for child in node.children(&mut cursor) {
         match child.kind() {
             "function_definition" => {
-                let sig = get_python_function_signature(&child, source);
+                let sig = render_python_signature(&parse_python_signature(&child, source));
                 result.push_str(&format!("{}{}:\n{}    ...\n", indent_str, sig, indent_str));
             }
             "class_definition" => {
@@ -656,7 +1601,7 @@ fn get_js_skeleton(node: &Node, source: &str, result: &mut String, indent: usize
     for child in node.children(&mut cursor) {
         match child.kind() {
             "function_declaration" => {
-                let sig = get_js_function_signature(&child, source);
+                let sig = render_js_signature(&parse_js_signature(&child, source));
                 result.push_str(&format!("{}{} {{ ... }}\n", indent_str, sig));
             }
             "class_declaration" => {
@@ -686,25 +1631,120 @@ fn get_js_skeleton(node: &Node, source: &str, result: &mut String, indent: usize
     }
 }
 
-/// Extracts all import/use statements from a parsed syntax tree.
+/// Reconstructs a Rust function/method declaration line from a parsed
+/// [`Signature`], normalized regardless of how the original was wrapped
+/// across lines or what its default-argument braces contained.
+fn render_rust_signature(sig: &Signature) -> String {
+    let mut out = String::new();
+    if let Some(vis) = &sig.visibility {
+        out.push_str(vis);
+        out.push(' ');
+    }
+    if sig.is_async {
+        out.push_str("async ");
+    }
+    out.push_str("fn ");
+    out.push_str(&sig.name);
+    if let Some(generics) = &sig.generics {
+        out.push_str(generics);
+    }
+    out.push('(');
+    out.push_str(&render_params(&sig.params));
+    out.push(')');
+    if let Some(ret) = &sig.return_type {
+        out.push_str(" -> ");
+        out.push_str(ret);
+    }
+    out
+}
+
+fn render_python_signature(sig: &Signature) -> String {
+    let mut out = String::new();
+    if sig.is_async {
+        out.push_str("async ");
+    }
+    out.push_str("def ");
+    out.push_str(&sig.name);
+    out.push('(');
+    out.push_str(&render_params(&sig.params));
+    out.push(')');
+    if let Some(ret) = &sig.return_type {
+        out.push_str(" -> ");
+        out.push_str(ret);
+    }
+    out
+}
+
+fn render_js_signature(sig: &Signature) -> String {
+    let mut out = String::new();
+    if sig.is_async {
+        out.push_str("async ");
+    }
+    out.push_str("function ");
+    out.push_str(&sig.name);
+    out.push('(');
+    out.push_str(&render_params(&sig.params));
+    out.push(')');
+    if let Some(ret) = &sig.return_type {
+        out.push_str(": ");
+        out.push_str(ret);
+    }
+    out
+}
+
+fn render_params(params: &[Param]) -> String {
+    params
+        .iter()
+        .map(|p| match &p.ty {
+            Some(ty) => format!("{}: {}", p.name, ty),
+            None => p.name.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A single import/use declaration, parsed into its structural parts
+/// instead of left as raw statement text — so callers can resolve
+/// `module_path` against a file set, or match on `imported_names`, without
+/// re-parsing [`raw`](ImportRecord::raw) themselves.
 ///
-/// This function scans the top level of the AST to find import declarations,
-/// which are useful for understanding a file's dependencies and relationships
-/// to other modules.
+/// # Fields
+/// * `raw` - The full source text of the statement, for display
+/// * `module_path` - The module/crate path segments, e.g. `["std", "collections"]`
+///   for `use std::collections::HashMap;`, or `["utils"]` for
+///   `from .utils import helper`
+/// * `imported_names` - Each name pulled in and its local alias, if renamed
+///   (`as` in Rust/Python, a specifier alias in JS); a bare Python
+///   `import foo.bar` lists `foo.bar` itself, and a JS import with no
+///   specifiers (`import './x'`) leaves it empty
+/// * `is_glob` - Whether this is a wildcard import (`use foo::*`, `from x import *`)
+/// * `is_relative` - Whether `module_path` is relative to the importing file
+///   rather than resolved from a root (Rust `self`/`super`, Python leading
+///   dots, JS/TS `./`/`../` specifiers)
+#[derive(Debug, Clone, Serialize, PartialEq, Default)]
+pub struct ImportRecord {
+    pub raw: String,
+    pub module_path: Vec<String>,
+    pub imported_names: Vec<(String, Option<String>)>,
+    pub is_glob: bool,
+    pub is_relative: bool,
+}
+
+/// Extracts all import/use statements from a parsed syntax tree, one
+/// [`ImportRecord`] per declaration.
 ///
 /// # Arguments
 /// * `tree` - A parsed tree-sitter syntax tree
 /// * `source` - The original source code
 /// * `lang` - The programming language of the source file
 ///
-/// # Returns
-/// A vector of strings, each containing the full text of an import statement.
-///
 /// # Language-Specific Behavior
-/// - **Rust**: Extracts `use` declarations (e.g., `use std::path::Path;`)
-/// - **Python**: Extracts `import` and `from ... import` statements
-/// - **JavaScript/TypeScript**: Extracts `import` statements
-pub fn find_imports(tree: &Tree, source: &str, lang: &SupportedLanguage) -> Vec<String> {
+/// - **Rust**: `use` declarations, including `{...}` groups and `as` renames
+/// - **Python**: `import` and `from ... import` statements, with relative
+///   (dotted) modules reflected in `is_relative`
+/// - **JavaScript/TypeScript**: `import` statements, covering default,
+///   named (`{ a, b as c }`), and namespace (`* as ns`) specifiers
+pub fn find_imports(tree: &Tree, source: &str, lang: &SupportedLanguage) -> Vec<ImportRecord> {
     let mut imports = Vec::new();
     let root = tree.root_node();
 
@@ -714,48 +1754,1351 @@ pub fn find_imports(tree: &Tree, source: &str, lang: &SupportedLanguage) -> Vec<
         SupportedLanguage::JavaScript | SupportedLanguage::TypeScript => {
             find_js_imports(&root, source, &mut imports)
         }
+        SupportedLanguage::Dynamic(_) => {}
     }
 
     imports
 }
 
-fn find_rust_imports(node: &Node, source: &str, imports: &mut Vec<String>) {
+/// Line-count breakdown of a source file: code, comment, and blank lines.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct LineBreakdown {
+    pub code: usize,
+    pub comments: usize,
+    pub blanks: usize,
+}
+
+/// Classifies every line of `source` as code, comment, or blank using the
+/// byte ranges of comment nodes in the parsed syntax tree.
+///
+/// A line is blank if it's empty or whitespace-only, a comment line if
+/// every non-whitespace byte on it falls inside a comment node, and code
+/// otherwise — so a line with trailing code and a comment (`let x = 1; //
+/// note`) counts as code, matching how line-counting tools like `tokei`
+/// treat it.
+pub fn line_breakdown(tree: &Tree, source: &str) -> LineBreakdown {
+    let mut comment_ranges = Vec::new();
+    collect_comment_ranges(&tree.root_node(), &mut comment_ranges);
+
+    let mut breakdown = LineBreakdown::default();
+    let mut offset = 0usize;
+
+    for line in source.split_inclusive('\n') {
+        let line_start = offset;
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+        offset += line.len();
+
+        if trimmed.trim().is_empty() {
+            breakdown.blanks += 1;
+            continue;
+        }
+
+        let is_comment_only = trimmed.char_indices().all(|(i, c)| {
+            c.is_whitespace() || {
+                let pos = line_start + i;
+                comment_ranges.iter().any(|&(s, e)| pos >= s && pos < e)
+            }
+        });
+
+        if is_comment_only {
+            breakdown.comments += 1;
+        } else {
+            breakdown.code += 1;
+        }
+    }
+
+    breakdown
+}
+
+fn collect_comment_ranges(node: &Node, ranges: &mut Vec<(usize, usize)>) {
+    if node.kind().contains("comment") {
+        ranges.push((node.start_byte(), node.end_byte()));
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_comment_ranges(&child, ranges);
+    }
+}
+
+fn find_rust_imports(node: &Node, source: &str, imports: &mut Vec<ImportRecord>) {
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         if child.kind() == "use_declaration" {
-            let text = get_text(&child, source);
-            imports.push(text);
+            let raw = get_text(&child, source);
+            let Some(tree) = child.child_by_field_name("argument") else {
+                continue;
+            };
+            imports.push(parse_rust_use_tree(&tree, source, raw));
+        }
+    }
+}
+
+/// Flattens a Rust `use` tree into one [`ImportRecord`]: `module_path` is
+/// the path shared by every leaf (the part before a `{...}` group, if any),
+/// and `imported_names` holds each leaf's bound name with its `as` alias.
+fn parse_rust_use_tree(node: &Node, source: &str, raw: String) -> ImportRecord {
+    let mut record = ImportRecord {
+        raw,
+        ..Default::default()
+    };
+    collect_rust_use_leaves(node, "", source, &mut record);
+    record.is_relative = matches!(record.module_path.first().map(String::as_str), Some("self" | "super"));
+    record
+}
+
+fn collect_rust_use_leaves(node: &Node, prefix: &str, source: &str, record: &mut ImportRecord) {
+    match node.kind() {
+        "use_as_clause" => {
+            if let Some(path) = node.child_by_field_name("path") {
+                let alias = node.child_by_field_name("alias").map(|n| get_text(&n, source));
+                push_rust_leaf(&format!("{prefix}{}", get_text(&path, source)), alias, record);
+            }
+        }
+        "scoped_use_list" => {
+            if let (Some(path), Some(list)) = (
+                node.child_by_field_name("path"),
+                node.child_by_field_name("list"),
+            ) {
+                if record.module_path.is_empty() {
+                    record.module_path = get_text(&path, source).split("::").map(str::to_string).collect();
+                }
+                let new_prefix = format!("{prefix}{}::", get_text(&path, source));
+                collect_rust_use_leaves(&list, &new_prefix, source, record);
+            }
+        }
+        "use_list" => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                if matches!(child.kind(), "{" | "}" | ",") {
+                    continue;
+                }
+                collect_rust_use_leaves(&child, prefix, source, record);
+            }
+        }
+        "use_wildcard" => {
+            record.is_glob = true;
+            if record.module_path.is_empty() {
+                let mut cursor = node.walk();
+                let path_text = node
+                    .children(&mut cursor)
+                    .find(|c| !matches!(c.kind(), "::" | "*"))
+                    .map(|n| get_text(&n, source))
+                    .unwrap_or_default();
+                record.module_path = format!("{prefix}{path_text}").split("::").map(str::to_string).collect();
+            }
+        }
+        "self" => {
+            // Bare `self` in a `{...}` group imports the prefix module itself.
+            let full = prefix.strip_suffix("::").unwrap_or(prefix).to_string();
+            push_rust_leaf(&full, None, record);
+        }
+        // A leaf: `identifier`, `scoped_identifier`, `crate`, `super`.
+        _ => {
+            push_rust_leaf(&format!("{prefix}{}", get_text(node, source)), None, record);
+        }
+    }
+}
+
+fn push_rust_leaf(full_path: &str, alias: Option<String>, record: &mut ImportRecord) {
+    if record.module_path.is_empty() {
+        let mut segments: Vec<&str> = full_path.split("::").collect();
+        let bound_leaf = segments.pop();
+        record.module_path = segments.into_iter().map(str::to_string).collect();
+        if record.module_path.is_empty() {
+            if let Some(leaf) = bound_leaf {
+                record.module_path.push(leaf.to_string());
+            }
         }
     }
+    let bound = full_path.rsplit("::").next().unwrap_or(full_path).to_string();
+    record.imported_names.push((bound, alias));
 }
 
-fn find_python_imports(node: &Node, source: &str, imports: &mut Vec<String>) {
+fn find_python_imports(node: &Node, source: &str, imports: &mut Vec<ImportRecord>) {
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         match child.kind() {
-            "import_statement" | "import_from_statement" => {
-                let text = get_text(&child, source);
-                imports.push(text);
-            }
+            "import_statement" => imports.push(parse_python_import_statement(&child, source)),
+            "import_from_statement" => imports.push(parse_python_import_from(&child, source)),
             _ => {}
         }
     }
 }
 
-fn find_js_imports(node: &Node, source: &str, imports: &mut Vec<String>) {
+/// Parses a bare `import a.b.c` / `import a.b as c` statement: each
+/// dotted name becomes its own `imported_names` entry (matching how Python
+/// binds each one under its own top-level or aliased name).
+fn parse_python_import_statement(node: &Node, source: &str) -> ImportRecord {
+    let raw = get_text(node, source);
+    let mut record = ImportRecord {
+        raw,
+        ..Default::default()
+    };
+
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        if child.kind() == "import_statement" {
-            let text = get_text(&child, source);
-            imports.push(text);
+        match child.kind() {
+            "dotted_name" => {
+                let name = get_text(&child, source);
+                if record.module_path.is_empty() {
+                    record.module_path = name.split('.').map(str::to_string).collect();
+                }
+                record.imported_names.push((name, None));
+            }
+            "aliased_import" => {
+                let base = child
+                    .child_by_field_name("name")
+                    .map(|n| get_text(&n, source))
+                    .unwrap_or_default();
+                let alias = child.child_by_field_name("alias").map(|n| get_text(&n, source));
+                if record.module_path.is_empty() {
+                    record.module_path = base.split('.').map(str::to_string).collect();
+                }
+                record.imported_names.push((base, alias));
+            }
+            _ => {}
         }
     }
+
+    record
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tree_sitter::Parser;
+/// Parses a `from <module> import a, b as c` statement: `module_path` is
+/// the module's dotted segments (with leading dots stripped, and
+/// `is_relative` set instead), and `imported_names` holds each imported
+/// name with its `as` alias.
+fn parse_python_import_from(node: &Node, source: &str) -> ImportRecord {
+    let raw = get_text(node, source);
+    let module_name_node = node.child_by_field_name("module_name");
+    let module = module_name_node.map(|n| get_text(&n, source)).unwrap_or_default();
+
+    // A relative module keeps one leading empty segment per extra dot
+    // beyond the first (`..pkg` -> parent-of-parent, one `""` then `pkg`),
+    // so `is_relative` consumers can walk back up `from`'s directory
+    // without needing the original dot count separately.
+    let is_relative = module.starts_with('.');
+    let dots = module.chars().take_while(|c| *c == '.').count();
+    let rest = &module[dots..];
+    let mut module_path: Vec<String> = if is_relative && dots > 1 {
+        vec![String::new(); dots - 1]
+    } else {
+        Vec::new()
+    };
+    if !rest.is_empty() {
+        module_path.extend(rest.split('.').map(str::to_string));
+    }
+
+    let mut record = ImportRecord {
+        raw,
+        module_path,
+        is_relative,
+        ..Default::default()
+    };
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if Some(child.start_byte()) == module_name_node.map(|n| n.start_byte()) {
+            continue;
+        }
+        match child.kind() {
+            "dotted_name" => {
+                record.imported_names.push((get_text(&child, source), None));
+            }
+            "aliased_import" => {
+                let base = child
+                    .child_by_field_name("name")
+                    .map(|n| get_text(&n, source))
+                    .unwrap_or_default();
+                let alias = child.child_by_field_name("alias").map(|n| get_text(&n, source));
+                record.imported_names.push((base, alias));
+            }
+            "wildcard_import" => record.is_glob = true,
+            _ => {}
+        }
+    }
+
+    record
+}
+
+fn find_js_imports(node: &Node, source: &str, imports: &mut Vec<ImportRecord>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() == "import_statement" {
+            if let Some(record) = parse_js_import_statement(&child, source) {
+                imports.push(record);
+            }
+        }
+    }
+}
+
+/// Parses an `import ... from '<source>'` statement into default, named
+/// (`{ a, b as c }`), and namespace (`* as ns`) specifiers, with the
+/// `'source'` string literal as `module_path`'s single segment.
+fn parse_js_import_statement(node: &Node, source: &str) -> Option<ImportRecord> {
+    let source_node = node.child_by_field_name("source")?;
+    let module = js_string_literal_text(&source_node, source);
+    let is_relative = module.starts_with('.');
+
+    let mut record = ImportRecord {
+        raw: get_text(node, source),
+        module_path: vec![module],
+        is_relative,
+        ..Default::default()
+    };
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_js_import_specifiers(&child, source, &mut record.imported_names);
+    }
+
+    Some(record)
+}
+
+/// Recurses through an `import_clause` to collect each specifier as
+/// `(name, alias)`: a default import's bare identifier, a namespace
+/// import's `* as ns` (recorded as `("*", Some(ns))`), and each named
+/// specifier's original name with its `as` alias, if any.
+fn collect_js_import_specifiers(node: &Node, source: &str, names: &mut Vec<(String, Option<String>)>) {
+    match node.kind() {
+        "identifier" => names.push((get_text(node, source), None)),
+        "namespace_import" => {
+            let mut cursor = node.walk();
+            if let Some(id) = node.children(&mut cursor).find(|c| c.kind() == "identifier") {
+                names.push(("*".to_string(), Some(get_text(&id, source))));
+            }
+        }
+        "import_clause" | "named_imports" => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                collect_js_import_specifiers(&child, source, names);
+            }
+        }
+        "import_specifier" => {
+            let name = node
+                .child_by_field_name("name")
+                .map(|n| get_text(&n, source))
+                .unwrap_or_default();
+            let alias = node.child_by_field_name("alias").map(|n| get_text(&n, source));
+            names.push((name, alias));
+        }
+        _ => {}
+    }
+}
+
+/// A node in the hierarchical symbol outline: a [`Symbol`] together with
+/// its full source range and any symbols nested inside it — methods
+/// inside an `impl`/class body, or items inside a `mod` block.
+///
+/// Unlike the flat list from [`extract_symbols`], a method here is a
+/// child of its container rather than a sibling, so the tree mirrors an
+/// editor's outline panel.
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolNode {
+    pub symbol: Symbol,
+    /// 1-indexed line where this symbol's node starts (same as `symbol.line`).
+    pub start_line: usize,
+    /// 1-indexed line where this symbol's node ends.
+    pub end_line: usize,
+    pub children: Vec<SymbolNode>,
+}
+
+/// The hierarchical symbol outline of a file: top-level symbols, each
+/// potentially containing nested symbols. See [`SymbolNode`].
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct SymbolTree {
+    pub roots: Vec<SymbolNode>,
+}
+
+impl SymbolTree {
+    /// Finds the innermost symbol whose range contains `line` (1-indexed),
+    /// descending through children to the most specific match.
+    pub fn symbol_at_line(&self, line: usize) -> Option<&SymbolNode> {
+        find_containing(&self.roots, line)
+    }
+}
+
+fn find_containing(nodes: &[SymbolNode], line: usize) -> Option<&SymbolNode> {
+    for node in nodes {
+        if line >= node.start_line && line <= node.end_line {
+            return find_containing(&node.children, line).or(Some(node));
+        }
+    }
+    None
+}
+
+fn make_node(symbol: Symbol, range_node: &Node, children: Vec<SymbolNode>) -> SymbolNode {
+    SymbolNode {
+        symbol,
+        start_line: range_node.start_position().row + 1,
+        end_line: range_node.end_position().row + 1,
+        children,
+    }
+}
+
+/// Extracts the hierarchical symbol outline from a parsed syntax tree.
+///
+/// This is the nesting-aware counterpart to [`extract_symbols`]: methods
+/// inside an `impl`/class body, and items inside a `mod` block, come back
+/// as `children` of their container rather than flattened siblings.
+///
+/// # Supported Languages
+/// - Rust: structs, enums, traits, consts, types, modules (with nested
+///   items), and `impl` blocks (with their methods as children)
+/// - Python: functions, classes (with methods as children)
+/// - JavaScript/TypeScript: functions, classes (with methods as children),
+///   interfaces, type aliases, variables
+pub fn extract_symbol_tree(tree: &Tree, source: &str, lang: &SupportedLanguage) -> SymbolTree {
+    let root = tree.root_node();
+
+    let roots = match lang {
+        SupportedLanguage::Rust => build_rust_symbol_tree(&root, source),
+        SupportedLanguage::Python => build_python_symbol_tree(&root, source),
+        SupportedLanguage::JavaScript | SupportedLanguage::TypeScript => {
+            build_js_symbol_tree(&root, source)
+        }
+        SupportedLanguage::Dynamic(_) => Vec::new(),
+    };
+
+    SymbolTree { roots }
+}
+
+fn build_rust_symbol_tree(node: &Node, source: &str) -> Vec<SymbolNode> {
+    let mut nodes = Vec::new();
+    let mut cursor = node.walk();
+
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "function_item" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Rust,
+                    ));
+                    let symbol = Symbol {
+                        name: get_text(&name_node, source),
+                        kind: SymbolKind::Function,
+                        line: child.start_position().row + 1,
+                        signature: Some(get_function_signature(&child, source)),
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: Some(parse_rust_signature(&child, source)),
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, Vec::new()));
+                }
+            }
+            "struct_item" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let name = get_text(&name_node, source);
+                    let children = rust_struct_field_nodes(&child, &name, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Rust,
+                    ));
+                    let symbol = Symbol {
+                        name,
+                        kind: SymbolKind::Struct,
+                        line: child.start_position().row + 1,
+                        signature: None,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, children));
+                }
+            }
+            "enum_item" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let name = get_text(&name_node, source);
+                    let children = rust_enum_variant_nodes(&child, &name, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Rust,
+                    ));
+                    let symbol = Symbol {
+                        name,
+                        kind: SymbolKind::Enum,
+                        line: child.start_position().row + 1,
+                        signature: None,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, children));
+                }
+            }
+            "trait_item" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Rust,
+                    ));
+                    let symbol = Symbol {
+                        name: get_text(&name_node, source),
+                        kind: SymbolKind::Trait,
+                        line: child.start_position().row + 1,
+                        signature: None,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, Vec::new()));
+                }
+            }
+            "impl_item" => {
+                let name = child
+                    .child_by_field_name("type")
+                    .map(|n| get_text(&n, source))
+                    .unwrap_or_else(|| "impl".to_string());
+                let start = child.start_byte();
+                let text = &source[start..];
+                let signature = text.find('{').map(|pos| text[..pos].trim().to_string());
+                let children = extract_rust_impl_method_nodes(&child, source);
+                let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                    &child,
+                    source,
+                    &SupportedLanguage::Rust,
+                ));
+                let symbol = Symbol {
+                    name,
+                    kind: SymbolKind::Impl,
+                    line: child.start_position().row + 1,
+                    signature,
+                    doc_summary,
+                    doc_full,
+                    parsed_signature: None,
+                    parent: None,
+                };
+                nodes.push(make_node(symbol, &child, children));
+            }
+            "const_item" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let symbol = Symbol {
+                        name: get_text(&name_node, source),
+                        kind: SymbolKind::Const,
+                        line: child.start_position().row + 1,
+                        signature: None,
+                        doc_summary: None,
+                        doc_full: None,
+                        parsed_signature: None,
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, Vec::new()));
+                }
+            }
+            "type_item" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let symbol = Symbol {
+                        name: get_text(&name_node, source),
+                        kind: SymbolKind::Type,
+                        line: child.start_position().row + 1,
+                        signature: None,
+                        doc_summary: None,
+                        doc_full: None,
+                        parsed_signature: None,
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, Vec::new()));
+                }
+            }
+            "mod_item" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let children = child
+                        .child_by_field_name("body")
+                        .map(|body| build_rust_symbol_tree(&body, source))
+                        .unwrap_or_default();
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Rust,
+                    ));
+                    let symbol = Symbol {
+                        name: get_text(&name_node, source),
+                        kind: SymbolKind::Module,
+                        line: child.start_position().row + 1,
+                        signature: None,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, children));
+                }
+            }
+            _ => {
+                nodes.extend(build_rust_symbol_tree(&child, source));
+            }
+        }
+    }
+
+    nodes
+}
+
+fn extract_rust_impl_method_nodes(node: &Node, source: &str) -> Vec<SymbolNode> {
+    let mut nodes = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() == "declaration_list" {
+            let mut inner_cursor = child.walk();
+            for item in child.children(&mut inner_cursor) {
+                if item.kind() == "function_item" {
+                    if let Some(name_node) = item.child_by_field_name("name") {
+                        let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                            &item,
+                            source,
+                            &SupportedLanguage::Rust,
+                        ));
+                        let symbol = Symbol {
+                            name: get_text(&name_node, source),
+                            kind: SymbolKind::Method,
+                            line: item.start_position().row + 1,
+                            signature: Some(get_function_signature(&item, source)),
+                            doc_summary,
+                            doc_full,
+                            parsed_signature: Some(parse_rust_signature(&item, source)),
+                            parent: None,
+                        };
+                        nodes.push(make_node(symbol, &item, Vec::new()));
+                    }
+                }
+            }
+        }
+    }
+    nodes
+}
+
+fn rust_struct_field_nodes(node: &Node, parent_name: &str, source: &str) -> Vec<SymbolNode> {
+    let mut nodes = Vec::new();
+    let mut fields = Vec::new();
+    extract_rust_struct_fields(node, parent_name, source, &mut fields);
+    for field in fields {
+        let line = field.line;
+        nodes.push(SymbolNode {
+            symbol: field,
+            start_line: line,
+            end_line: line,
+            children: Vec::new(),
+        });
+    }
+    nodes
+}
+
+fn rust_enum_variant_nodes(node: &Node, parent_name: &str, source: &str) -> Vec<SymbolNode> {
+    let mut nodes = Vec::new();
+    let mut variants = Vec::new();
+    extract_rust_enum_variants(node, parent_name, source, &mut variants);
+    for variant in variants {
+        let line = variant.line;
+        nodes.push(SymbolNode {
+            symbol: variant,
+            start_line: line,
+            end_line: line,
+            children: Vec::new(),
+        });
+    }
+    nodes
+}
+
+fn build_python_symbol_tree(node: &Node, source: &str) -> Vec<SymbolNode> {
+    let mut nodes = Vec::new();
+    let mut cursor = node.walk();
+
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "function_definition" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Python,
+                    ));
+                    let symbol = Symbol {
+                        name: get_text(&name_node, source),
+                        kind: SymbolKind::Function,
+                        line: child.start_position().row + 1,
+                        signature: Some(get_python_function_signature(&child, source)),
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: Some(parse_python_signature(&child, source)),
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, Vec::new()));
+                }
+            }
+            "class_definition" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let name = get_text(&name_node, source);
+                    let mut children = child
+                        .child_by_field_name("body")
+                        .map(|body| build_python_symbol_tree(&body, source))
+                        .unwrap_or_default();
+                    children.extend(python_class_attribute_nodes(&child, &name, source));
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::Python,
+                    ));
+                    let symbol = Symbol {
+                        name,
+                        kind: SymbolKind::Class,
+                        line: child.start_position().row + 1,
+                        signature: None,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, children));
+                }
+            }
+            _ => {
+                nodes.extend(build_python_symbol_tree(&child, source));
+            }
+        }
+    }
+
+    nodes
+}
+
+fn python_class_attribute_nodes(node: &Node, parent_name: &str, source: &str) -> Vec<SymbolNode> {
+    let mut nodes = Vec::new();
+    let mut attributes = Vec::new();
+    extract_python_class_attributes(node, parent_name, source, &mut attributes);
+    for attribute in attributes {
+        let line = attribute.line;
+        nodes.push(SymbolNode {
+            symbol: attribute,
+            start_line: line,
+            end_line: line,
+            children: Vec::new(),
+        });
+    }
+    nodes
+}
+
+fn build_js_symbol_tree(node: &Node, source: &str) -> Vec<SymbolNode> {
+    let mut nodes = Vec::new();
+    let mut cursor = node.walk();
+
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "function_declaration" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::JavaScript,
+                    ));
+                    let symbol = Symbol {
+                        name: get_text(&name_node, source),
+                        kind: SymbolKind::Function,
+                        line: child.start_position().row + 1,
+                        signature: Some(get_js_function_signature(&child, source)),
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: Some(parse_js_signature(&child, source)),
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, Vec::new()));
+                }
+            }
+            "class_declaration" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let children = child
+                        .child_by_field_name("body")
+                        .map(|body| build_js_class_method_nodes(&body, source))
+                        .unwrap_or_default();
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::JavaScript,
+                    ));
+                    let symbol = Symbol {
+                        name: get_text(&name_node, source),
+                        kind: SymbolKind::Class,
+                        line: child.start_position().row + 1,
+                        signature: None,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, children));
+                }
+            }
+            "interface_declaration" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let name = get_text(&name_node, source);
+                    let children = ts_interface_member_nodes(&child, &name, source);
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::JavaScript,
+                    ));
+                    let symbol = Symbol {
+                        name,
+                        kind: SymbolKind::Interface,
+                        line: child.start_position().row + 1,
+                        signature: None,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, children));
+                }
+            }
+            "type_alias_declaration" => {
+                if let Some(name_node) = child.child_by_field_name("name") {
+                    let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                        &child,
+                        source,
+                        &SupportedLanguage::JavaScript,
+                    ));
+                    let symbol = Symbol {
+                        name: get_text(&name_node, source),
+                        kind: SymbolKind::Type,
+                        line: child.start_position().row + 1,
+                        signature: None,
+                        doc_summary,
+                        doc_full,
+                        parsed_signature: None,
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, Vec::new()));
+                }
+            }
+            "lexical_declaration" | "variable_declaration" => {
+                nodes.extend(build_js_variable_nodes(&child, source));
+            }
+            "export_statement" => {
+                nodes.extend(build_js_symbol_tree(&child, source));
+            }
+            _ => {
+                nodes.extend(build_js_symbol_tree(&child, source));
+            }
+        }
+    }
+
+    nodes
+}
+
+fn ts_interface_member_nodes(node: &Node, parent_name: &str, source: &str) -> Vec<SymbolNode> {
+    let mut nodes = Vec::new();
+    let mut members = Vec::new();
+    extract_ts_interface_members(node, parent_name, source, &mut members);
+    for member in members {
+        let line = member.line;
+        nodes.push(SymbolNode {
+            symbol: member,
+            start_line: line,
+            end_line: line,
+            children: Vec::new(),
+        });
+    }
+    nodes
+}
+
+fn build_js_class_method_nodes(class_body: &Node, source: &str) -> Vec<SymbolNode> {
+    let mut nodes = Vec::new();
+    let mut cursor = class_body.walk();
+    for item in class_body.children(&mut cursor) {
+        if item.kind() == "method_definition" {
+            if let Some(name_node) = item.child_by_field_name("name") {
+                let (doc_summary, doc_full) = doc_fields(get_doc_comment(
+                    &item,
+                    source,
+                    &SupportedLanguage::JavaScript,
+                ));
+                let symbol = Symbol {
+                    name: get_text(&name_node, source),
+                    kind: SymbolKind::Method,
+                    line: item.start_position().row + 1,
+                    signature: None,
+                    doc_summary,
+                    doc_full,
+                    parsed_signature: Some(parse_js_signature(&item, source)),
+                    parent: None,
+                };
+                nodes.push(make_node(symbol, &item, Vec::new()));
+            }
+        }
+    }
+    nodes
+}
+
+fn build_js_variable_nodes(node: &Node, source: &str) -> Vec<SymbolNode> {
+    let mut nodes = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() == "variable_declarator" {
+            if let Some(name_node) = child.child_by_field_name("name") {
+                if let Some(value) = child.child_by_field_name("value") {
+                    let kind = match value.kind() {
+                        "arrow_function" | "function" => SymbolKind::Function,
+                        _ => SymbolKind::Variable,
+                    };
+                    let name = get_text(&name_node, source);
+                    let parsed_signature = matches!(value.kind(), "arrow_function" | "function")
+                        .then(|| parse_js_value_signature(&name, &value, source));
+                    let symbol = Symbol {
+                        name,
+                        kind,
+                        line: child.start_position().row + 1,
+                        signature: None,
+                        doc_summary: None,
+                        doc_full: None,
+                        parsed_signature,
+                        parent: None,
+                    };
+                    nodes.push(make_node(symbol, &child, Vec::new()));
+                }
+            }
+        }
+    }
+    nodes
+}
+
+/// Renders a [`SymbolTree`] as an indented outline, one symbol per line
+/// with its line range — similar to an editor's outline/breadcrumb panel.
+///
+/// # Example Output (Rust)
+/// ```text
+/// impl Circle (5:9)
+///     fn new() -> Self (6:6)
+///     fn area(&self) -> f64 (7:7)
+/// ```
+pub fn render_symbol_tree(tree: &SymbolTree) -> String {
+    let mut out = String::new();
+    render_symbol_nodes(&tree.roots, 0, &mut out);
+    out
+}
+
+fn render_symbol_nodes(nodes: &[SymbolNode], indent: usize, out: &mut String) {
+    let indent_str = "    ".repeat(indent);
+    for node in nodes {
+        // A signature already spells out the kind (`fn new()`, `impl Circle`);
+        // fall back to "kind name" only for symbols that don't have one.
+        let label = match &node.symbol.signature {
+            Some(sig) => sig.clone(),
+            None => format!("{} {}", node.symbol.kind, node.symbol.name),
+        };
+        out.push_str(&format!(
+            "{}{} ({}:{})\n",
+            indent_str, label, node.start_line, node.end_line
+        ));
+        render_symbol_nodes(&node.children, indent + 1, out);
+    }
+}
+
+/// One call site found inside a function or method body: `caller` invokes
+/// `callee` at `line`:`column` (both 1-indexed). `is_method` distinguishes
+/// `obj.callee()` (a field/member/attribute access callee) from a plain
+/// function call or path-qualified associated call like `Type::callee()`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CallEdge {
+    pub caller: String,
+    pub callee: String,
+    pub line: usize,
+    pub column: usize,
+    pub is_method: bool,
+}
+
+/// The call graph extracted from a file's functions and methods. See
+/// [`extract_calls`].
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct CallGraph {
+    pub edges: Vec<CallEdge>,
+}
+
+impl CallGraph {
+    /// Everything `caller` invokes, in source order.
+    pub fn callees_of(&self, caller: &str) -> Vec<&CallEdge> {
+        self.edges.iter().filter(|e| e.caller == caller).collect()
+    }
+
+    /// Every call site that invokes `callee`, across all callers.
+    pub fn callers_of(&self, callee: &str) -> Vec<&CallEdge> {
+        self.edges.iter().filter(|e| e.callee == callee).collect()
+    }
+
+    /// `Function` symbols from `symbols` that never show up as a callee in
+    /// this graph — candidates for dead code. Lexical name resolution means
+    /// this is only a hint: a function called solely through a trait object
+    /// or callback passed by name elsewhere won't be recognized as called.
+    pub fn dead_functions<'a>(&self, symbols: &'a [Symbol]) -> Vec<&'a Symbol> {
+        symbols
+            .iter()
+            .filter(|s| s.kind == SymbolKind::Function)
+            .filter(|s| !self.edges.iter().any(|e| e.callee == s.name))
+            .collect()
+    }
+}
+
+/// Extracts a name-based call graph from a parsed syntax tree.
+///
+/// Walks every call node (`call_expression` for Rust/JS/TS, `call` for
+/// Python; Rust also has `macro_invocation`) and resolves its callee to a
+/// plain name — `obj.method(...)` and `Type::assoc(...)` both resolve to
+/// their trailing identifier, so this can't tell apart two same-named
+/// methods on different types. Each call site is then attributed to its
+/// enclosing function/method by range containment against
+/// [`extract_symbol_tree`]; calls outside any function body (e.g. in a
+/// `const` initializer) are dropped.
+///
+/// Name resolution is intentionally this simple — no type inference — as
+/// a first cut good enough to answer "who calls `X`" and "what does `X`
+/// call".
+///
+/// # Supported Languages
+/// - Rust: `call_expression` and `macro_invocation`
+/// - Python: `call`
+/// - JavaScript/TypeScript: `call_expression`
+pub fn extract_calls(tree: &Tree, source: &str, lang: &SupportedLanguage) -> CallGraph {
+    let root = tree.root_node();
+    let outline = extract_symbol_tree(tree, source, lang);
+
+    let mut sites = Vec::new();
+    match lang {
+        SupportedLanguage::Rust => collect_rust_calls(&root, source, &mut sites),
+        SupportedLanguage::Python => collect_python_calls(&root, source, &mut sites),
+        SupportedLanguage::JavaScript | SupportedLanguage::TypeScript => {
+            collect_js_calls(&root, source, &mut sites)
+        }
+        SupportedLanguage::Dynamic(_) => {}
+    }
+
+    let mut edges = Vec::new();
+    let mut seen = HashSet::new();
+    for (line, column, callee, is_method) in sites {
+        let Some(caller_node) = outline.symbol_at_line(line) else {
+            continue;
+        };
+        if !matches!(
+            caller_node.symbol.kind,
+            SymbolKind::Function | SymbolKind::Method
+        ) {
+            continue;
+        }
+        let caller = caller_node.symbol.name.clone();
+        // The same callee can appear more than once on one line (e.g. two
+        // arguments to the same call), which would otherwise double-count
+        // it as a distinct edge.
+        if !seen.insert((caller.clone(), callee.clone(), line)) {
+            continue;
+        }
+        edges.push(CallEdge { caller, callee, line, column, is_method });
+    }
+
+    CallGraph { edges }
+}
+
+/// A call site as collected from the AST, before it's attributed to an
+/// enclosing caller: `(line, column, callee, is_method)`, both 1-indexed.
+type CallSite = (usize, usize, String, bool);
+
+fn collect_rust_calls(node: &Node, source: &str, sites: &mut Vec<CallSite>) {
+    match node.kind() {
+        "call_expression" => {
+            if let Some(function) = node.child_by_field_name("function") {
+                let position = function.start_position();
+                sites.push((
+                    position.row + 1,
+                    position.column + 1,
+                    callee_name(&function, source),
+                    is_method_callee(&function),
+                ));
+            }
+        }
+        "macro_invocation" => {
+            if let Some(macro_node) = node.child_by_field_name("macro") {
+                let position = macro_node.start_position();
+                sites.push((
+                    position.row + 1,
+                    position.column + 1,
+                    get_text(&macro_node, source),
+                    false,
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_rust_calls(&child, source, sites);
+    }
+}
+
+fn collect_python_calls(node: &Node, source: &str, sites: &mut Vec<CallSite>) {
+    if node.kind() == "call" {
+        if let Some(function) = node.child_by_field_name("function") {
+            let position = function.start_position();
+            sites.push((
+                position.row + 1,
+                position.column + 1,
+                callee_name(&function, source),
+                is_method_callee(&function),
+            ));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_python_calls(&child, source, sites);
+    }
+}
+
+fn collect_js_calls(node: &Node, source: &str, sites: &mut Vec<CallSite>) {
+    if node.kind() == "call_expression" {
+        if let Some(function) = node.child_by_field_name("function") {
+            let position = function.start_position();
+            sites.push((
+                position.row + 1,
+                position.column + 1,
+                callee_name(&function, source),
+                is_method_callee(&function),
+            ));
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_js_calls(&child, source, sites);
+    }
+}
+
+/// Whether a call's `function` node is a field/member/attribute access
+/// (`obj.callee(...)`) as opposed to a bare identifier or path-qualified
+/// associated call (`Type::callee(...)`).
+fn is_method_callee(function: &Node) -> bool {
+    matches!(
+        function.kind(),
+        "field_expression" | "member_expression" | "attribute"
+    )
+}
+
+/// Resolves a call's `function` node to a plain callee name. Field-access
+/// callees (`obj.method`, Rust `field_expression`/JS `member_expression`/
+/// Python `attribute`) resolve to the accessed field; path callees (Rust
+/// `scoped_identifier`, e.g. `Type::assoc`) resolve to the final segment.
+fn callee_name(function: &Node, source: &str) -> String {
+    match function.kind() {
+        "field_expression" => function
+            .child_by_field_name("field")
+            .map(|n| get_text(&n, source))
+            .unwrap_or_else(|| get_text(function, source)),
+        "member_expression" => function
+            .child_by_field_name("property")
+            .map(|n| get_text(&n, source))
+            .unwrap_or_else(|| get_text(function, source)),
+        "attribute" => function
+            .child_by_field_name("attribute")
+            .map(|n| get_text(&n, source))
+            .unwrap_or_else(|| get_text(function, source)),
+        "scoped_identifier" => function
+            .child_by_field_name("name")
+            .map(|n| get_text(&n, source))
+            .unwrap_or_else(|| get_text(function, source)),
+        _ => get_text(function, source),
+    }
+}
+
+/// One already-read, already-parsed source file, as input to
+/// [`find_references`]. Kept as a plain bundle of borrowed data so the
+/// subsystem stays pure AST logic with no filesystem access of its own —
+/// callers (e.g. a project walk) own reading and parsing.
+pub struct ParsedFile<'a> {
+    pub path: &'a str,
+    pub source: &'a str,
+    pub tree: &'a Tree,
+}
+
+/// Whether a [`Reference`] reads or writes the symbol it names.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferenceKind {
+    Read,
+    Write,
+}
+
+/// A single usage of a symbol found by [`find_references`] — as opposed to
+/// its definition, which [`extract_symbols`] already covers.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Reference {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    /// The enclosing function or method, if the reference occurs inside one.
+    pub enclosing: Option<String>,
+    pub category: ReferenceKind,
+}
+
+/// Finds every usage of `symbol_name` across `files`, excluding the
+/// definition site(s) themselves.
+///
+/// Mirrors the two-phase approach proven reference-search tools use: a
+/// cheap text scan first finds candidate byte offsets where the identifier
+/// string occurs (skipping ones that are really a substring of a longer
+/// identifier), then each candidate is confirmed by descending into that
+/// file's tree at the matching offset and checking the node is a reference
+/// — an `identifier`/`type_identifier` (Rust), `identifier` (Python), or
+/// `identifier`/`property_identifier` (JS) that isn't itself the `name`
+/// field of a definition node. This keeps the search near-linear in file
+/// size instead of fully resolving every identifier in every file.
+pub fn find_references(symbol_name: &str, files: &[ParsedFile], lang: &SupportedLanguage) -> Vec<Reference> {
+    let mut references = Vec::new();
+    for file in files {
+        collect_file_references(symbol_name, file, lang, &mut references);
+    }
+    references
+}
+
+fn collect_file_references(
+    symbol_name: &str,
+    file: &ParsedFile,
+    lang: &SupportedLanguage,
+    out: &mut Vec<Reference>,
+) {
+    if symbol_name.is_empty() {
+        return;
+    }
+
+    let root = file.tree.root_node();
+    let outline = extract_symbol_tree(file.tree, file.source, lang);
+
+    let mut search_from = 0;
+    while let Some(offset) = file.source[search_from..].find(symbol_name) {
+        let byte = search_from + offset;
+        search_from = byte + symbol_name.len();
+
+        if !is_identifier_boundary(file.source, byte, symbol_name.len()) {
+            continue;
+        }
+        let Some(node) = root.descendant_for_byte_range(byte, byte + symbol_name.len()) else {
+            continue;
+        };
+        if get_text(&node, file.source) != symbol_name {
+            continue;
+        }
+        if !is_reference_node_kind(node.kind(), lang) || is_definition_name(&node) {
+            continue;
+        }
+
+        let position = node.start_position();
+        let enclosing = outline
+            .symbol_at_line(position.row + 1)
+            .filter(|n| matches!(n.symbol.kind, SymbolKind::Function | SymbolKind::Method))
+            .map(|n| n.symbol.name.clone());
+
+        out.push(Reference {
+            file: file.path.to_string(),
+            line: position.row + 1,
+            column: position.column + 1,
+            enclosing,
+            category: reference_category(&node),
+        });
+    }
+}
+
+/// Rejects matches that are really part of a longer identifier (`foo`
+/// inside `foobar`) by checking the bytes immediately surrounding the hit
+/// aren't themselves identifier characters.
+fn is_identifier_boundary(source: &str, start: usize, len: usize) -> bool {
+    let before_ok = source[..start]
+        .chars()
+        .next_back()
+        .is_none_or(|c| !c.is_alphanumeric() && c != '_');
+    let end = start + len;
+    let after_ok = source[end..]
+        .chars()
+        .next()
+        .is_none_or(|c| !c.is_alphanumeric() && c != '_');
+    before_ok && after_ok
+}
+
+fn is_reference_node_kind(kind: &str, lang: &SupportedLanguage) -> bool {
+    match lang {
+        SupportedLanguage::Rust => matches!(kind, "identifier" | "type_identifier"),
+        SupportedLanguage::Python => kind == "identifier",
+        SupportedLanguage::JavaScript | SupportedLanguage::TypeScript => {
+            matches!(kind, "identifier" | "property_identifier")
+        }
+        SupportedLanguage::Dynamic(_) => kind == "identifier",
+    }
+}
+
+/// True if `node` is the `name` field of a definition node (function,
+/// struct, class, etc.) — i.e. the declaration site itself rather than a
+/// reference to it.
+fn is_definition_name(node: &Node) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    let is_name_field = parent
+        .child_by_field_name("name")
+        .is_some_and(|n| n.id() == node.id());
+
+    is_name_field
+        && matches!(
+            parent.kind(),
+            "function_item"
+                | "struct_item"
+                | "enum_item"
+                | "trait_item"
+                | "mod_item"
+                | "const_item"
+                | "type_item"
+                | "function_definition"
+                | "class_definition"
+                | "function_declaration"
+                | "class_declaration"
+                | "interface_declaration"
+                | "type_alias_declaration"
+                | "variable_declarator"
+        )
+}
+
+/// Classifies a reference as a write if it sits on the left side of an
+/// assignment or a `let` binding's pattern, walking up through enclosing
+/// expressions (e.g. a field access) until either an assignment/binding or
+/// a statement boundary is found.
+fn reference_category(node: &Node) -> ReferenceKind {
+    let mut current = *node;
+    while let Some(parent) = current.parent() {
+        match parent.kind() {
+            "assignment_expression" | "assignment" => {
+                let is_left = parent
+                    .child_by_field_name("left")
+                    .is_some_and(|n| n.id() == current.id());
+                return if is_left { ReferenceKind::Write } else { ReferenceKind::Read };
+            }
+            "let_declaration" => {
+                let is_pattern = parent
+                    .child_by_field_name("pattern")
+                    .is_some_and(|n| n.id() == current.id());
+                return if is_pattern { ReferenceKind::Write } else { ReferenceKind::Read };
+            }
+            "block" | "function_item" | "function_definition" | "function_declaration" => break,
+            _ => {}
+        }
+        current = parent;
+    }
+    ReferenceKind::Read
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tree_sitter::Parser;
 
     fn parse_rust(source: &str) -> Tree {
         let mut parser = Parser::new();
@@ -870,16 +3213,53 @@ pub enum Result<T, E> {
         let structs: Vec<_> = symbols.iter().filter(|s| s.kind == SymbolKind::Struct).collect();
         let enums: Vec<_> = symbols.iter().filter(|s| s.kind == SymbolKind::Enum).collect();
 
-        assert_eq!(structs.len(), 2);
-        assert_eq!(enums.len(), 2);
+        assert_eq!(structs.len(), 2);
+        assert_eq!(enums.len(), 2);
+
+        // Check struct names
+        assert!(structs.iter().any(|s| s.name == "Point"));
+        assert!(structs.iter().any(|s| s.name == "User"));
+
+        // Check enum names
+        assert!(enums.iter().any(|s| s.name == "Color"));
+        assert!(enums.iter().any(|s| s.name == "Result"));
+    }
+
+    #[test]
+    fn test_extract_rust_struct_fields_and_enum_variants() {
+        let source = r#"
+struct Point {
+    /// The horizontal coordinate.
+    x: i32,
+    y: i32,
+}
+
+struct Pair(i32, String);
+
+enum Color {
+    /// The absence of color.
+    Red,
+    Green,
+    Blue,
+}
+"#;
+        let tree = parse_rust(source);
+        let symbols = extract_symbols(&tree, source, &SupportedLanguage::Rust);
+
+        let fields: Vec<_> = symbols.iter().filter(|s| s.kind == SymbolKind::Field).collect();
+        let variants: Vec<_> = symbols.iter().filter(|s| s.kind == SymbolKind::Variant).collect();
 
-        // Check struct names
-        assert!(structs.iter().any(|s| s.name == "Point"));
-        assert!(structs.iter().any(|s| s.name == "User"));
+        assert_eq!(fields.len(), 4);
+        let x = fields.iter().find(|f| f.name == "x").unwrap();
+        assert_eq!(x.parent.as_deref(), Some("Point"));
+        assert_eq!(x.doc_summary.as_deref(), Some("The horizontal coordinate."));
+        assert!(fields.iter().any(|f| f.name == "0" && f.parent.as_deref() == Some("Pair")));
+        assert!(fields.iter().any(|f| f.name == "1" && f.parent.as_deref() == Some("Pair")));
 
-        // Check enum names
-        assert!(enums.iter().any(|s| s.name == "Color"));
-        assert!(enums.iter().any(|s| s.name == "Result"));
+        assert_eq!(variants.len(), 3);
+        let red = variants.iter().find(|v| v.name == "Red").unwrap();
+        assert_eq!(red.parent.as_deref(), Some("Color"));
+        assert_eq!(red.doc_summary.as_deref(), Some("The absence of color."));
     }
 
     #[test]
@@ -1006,6 +3386,30 @@ class Admin(User):
         assert!(methods.iter().any(|s| s.name == "get_name"));
     }
 
+    #[test]
+    fn test_extract_python_class_attributes() {
+        let source = r#"
+class Config:
+    debug: bool = False
+    name = "default"
+
+    def __init__(self):
+        self.runtime_only = True
+"#;
+        let tree = parse_python(source);
+        let symbols = extract_symbols(&tree, source, &SupportedLanguage::Python);
+
+        let fields: Vec<_> = symbols.iter().filter(|s| s.kind == SymbolKind::Field).collect();
+        assert_eq!(fields.len(), 2);
+        let debug = fields.iter().find(|f| f.name == "debug").unwrap();
+        assert_eq!(debug.parent.as_deref(), Some("Config"));
+        assert_eq!(debug.signature.as_deref(), Some("bool"));
+        assert!(fields.iter().any(|f| f.name == "name"));
+        // `self.runtime_only` is set inside a method body, not the class
+        // body, so it isn't visible without evaluating the method.
+        assert!(!fields.iter().any(|f| f.name == "runtime_only"));
+    }
+
     #[test]
     fn test_extract_javascript_symbols() {
         let source = r#"
@@ -1089,11 +3493,87 @@ fn undocumented() {}
         let symbols = extract_symbols(&tree, source, &SupportedLanguage::Rust);
 
         let documented = symbols.iter().find(|s| s.name == "documented").unwrap();
-        assert!(documented.doc_comment.is_some());
-        assert!(documented.doc_comment.as_ref().unwrap().contains("documented function"));
+        assert_eq!(documented.doc_summary.as_deref(), Some("This is a documented function"));
+        assert_eq!(documented.doc_full.as_deref(), Some("This is a documented function"));
+
+        let multi_doc = symbols.iter().find(|s| s.name == "multi_doc").unwrap();
+        assert_eq!(multi_doc.doc_summary.as_deref(), Some("Multi-line doc comment"));
+        assert_eq!(
+            multi_doc.doc_full.as_deref(),
+            Some("Multi-line doc comment\nwith additional details")
+        );
 
         let undocumented = symbols.iter().find(|s| s.name == "undocumented").unwrap();
-        assert!(undocumented.doc_comment.is_none());
+        assert!(undocumented.doc_summary.is_none());
+        assert!(undocumented.doc_full.is_none());
+    }
+
+    #[test]
+    fn test_python_docstring_full_body() {
+        let source = r#"
+def documented():
+    """First line.
+
+    Second paragraph with more detail.
+    """
+    pass
+"#;
+        let tree = parse_python(source);
+        let symbols = extract_symbols(&tree, source, &SupportedLanguage::Python);
+
+        let documented = symbols.iter().find(|s| s.name == "documented").unwrap();
+        assert_eq!(documented.doc_summary.as_deref(), Some("First line."));
+        assert_eq!(
+            documented.doc_full.as_deref(),
+            Some("First line.\n\n    Second paragraph with more detail.")
+        );
+    }
+
+    #[test]
+    fn test_python_docstring_single_quoted() {
+        let source = r#"
+def greet():
+    'Says hello.'
+    pass
+
+class Greeter:
+    "A friendly greeter."
+    pass
+"#;
+        let tree = parse_python(source);
+        let symbols = extract_symbols(&tree, source, &SupportedLanguage::Python);
+
+        let greet = symbols.iter().find(|s| s.name == "greet").unwrap();
+        assert_eq!(greet.doc_summary.as_deref(), Some("Says hello."));
+
+        let greeter = symbols.iter().find(|s| s.name == "Greeter").unwrap();
+        assert_eq!(greeter.doc_summary.as_deref(), Some("A friendly greeter."));
+    }
+
+    #[test]
+    fn test_js_jsdoc_extraction() {
+        let source = r#"
+/**
+ * Adds two numbers.
+ * @param a first number
+ */
+function add(a, b) { return a + b; }
+
+function plain() {}
+"#;
+        let tree = parse_javascript(source);
+        let symbols = extract_symbols(&tree, source, &SupportedLanguage::JavaScript);
+
+        let add = symbols.iter().find(|s| s.name == "add").unwrap();
+        assert_eq!(add.doc_summary.as_deref(), Some("Adds two numbers."));
+        assert_eq!(
+            add.doc_full.as_deref(),
+            Some("Adds two numbers.\n@param a first number")
+        );
+
+        let plain = symbols.iter().find(|s| s.name == "plain").unwrap();
+        assert!(plain.doc_summary.is_none());
+        assert!(plain.doc_full.is_none());
     }
 
     #[test]
@@ -1109,9 +3589,12 @@ fn main() {}
         let imports = find_imports(&tree, source, &SupportedLanguage::Rust);
 
         assert_eq!(imports.len(), 3);
-        assert!(imports.iter().any(|i| i.contains("std::collections::HashMap")));
-        assert!(imports.iter().any(|i| i.contains("std::path::Path")));
-        assert!(imports.iter().any(|i| i.contains("crate::utils::helper")));
+        assert!(imports.iter().any(|i| i.raw.contains("std::collections::HashMap")));
+        assert!(imports.iter().any(|i| i.module_path == vec!["std", "collections"]
+            && i.imported_names == vec![("HashMap".to_string(), None)]));
+        assert!(imports.iter().any(|i| i.raw.contains("std::path::Path")));
+        assert!(imports.iter().any(|i| i.raw.contains("crate::utils::helper")));
+        assert!(!imports.iter().any(|i| i.is_glob || i.is_relative));
     }
 
     #[test]
@@ -1129,10 +3612,24 @@ def main():
         let imports = find_imports(&tree, source, &SupportedLanguage::Python);
 
         assert!(imports.len() >= 4);
-        assert!(imports.iter().any(|i| i.contains("import os")));
-        assert!(imports.iter().any(|i| i.contains("import sys")));
-        assert!(imports.iter().any(|i| i.contains("from collections")));
-        assert!(imports.iter().any(|i| i.contains("from typing")));
+        assert!(imports.iter().any(|i| i.raw.contains("import os")));
+        assert!(imports.iter().any(|i| i.raw.contains("import sys")));
+        let from_collections = imports
+            .iter()
+            .find(|i| i.raw.contains("from collections"))
+            .unwrap();
+        assert_eq!(from_collections.module_path, vec!["collections"]);
+        assert_eq!(
+            from_collections.imported_names,
+            vec![("defaultdict".to_string(), None)]
+        );
+        assert!(!from_collections.is_relative);
+
+        let from_typing = imports.iter().find(|i| i.raw.contains("from typing")).unwrap();
+        assert_eq!(
+            from_typing.imported_names,
+            vec![("List".to_string(), None), ("Dict".to_string(), None)]
+        );
     }
 
     #[test]
@@ -1148,9 +3645,69 @@ function App() {}
         let imports = find_imports(&tree, source, &SupportedLanguage::JavaScript);
 
         assert_eq!(imports.len(), 3);
-        assert!(imports.iter().any(|i| i.contains("React")));
-        assert!(imports.iter().any(|i| i.contains("useState")));
-        assert!(imports.iter().any(|i| i.contains("utils")));
+        let default_import = imports.iter().find(|i| i.module_path == vec!["react"]
+            && i.imported_names == vec![("React".to_string(), None)])
+            .unwrap();
+        assert!(!default_import.is_relative);
+
+        let named_import = imports
+            .iter()
+            .find(|i| i.imported_names.iter().any(|(n, _)| n == "useState"))
+            .unwrap();
+        assert_eq!(
+            named_import.imported_names,
+            vec![("useState".to_string(), None), ("useEffect".to_string(), None)]
+        );
+
+        let namespace_import = imports
+            .iter()
+            .find(|i| i.module_path == vec!["./utils"])
+            .unwrap();
+        assert_eq!(
+            namespace_import.imported_names,
+            vec![("*".to_string(), Some("utils".to_string()))]
+        );
+        assert!(namespace_import.is_relative);
+    }
+
+    #[test]
+    fn test_python_signature_param_defaults() {
+        let source = r#"
+def greet(name: str, greeting: str = "hello", *args, **kwargs):
+    pass
+"#;
+        let tree = parse_python(source);
+        let symbols = extract_symbols(&tree, source, &SupportedLanguage::Python);
+        let greet = symbols.iter().find(|s| s.name == "greet").unwrap();
+        let sig = greet.parsed_signature.as_ref().unwrap();
+
+        let name_param = sig.params.iter().find(|p| p.name == "name").unwrap();
+        assert_eq!(name_param.ty.as_deref(), Some("str"));
+        assert_eq!(name_param.default, None);
+
+        let greeting_param = sig.params.iter().find(|p| p.name == "greeting").unwrap();
+        assert_eq!(greeting_param.ty.as_deref(), Some("str"));
+        assert_eq!(greeting_param.default.as_deref(), Some("\"hello\""));
+
+        assert!(sig.params.iter().any(|p| p.name == "*args"));
+        assert!(sig.params.iter().any(|p| p.name == "**kwargs"));
+    }
+
+    #[test]
+    fn test_js_signature_param_defaults() {
+        let source = r#"
+function greet(name, greeting = "hello") {}
+"#;
+        let tree = parse_javascript(source);
+        let symbols = extract_symbols(&tree, source, &SupportedLanguage::JavaScript);
+        let greet = symbols.iter().find(|s| s.name == "greet").unwrap();
+        let sig = greet.parsed_signature.as_ref().unwrap();
+
+        let name_param = sig.params.iter().find(|p| p.name == "name").unwrap();
+        assert_eq!(name_param.default, None);
+
+        let greeting_param = sig.params.iter().find(|p| p.name == "greeting").unwrap();
+        assert_eq!(greeting_param.default.as_deref(), Some("\"hello\""));
     }
 
     #[test]
@@ -1213,4 +3770,400 @@ class User:
         let skeleton = get_skeleton(&tree, source, &SupportedLanguage::Rust);
         assert!(skeleton.is_empty());
     }
+
+    #[test]
+    fn test_symbol_tree_rust_impl_methods_are_children() {
+        let source = r#"
+impl Circle {
+    fn new() -> Self {
+        Circle {}
+    }
+
+    pub fn area(&self) -> f64 {
+        0.0
+    }
+}
+"#;
+        let tree = parse_rust(source);
+        let symbol_tree = extract_symbol_tree(&tree, source, &SupportedLanguage::Rust);
+
+        assert_eq!(symbol_tree.roots.len(), 1);
+        let impl_node = &symbol_tree.roots[0];
+        assert_eq!(impl_node.symbol.kind, SymbolKind::Impl);
+        assert_eq!(impl_node.symbol.name, "Circle");
+        assert_eq!(impl_node.children.len(), 2);
+        assert!(impl_node.children.iter().any(|c| c.symbol.name == "new"));
+        assert!(impl_node.children.iter().any(|c| c.symbol.name == "area"));
+    }
+
+    #[test]
+    fn test_symbol_tree_rust_mod_nests_items() {
+        let source = r#"
+mod internal {
+    fn hidden() {}
+}
+"#;
+        let tree = parse_rust(source);
+        let symbol_tree = extract_symbol_tree(&tree, source, &SupportedLanguage::Rust);
+
+        let module = symbol_tree
+            .roots
+            .iter()
+            .find(|n| n.symbol.name == "internal")
+            .unwrap();
+        assert_eq!(module.symbol.kind, SymbolKind::Module);
+        assert_eq!(module.children.len(), 1);
+        assert_eq!(module.children[0].symbol.name, "hidden");
+    }
+
+    #[test]
+    fn test_symbol_tree_python_class_methods_are_children() {
+        let source = r#"
+class User:
+    def __init__(self, name):
+        self.name = name
+
+    def get_name(self):
+        return self.name
+"#;
+        let tree = parse_python(source);
+        let symbol_tree = extract_symbol_tree(&tree, source, &SupportedLanguage::Python);
+
+        let class = symbol_tree
+            .roots
+            .iter()
+            .find(|n| n.symbol.name == "User")
+            .unwrap();
+        assert_eq!(class.children.len(), 2);
+        assert!(class.children.iter().any(|c| c.symbol.name == "__init__"));
+        assert!(class.children.iter().any(|c| c.symbol.name == "get_name"));
+    }
+
+    #[test]
+    fn test_symbol_tree_js_class_methods_are_children() {
+        let source = r#"
+class User {
+    constructor(name) {
+        this.name = name;
+    }
+
+    getName() {
+        return this.name;
+    }
+}
+"#;
+        let tree = parse_javascript(source);
+        let symbol_tree = extract_symbol_tree(&tree, source, &SupportedLanguage::JavaScript);
+
+        let class = symbol_tree
+            .roots
+            .iter()
+            .find(|n| n.symbol.name == "User")
+            .unwrap();
+        assert_eq!(class.children.len(), 2);
+        assert!(class.children.iter().any(|c| c.symbol.name == "constructor"));
+        assert!(class.children.iter().any(|c| c.symbol.name == "getName"));
+    }
+
+    #[test]
+    fn test_symbol_at_line_finds_innermost_match() {
+        let source = r#"
+impl Circle {
+    fn new() -> Self {
+        Circle {}
+    }
+}
+"#;
+        let tree = parse_rust(source);
+        let symbol_tree = extract_symbol_tree(&tree, source, &SupportedLanguage::Rust);
+
+        // Line 3 is inside `fn new`, which is inside `impl Circle`.
+        let inner = symbol_tree.symbol_at_line(3).unwrap();
+        assert_eq!(inner.symbol.name, "new");
+
+        // A line outside any range shouldn't match.
+        assert!(symbol_tree.symbol_at_line(100).is_none());
+    }
+
+    #[test]
+    fn test_render_symbol_tree_shows_nesting_and_ranges() {
+        let source = r#"
+impl Circle {
+    fn new() -> Self {
+        Circle {}
+    }
+}
+"#;
+        let tree = parse_rust(source);
+        let symbol_tree = extract_symbol_tree(&tree, source, &SupportedLanguage::Rust);
+        let rendered = render_symbol_tree(&symbol_tree);
+
+        assert!(rendered.contains("impl Circle"));
+        assert!(rendered.contains("    fn new"));
+    }
+
+    #[test]
+    fn test_extract_calls_rust_resolves_field_and_path_callees() {
+        let source = r#"
+fn main() {
+    helper();
+    shape.draw();
+    Circle::new();
+    println!("hi");
+}
+
+fn helper() {}
+"#;
+        let tree = parse_rust(source);
+        let graph = extract_calls(&tree, source, &SupportedLanguage::Rust);
+
+        let callees: Vec<&str> = graph
+            .callees_of("main")
+            .iter()
+            .map(|e| e.callee.as_str())
+            .collect();
+        assert_eq!(callees, vec!["helper", "draw", "new", "println"]);
+    }
+
+    #[test]
+    fn test_find_references_rust_across_files_with_enclosing() {
+        let main_source = r#"
+fn process(count: u32) -> u32 {
+    count + 1
+}
+
+fn main() {
+    let result = process(5);
+    println!("{}", result);
+}
+"#;
+        let other_source = r#"
+fn retry(count: u32) {
+    process(count);
+}
+"#;
+        let main_tree = parse_rust(main_source);
+        let other_tree = parse_rust(other_source);
+        let files = vec![
+            ParsedFile { path: "src/main.rs", source: main_source, tree: &main_tree },
+            ParsedFile { path: "src/other.rs", source: other_source, tree: &other_tree },
+        ];
+
+        let references = find_references("process", &files, &SupportedLanguage::Rust);
+
+        // The `fn process` definition itself must not show up as a reference.
+        assert!(references.iter().all(|r| r.line != 2 || r.file != "src/main.rs"));
+
+        let main_ref = references.iter().find(|r| r.file == "src/main.rs").unwrap();
+        assert_eq!(main_ref.enclosing.as_deref(), Some("main"));
+
+        let other_ref = references.iter().find(|r| r.file == "src/other.rs").unwrap();
+        assert_eq!(other_ref.enclosing.as_deref(), Some("retry"));
+
+        assert_eq!(references.len(), 2);
+    }
+
+    #[test]
+    fn test_find_references_classifies_read_and_write() {
+        let source = r#"
+fn main() {
+    let total = 0;
+    total = total + 1;
+}
+"#;
+        let tree = parse_rust(source);
+        let files = vec![ParsedFile { path: "src/main.rs", source, tree: &tree }];
+
+        let references = find_references("total", &files, &SupportedLanguage::Rust);
+        assert_eq!(references.len(), 3);
+
+        let writes: Vec<_> = references.iter().filter(|r| r.category == ReferenceKind::Write).collect();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(writes[0].line, 3);
+        assert_eq!(writes[1].line, 4);
+
+        let reads: Vec<_> = references.iter().filter(|r| r.category == ReferenceKind::Read).collect();
+        assert_eq!(reads.len(), 1);
+        assert_eq!(reads[0].line, 4);
+    }
+
+    #[test]
+    fn test_find_references_rejects_substring_matches() {
+        let source = "fn foo() {}\nfn foobar() {\n    foo();\n}\n";
+        let tree = parse_rust(source);
+        let files = vec![ParsedFile { path: "src/main.rs", source, tree: &tree }];
+
+        let references = find_references("foo", &files, &SupportedLanguage::Rust);
+
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].line, 3);
+    }
+
+    #[test]
+    fn test_extract_calls_attributes_call_to_enclosing_method() {
+        let source = r#"
+impl Circle {
+    fn new() -> Self {
+        Self::default()
+    }
+}
+"#;
+        let tree = parse_rust(source);
+        let graph = extract_calls(&tree, source, &SupportedLanguage::Rust);
+
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].caller, "new");
+        assert_eq!(graph.edges[0].callee, "default");
+    }
+
+    #[test]
+    fn test_extract_calls_ignores_calls_outside_any_function() {
+        let source = "const X: u32 = compute();\n";
+        let tree = parse_rust(source);
+        let graph = extract_calls(&tree, source, &SupportedLanguage::Rust);
+
+        assert!(graph.edges.is_empty());
+    }
+
+    #[test]
+    fn test_extract_calls_python_and_javascript() {
+        let py_source = "def main():\n    helper()\n    obj.method()\n";
+        let py_tree = parse_python(py_source);
+        let py_graph = extract_calls(&py_tree, py_source, &SupportedLanguage::Python);
+        assert_eq!(
+            py_graph.callers_of("helper")[0].caller,
+            "main"
+        );
+        assert_eq!(py_graph.callers_of("method")[0].caller, "main");
+
+        let js_source = "function main() {\n    helper();\n    obj.method();\n}\n";
+        let js_tree = parse_javascript(js_source);
+        let js_graph = extract_calls(&js_tree, js_source, &SupportedLanguage::JavaScript);
+        assert_eq!(js_graph.callers_of("helper")[0].caller, "main");
+        assert_eq!(js_graph.callers_of("method")[0].caller, "main");
+    }
+
+    #[test]
+    fn test_extract_calls_dedupes_same_line_repeats() {
+        let source = "fn main() {\n    add(helper(), helper());\n}\n\nfn helper() -> i32 { 0 }\nfn add(a: i32, b: i32) -> i32 { a + b }\n";
+        let tree = parse_rust(source);
+        let graph = extract_calls(&tree, source, &SupportedLanguage::Rust);
+
+        let helper_calls: Vec<_> = graph
+            .callees_of("main")
+            .into_iter()
+            .filter(|e| e.callee == "helper")
+            .collect();
+        assert_eq!(helper_calls.len(), 1);
+    }
+
+    #[test]
+    fn test_call_graph_dead_functions() {
+        let source = "fn main() {\n    used();\n}\n\nfn used() {}\nfn unused() {}\n";
+        let tree = parse_rust(source);
+        let graph = extract_calls(&tree, source, &SupportedLanguage::Rust);
+        let symbols = extract_symbols(&tree, source, &SupportedLanguage::Rust);
+
+        // `main` is its own kind of "uncalled" (it's an entry point, not
+        // dead), but this is lexical name resolution with no special casing
+        // for entry points, so it shows up alongside genuinely dead code.
+        let dead: Vec<&str> = graph.dead_functions(&symbols).iter().map(|s| s.name.as_str()).collect();
+        assert!(dead.contains(&"unused"));
+        assert!(!dead.contains(&"used"));
+    }
+
+    #[test]
+    fn test_extract_symbols_rust_use_declarations() {
+        let source = r#"
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::io::Result as IoResult;
+use crate::analysis::symbols::*;
+
+fn main() {}
+"#;
+        let tree = parse_rust(source);
+        let symbols = extract_symbols(&tree, source, &SupportedLanguage::Rust);
+        let imports: Vec<_> = symbols.iter().filter(|s| s.kind == SymbolKind::Import).collect();
+
+        let hashmap = imports.iter().find(|s| s.name == "std::collections::HashMap").unwrap();
+        assert_eq!(hashmap.signature.as_deref(), Some("HashMap"));
+
+        let fmt_self = imports.iter().find(|s| s.name == "std::fmt").unwrap();
+        assert_eq!(fmt_self.signature.as_deref(), Some("fmt"));
+
+        let display = imports.iter().find(|s| s.name == "std::fmt::Display").unwrap();
+        assert_eq!(display.signature.as_deref(), Some("Display"));
+
+        let aliased = imports.iter().find(|s| s.name == "std::io::Result").unwrap();
+        assert_eq!(aliased.signature.as_deref(), Some("IoResult"));
+
+        let wildcard = imports.iter().find(|s| s.name == "crate::analysis::symbols::*").unwrap();
+        assert_eq!(wildcard.signature, None);
+    }
+
+    #[test]
+    fn test_extract_symbols_python_imports() {
+        let source = r#"
+import os
+import numpy as np
+from . import sibling
+from ..pkg import thing
+from pkg.mod import foo, bar as baz
+"#;
+        let tree = parse_python(source);
+        let symbols = extract_symbols(&tree, source, &SupportedLanguage::Python);
+        let imports: Vec<_> = symbols.iter().filter(|s| s.kind == SymbolKind::Import).collect();
+
+        let os_import = imports.iter().find(|s| s.name == "os").unwrap();
+        assert_eq!(os_import.signature.as_deref(), Some("os"));
+
+        let np_import = imports.iter().find(|s| s.name == "numpy").unwrap();
+        assert_eq!(np_import.signature.as_deref(), Some("np"));
+
+        let sibling = imports.iter().find(|s| s.name == ".sibling").unwrap();
+        assert_eq!(sibling.signature.as_deref(), Some("sibling"));
+
+        let thing = imports.iter().find(|s| s.name == "..pkg.thing").unwrap();
+        assert_eq!(thing.signature.as_deref(), Some("thing"));
+
+        let foo = imports.iter().find(|s| s.name == "pkg.mod.foo").unwrap();
+        assert_eq!(foo.signature.as_deref(), Some("foo"));
+
+        let baz = imports.iter().find(|s| s.name == "pkg.mod.bar").unwrap();
+        assert_eq!(baz.signature.as_deref(), Some("baz"));
+    }
+
+    #[test]
+    fn test_extract_symbols_js_imports_and_reexports() {
+        let source = r#"
+import React from 'react';
+import { useState, useEffect as useFx } from 'react';
+import * as utils from './utils';
+import './side-effect.css';
+export { helper } from './helper';
+export function notAnImport() {}
+"#;
+        let tree = parse_javascript(source);
+        let symbols = extract_symbols(&tree, source, &SupportedLanguage::JavaScript);
+        let imports: Vec<_> = symbols.iter().filter(|s| s.kind == SymbolKind::Import).collect();
+
+        let react = imports.iter().find(|s| s.name == "react" && s.signature.as_deref() == Some("React")).unwrap();
+        assert_eq!(react.signature.as_deref(), Some("React"));
+
+        let named = imports.iter().find(|s| s.signature.as_deref() == Some("useState, useFx")).unwrap();
+        assert_eq!(named.name, "react");
+
+        let star = imports.iter().find(|s| s.name == "./utils").unwrap();
+        assert_eq!(star.signature.as_deref(), Some("utils"));
+
+        let side_effect = imports.iter().find(|s| s.name == "./side-effect.css").unwrap();
+        assert_eq!(side_effect.signature, None);
+
+        let reexport = imports.iter().find(|s| s.name == "./helper").unwrap();
+        assert_eq!(reexport.signature.as_deref(), Some("helper"));
+
+        assert!(symbols.iter().any(|s| s.kind == SymbolKind::Function && s.name == "notAnImport"));
+        assert!(imports.iter().all(|s| s.name != "notAnImport"));
+    }
 }
@@ -0,0 +1,224 @@
+//! Pluggable token-count estimators for budget arithmetic in `build_context`.
+//!
+//! `build_context` only needs *a* consistent token count per string to
+//! budget against, and which estimator produces it is swappable: the
+//! default [`HeuristicTokenizer`] is a fast word/char/punctuation estimate,
+//! while [`BpeTokenizer`] loads a merges/vocab table and runs real
+//! byte-pair encoding for callers that want counts matching a specific
+//! model's tokenizer.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Punctuation/operator characters treated as individual tokens and, for
+/// [`BpeTokenizer`], as pre-tokenization split points.
+const OPERATOR_CHARS: &[char] = &[
+    '(', ')', '{', '}', '[', ']', ';', ',', '.', ':', '<', '>', '=', '+', '-', '*', '/', '&', '|', '!', '@', '#', '$', '%', '^',
+];
+
+/// Estimates a token count for a piece of text.
+///
+/// Implementations don't need to match any particular model's tokenizer
+/// exactly, but a closer match means `build_context`'s token budget is
+/// less likely to over- or under-fill.
+pub trait Tokenizer {
+    fn estimate_tokens(&self, text: &str) -> usize;
+}
+
+/// The original hybrid word/char/punctuation heuristic. Used whenever a
+/// caller doesn't need counts matching a specific model.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicTokenizer;
+
+impl Tokenizer for HeuristicTokenizer {
+    fn estimate_tokens(&self, text: &str) -> usize {
+        estimate_tokens_heuristic(text)
+    }
+}
+
+/// Estimate token count for text using a hybrid word/character approach.
+///
+/// This provides a more accurate estimate than pure character count,
+/// especially for code which tends to have shorter tokens due to
+/// punctuation and operators.
+///
+/// # Algorithm
+/// 1. Count words (split on whitespace)
+/// 2. Count punctuation/operators (often individual tokens in code)
+/// 3. Character-based estimate (non-whitespace / 4)
+/// 4. Weighted word estimate (words * 1.3 + punctuation / 2)
+/// 5. Average the character and word estimates
+pub fn estimate_tokens_heuristic(text: &str) -> usize {
+    if text.is_empty() {
+        return 0;
+    }
+
+    // Count words
+    let word_count = text.split_whitespace().count();
+
+    // Count punctuation/operators (these are often individual tokens)
+    let punct_count = text.chars().filter(|c| OPERATOR_CHARS.contains(c)).count();
+
+    // Character-based estimate (for non-whitespace)
+    let char_count = text.chars().filter(|c| !c.is_whitespace()).count();
+    let char_estimate = (char_count + 3) / 4;
+
+    // Weighted average: code typically has ~1.3 tokens per word due to operators
+    // and shorter identifiers
+    let word_estimate = (word_count as f64 * 1.3) as usize + punct_count / 2;
+
+    // Take the average of both approaches for robustness
+    (char_estimate + word_estimate) / 2
+}
+
+/// Byte-pair-encoding tokenizer backed by a loaded merges/vocab table, for
+/// callers that want counts matching a specific model's tokenizer.
+///
+/// Compiled merge ranks are cached in a `HashMap<(String, String), u32>`
+/// (lower rank = applied first) for O(1) adjacent-pair lookup during
+/// merging.
+pub struct BpeTokenizer {
+    merges: HashMap<(String, String), u32>,
+    vocab: HashSet<String>,
+}
+
+impl BpeTokenizer {
+    /// Loads a merges table (GPT-2/RoBERTa `merges.txt` style: one
+    /// `piece_a piece_b` rule per line, highest-priority first) and a
+    /// vocab table (one known subword unit per line) from disk.
+    pub fn load(merges_path: &Path, vocab_path: &Path) -> Result<Self> {
+        let merges_text = std::fs::read_to_string(merges_path)
+            .with_context(|| format!("Failed to read merges table at {}", merges_path.display()))?;
+        let vocab_text = std::fs::read_to_string(vocab_path)
+            .with_context(|| format!("Failed to read vocab table at {}", vocab_path.display()))?;
+
+        let mut merges = HashMap::new();
+        for (rank, line) in merges_text.lines().enumerate() {
+            let mut parts = line.split_whitespace();
+            let (Some(a), Some(b)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            merges.insert((a.to_string(), b.to_string()), rank as u32);
+        }
+
+        let vocab = vocab_text.lines().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect();
+
+        Ok(Self { merges, vocab })
+    }
+
+    /// Splits `text` into word pieces on whitespace and on the same
+    /// operator/punctuation characters the heuristic tokenizer enumerates,
+    /// keeping each operator character as its own piece.
+    fn pre_tokenize(text: &str) -> Vec<String> {
+        let mut pieces = Vec::new();
+        let mut current = String::new();
+
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !current.is_empty() {
+                    pieces.push(std::mem::take(&mut current));
+                }
+            } else if OPERATOR_CHARS.contains(&c) {
+                if !current.is_empty() {
+                    pieces.push(std::mem::take(&mut current));
+                }
+                pieces.push(c.to_string());
+            } else {
+                current.push(c);
+            }
+        }
+        if !current.is_empty() {
+            pieces.push(current);
+        }
+
+        pieces
+    }
+
+    /// Repeatedly merges the highest-priority (lowest-rank) adjacent pair
+    /// in `piece` until no merge rule applies, returning the resulting
+    /// subword count. Falls back to a char count if `piece` contains a
+    /// character with no single-character entry in the vocab.
+    fn bpe_token_count(&self, piece: &str) -> usize {
+        let mut symbols: Vec<String> = piece.chars().map(|c| c.to_string()).collect();
+
+        if symbols.iter().any(|s| !self.vocab.contains(s)) {
+            return symbols.len();
+        }
+
+        loop {
+            let best = symbols
+                .windows(2)
+                .enumerate()
+                .filter_map(|(i, pair)| self.merges.get(&(pair[0].clone(), pair[1].clone())).map(|&rank| (i, rank)))
+                .min_by_key(|&(_, rank)| rank);
+
+            let Some((idx, _)) = best else {
+                break;
+            };
+
+            let merged = format!("{}{}", symbols[idx], symbols[idx + 1]);
+            symbols.splice(idx..=idx + 1, [merged]);
+        }
+
+        symbols.len()
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn estimate_tokens(&self, text: &str) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+
+        Self::pre_tokenize(text).iter().map(|piece| self.bpe_token_count(piece)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heuristic_empty() {
+        assert_eq!(HeuristicTokenizer.estimate_tokens(""), 0);
+    }
+
+    #[test]
+    fn test_heuristic_matches_free_function() {
+        assert_eq!(HeuristicTokenizer.estimate_tokens("hello world"), estimate_tokens_heuristic("hello world"));
+    }
+
+    fn bpe_fixture() -> BpeTokenizer {
+        let merges = HashMap::from([(("l".to_string(), "o".to_string()), 0), (("lo".to_string(), "w".to_string()), 1)]);
+        let vocab = HashSet::from(["l".to_string(), "o".to_string(), "w".to_string(), "lo".to_string(), "low".to_string()]);
+        BpeTokenizer { merges, vocab }
+    }
+
+    #[test]
+    fn test_bpe_merges_in_rank_order() {
+        let tokenizer = bpe_fixture();
+        // "low" -> [l, o, w] -> merge (l,o) -> [lo, w] -> merge (lo,w) -> [low]
+        assert_eq!(tokenizer.estimate_tokens("low"), 1);
+    }
+
+    #[test]
+    fn test_bpe_falls_back_on_unknown_char() {
+        let tokenizer = bpe_fixture();
+        // "lox" has no vocab entry for "x", so it falls back to a char count.
+        assert_eq!(tokenizer.estimate_tokens("lox"), 3);
+    }
+
+    #[test]
+    fn test_bpe_pre_tokenize_splits_on_operators() {
+        let pieces = BpeTokenizer::pre_tokenize("let x = 1;");
+        assert_eq!(pieces, vec!["let", "x", "=", "1", ";"]);
+    }
+
+    #[test]
+    fn test_bpe_empty_text() {
+        let tokenizer = bpe_fixture();
+        assert_eq!(tokenizer.estimate_tokens(""), 0);
+    }
+}
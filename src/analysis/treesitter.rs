@@ -1,12 +1,16 @@
 use anyhow::{Context, Result};
 use std::path::Path;
-use tree_sitter::{Language, Parser, Tree};
+use tree_sitter::{Language, Parser, Query, Tree};
+
+use super::grammar::LoadedGrammar;
 
 /// Enumeration of programming languages supported for tree-sitter parsing.
 ///
 /// This enum represents the languages that ctx can parse and analyze
-/// using tree-sitter grammars. Each variant corresponds to a specific
-/// language grammar that enables syntax-aware code analysis.
+/// using tree-sitter grammars. The built-in variants are compiled into the
+/// binary; [`SupportedLanguage::Dynamic`] wraps a grammar that was loaded at
+/// runtime (see [`super::grammar`]), keeping this type an open registry
+/// rather than a fixed, recompile-to-extend enum.
 ///
 /// # Supported Languages
 /// - **Rust**: `.rs` files
@@ -23,6 +27,8 @@ pub enum SupportedLanguage {
     JavaScript,
     /// TypeScript language including TSX (`.ts`, `.tsx`, `.mts`, `.cts` extensions).
     TypeScript,
+    /// A grammar loaded at runtime from config, identified by language name.
+    Dynamic(LoadedGrammar),
 }
 
 impl SupportedLanguage {
@@ -81,28 +87,213 @@ impl SupportedLanguage {
             Self::Python => tree_sitter_python::language(),
             Self::JavaScript => tree_sitter_javascript::language(),
             Self::TypeScript => tree_sitter_typescript::language_typescript(),
+            Self::Dynamic(grammar) => grammar.language.clone(),
         }
     }
 
-    /// Returns the lowercase string name of the language.
-    ///
-    /// Useful for display purposes and serialization.
+    /// Returns the string name of the language.
     ///
-    /// # Returns
-    /// A static string: "rust", "python", "javascript", or "typescript".
-    pub fn name(&self) -> &'static str {
+    /// Useful for display purposes and serialization. Built-in languages
+    /// return a static string; dynamically loaded grammars return their
+    /// configured name.
+    pub fn name(&self) -> &str {
         match self {
             Self::Rust => "rust",
             Self::Python => "python",
             Self::JavaScript => "javascript",
             Self::TypeScript => "typescript",
+            Self::Dynamic(grammar) => &grammar.name,
+        }
+    }
+
+    /// Built-in `#!` interpreter -> language table, consulted by
+    /// [`Self::from_shebang`] and [`LanguageRegistry::detect`] for
+    /// extensionless scripts.
+    const SHEBANG_INTERPRETERS: &'static [(&'static str, fn() -> Self)] = &[
+        ("python3", || Self::Python),
+        ("python", || Self::Python),
+        ("python2", || Self::Python),
+        ("node", || Self::JavaScript),
+        ("nodejs", || Self::JavaScript),
+    ];
+
+    /// Resolves a language from a file's first line, if it starts with a
+    /// `#!` shebang naming a known interpreter (following an `env`
+    /// indirection, e.g. `#!/usr/bin/env python3`).
+    pub fn from_shebang(first_line: &str) -> Option<Self> {
+        let interpreter = parse_shebang_interpreter(first_line)?;
+        Self::SHEBANG_INTERPRETERS
+            .iter()
+            .find(|(name, _)| *name == interpreter)
+            .map(|(_, make)| make())
+    }
+
+    /// Detects a language using, in order: file extension, then (for
+    /// extensionless or unrecognized files) a `#!` shebang on `first_line`.
+    ///
+    /// This only consults the built-in table; [`LanguageRegistry::detect`]
+    /// additionally checks dynamically configured filenames and shebangs.
+    pub fn detect(path: &Path, first_line: Option<&str>) -> Option<Self> {
+        Self::from_path(path).or_else(|| first_line.and_then(Self::from_shebang))
+    }
+
+    /// Like [`Self::detect`], but takes a file's full `source` instead of
+    /// an already-extracted first line — the convenience a caller that
+    /// already has the content in hand (e.g. [`parse_file`]) needs instead
+    /// of splitting it themselves just to get the first line.
+    pub fn from_path_and_content(path: &Path, source: &str) -> Option<Self> {
+        Self::detect(path, source.lines().next())
+    }
+}
+
+/// Extracts the interpreter name from a `#!` shebang line, unwrapping an
+/// `env` indirection (`#!/usr/bin/env python3` -> `python3`). The name is
+/// returned as written; version suffixes (`python3.11`, `-3.11`) are not
+/// stripped.
+fn parse_shebang_interpreter(first_line: &str) -> Option<&str> {
+    let rest = first_line.trim().strip_prefix("#!")?;
+    let mut parts = rest.split_whitespace();
+    let mut interpreter = parts.next()?.rsplit('/').next()?;
+    if interpreter == "env" {
+        interpreter = parts.next()?;
+    }
+    Some(interpreter)
+}
+
+/// Matches `name` against a filename pattern that may contain a single `*`
+/// wildcard (e.g. `*.in`, `Dockerfile.*`, or an exact name like `Makefile`).
+fn filename_matches(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, suffix)) => {
+            name.len() >= prefix.len() + suffix.len()
+                && name.starts_with(prefix)
+                && name.ends_with(suffix)
         }
     }
 }
 
+/// An open registry of languages ctx knows how to parse.
+///
+/// Merges the four built-in languages with any grammars loaded at runtime
+/// (see [`super::grammar::load_all`]), so callers that need "every language
+/// we can currently parse" don't have to special-case dynamically loaded
+/// extensions separately from the built-in enum.
+#[derive(Debug, Clone, Default)]
+pub struct LanguageRegistry {
+    dynamic: Vec<LoadedGrammar>,
+}
+
+impl LanguageRegistry {
+    /// Creates a registry with no dynamically loaded grammars (built-ins only).
+    pub fn new() -> Self {
+        Self { dynamic: Vec::new() }
+    }
+
+    /// Creates a registry that also knows about the given dynamically loaded grammars.
+    pub fn with_dynamic(dynamic: Vec<LoadedGrammar>) -> Self {
+        Self { dynamic }
+    }
+
+    /// Resolves a file extension to a language, checking built-ins first and
+    /// then any dynamically loaded grammars.
+    pub fn resolve_extension(&self, ext: &str) -> Option<SupportedLanguage> {
+        if let Some(lang) = SupportedLanguage::from_extension(ext) {
+            return Some(lang);
+        }
+
+        self.dynamic
+            .iter()
+            .find(|g| g.extensions.iter().any(|e| e == ext))
+            .cloned()
+            .map(SupportedLanguage::Dynamic)
+    }
+
+    /// Resolves a path to a language using its extension, checking built-ins
+    /// first and then any dynamically loaded grammars.
+    pub fn resolve_path(&self, path: &Path) -> Option<SupportedLanguage> {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .and_then(|e| self.resolve_extension(e))
+    }
+
+    /// Detects a language for `path`, trying in order: extension (built-in
+    /// then dynamic), exact/glob filename match against a dynamic grammar's
+    /// configured `filenames`, a built-in `#!` shebang, and finally a
+    /// dynamic grammar's configured `shebangs`. `first_line` should be the
+    /// file's first line when available, to support shebang detection for
+    /// extensionless scripts.
+    pub fn detect(&self, path: &Path, first_line: Option<&str>) -> Option<SupportedLanguage> {
+        if let Some(lang) = self.resolve_path(path) {
+            return Some(lang);
+        }
+
+        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+            if let Some(grammar) = self
+                .dynamic
+                .iter()
+                .find(|g| g.filenames.iter().any(|pat| filename_matches(pat, name)))
+            {
+                return Some(SupportedLanguage::Dynamic(grammar.clone()));
+            }
+        }
+
+        if let Some(lang) = first_line.and_then(SupportedLanguage::from_shebang) {
+            return Some(lang);
+        }
+
+        let interpreter = first_line.and_then(parse_shebang_interpreter)?;
+        self.dynamic
+            .iter()
+            .find(|g| g.shebangs.iter().any(|s| s == interpreter))
+            .cloned()
+            .map(SupportedLanguage::Dynamic)
+    }
+
+    /// Resolves a language by name (e.g. a `linguist-language=<name>`
+    /// override from `.gitattributes`), checking built-ins first and then
+    /// any dynamically loaded grammars. Matching is case-insensitive.
+    pub fn resolve_name(&self, name: &str) -> Option<SupportedLanguage> {
+        match name.to_lowercase().as_str() {
+            "rust" => return Some(SupportedLanguage::Rust),
+            "python" => return Some(SupportedLanguage::Python),
+            "javascript" => return Some(SupportedLanguage::JavaScript),
+            "typescript" => return Some(SupportedLanguage::TypeScript),
+            _ => {}
+        }
+
+        self.dynamic
+            .iter()
+            .find(|g| g.name.eq_ignore_ascii_case(name))
+            .cloned()
+            .map(SupportedLanguage::Dynamic)
+    }
+
+    /// Names of every dynamically loaded grammar this registry knows about,
+    /// in load order — the built-in languages (Rust/Python/JS/TS) aren't
+    /// included since they're always available regardless of config.
+    pub fn dynamic_grammar_names(&self) -> Vec<String> {
+        self.dynamic.iter().map(|g| g.name.clone()).collect()
+    }
+
+    /// All file extensions known to this registry, built-in and dynamic.
+    pub fn known_extensions(&self) -> Vec<String> {
+        let mut exts: Vec<String> = vec![
+            "rs".into(), "py".into(), "js".into(), "jsx".into(), "mjs".into(), "cjs".into(),
+            "ts".into(), "tsx".into(), "mts".into(), "cts".into(),
+        ];
+        for grammar in &self.dynamic {
+            exts.extend(grammar.extensions.iter().cloned());
+        }
+        exts
+    }
+}
+
 /// Parses source code into a tree-sitter syntax tree.
 ///
-/// Automatically detects the language from the file path's extension
+/// Automatically detects the language from the file path's extension,
+/// falling back to a `#!` shebang on the first line of `source` for
+/// extensionless scripts (see [`SupportedLanguage::from_path_and_content`]),
 /// and configures the appropriate parser. Returns `None` for unsupported
 /// file types rather than failing.
 ///
@@ -123,11 +314,21 @@ impl SupportedLanguage {
 /// }
 /// ```
 pub fn parse_file(path: &Path, source: &str) -> Result<Option<Tree>> {
-    let lang = match SupportedLanguage::from_path(path) {
+    let lang = match SupportedLanguage::from_path_and_content(path, source) {
         Some(l) => l,
         None => return Ok(None),
     };
 
+    parse_with_language(source, &lang)
+}
+
+/// Parses source code with an already-resolved language.
+///
+/// Unlike [`parse_file`], this doesn't re-derive the language from a file
+/// extension, so it works for languages resolved through a
+/// [`LanguageRegistry`] (including dynamically loaded grammars) as well as
+/// the built-in four.
+pub fn parse_with_language(source: &str, lang: &SupportedLanguage) -> Result<Option<Tree>> {
     let mut parser = Parser::new();
     parser
         .set_language(&lang.language())
@@ -136,6 +337,33 @@ pub fn parse_file(path: &Path, source: &str) -> Result<Option<Tree>> {
     parser.parse(source, None).context("Failed to parse").map(Some)
 }
 
+/// Per-language tree-sitter query matching call-expression nodes, capturing
+/// the called name as `@callee`. Used by
+/// [`crate::commands::diff_context::find_callers`] for AST-scoped caller
+/// detection (an exact match on a parsed call site) instead of a substring
+/// search that can't tell a call from a comment, a string, or a longer
+/// identifier. Returns `None` for a dynamically loaded grammar, since its
+/// call-site node shapes aren't known ahead of time.
+pub fn call_query(lang: &SupportedLanguage) -> Option<Query> {
+    let source = match lang {
+        SupportedLanguage::Rust => {
+            "(call_expression function: (identifier) @callee)
+             (call_expression function: (field_expression field: (field_identifier) @callee))"
+        }
+        SupportedLanguage::Python => {
+            "(call function: (identifier) @callee)
+             (call function: (attribute attribute: (identifier) @callee))"
+        }
+        SupportedLanguage::JavaScript | SupportedLanguage::TypeScript => {
+            "(call_expression function: (identifier) @callee)
+             (call_expression function: (member_expression property: (property_identifier) @callee))"
+        }
+        SupportedLanguage::Dynamic(_) => return None,
+    };
+
+    Query::new(&lang.language(), source).ok()
+}
+
 /// Creates a configured tree-sitter parser for a specific language.
 ///
 /// This function creates a new parser instance and configures it with
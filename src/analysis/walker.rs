@@ -123,6 +123,18 @@ const DEFAULT_IGNORES: &[&str] = &[
 /// - [`create_walker_with_hidden`] - Variant that includes hidden files
 /// - [`should_ignore`] - Manual ignore checking for paths
 pub fn create_walker(root: &Path) -> WalkBuilder {
+    create_walker_with_extra_ignores(root, &[])
+}
+
+/// Like [`create_walker`], but also applies extra gitignore-style glob
+/// patterns on top of the built-in [`DEFAULT_IGNORES`] — e.g. the `ignore`
+/// list from a loaded `Config`.
+///
+/// # Arguments
+/// * `root` - The root directory to start walking from
+/// * `extra_ignores` - Additional glob patterns to exclude, same syntax as
+///   `.gitignore` entries (e.g. `*.generated.rs`, `fixtures/`)
+pub fn create_walker_with_extra_ignores(root: &Path, extra_ignores: &[String]) -> WalkBuilder {
     let mut builder = WalkBuilder::new(root);
 
     // Respect .gitignore files
@@ -137,13 +149,17 @@ pub fn create_walker(root: &Path) -> WalkBuilder {
     // But we do want to follow symlinks for actual source files
     builder.follow_links(false);
 
-    // Add our default ignores as overrides (works even without .gitignore)
+    // Add our default ignores, plus any extras, as overrides (works even
+    // without a .gitignore file).
     if let Some(overrides) = ignore::overrides::OverrideBuilder::new(root)
         .add("!**/.git/**")
         .ok()
         .and_then(|b| {
-            // Add all default ignores
-            for pattern in DEFAULT_IGNORES {
+            let patterns = DEFAULT_IGNORES
+                .iter()
+                .copied()
+                .chain(extra_ignores.iter().map(|s| s.as_str()));
+            for pattern in patterns {
                 // Convert pattern to a negation (ignore pattern)
                 let ignore_pattern = format!("!**/{}", pattern);
                 if b.add(&ignore_pattern).is_err() {
@@ -160,6 +176,66 @@ pub fn create_walker(root: &Path) -> WalkBuilder {
     builder
 }
 
+/// Compiles a list of user-supplied exclude glob patterns (e.g. `vendor/**`,
+/// `*.lock`, `dist/**`) into a matcher, for filtering paths gathered from
+/// sources other than a directory walk (e.g. file paths pulled out of git
+/// history) the same way [`create_walker_with_extra_ignores`] filters them
+/// during a walk. Returns `None` if `excludes` is empty.
+///
+/// # Arguments
+/// * `root` - The root directory the patterns are relative to
+/// * `excludes` - Glob patterns to exclude, same syntax as `.gitignore` entries
+pub fn build_exclude_matcher(root: &Path, excludes: &[String]) -> Option<ignore::overrides::Override> {
+    if excludes.is_empty() {
+        return None;
+    }
+
+    let mut builder = ignore::overrides::OverrideBuilder::new(root);
+    for pattern in excludes {
+        let ignore_pattern = format!("!**/{}", pattern);
+        if builder.add(&ignore_pattern).is_err() {
+            let _ = builder.add(&format!("!{}", pattern));
+        }
+    }
+    builder.build().ok()
+}
+
+/// Checks `path` against a matcher built by [`build_exclude_matcher`].
+pub fn is_excluded(matcher: &ignore::overrides::Override, path: &Path) -> bool {
+    matcher.matched(path, false).is_ignore()
+}
+
+/// Compiles a list of user-supplied include glob patterns into a matcher
+/// that force-keeps matching paths regardless of `.gitignore` rules, for
+/// callers that let a prompt opt back into an otherwise-ignored path (e.g.
+/// a generated `dist/index.ts` the user names explicitly). Returns `None`
+/// if `includes` is empty.
+///
+/// Unlike [`build_exclude_matcher`], these patterns are added without
+/// negation: `ignore::overrides::Override` treats un-negated patterns as
+/// whitelist entries, which is exactly "keep this even if ignored
+/// elsewhere" — see [`is_included`].
+///
+/// # Arguments
+/// * `root` - The root directory the patterns are relative to
+/// * `includes` - Glob patterns to force-include, same syntax as `.gitignore` entries
+pub fn build_include_matcher(root: &Path, includes: &[String]) -> Option<ignore::overrides::Override> {
+    if includes.is_empty() {
+        return None;
+    }
+
+    let mut builder = ignore::overrides::OverrideBuilder::new(root);
+    for pattern in includes {
+        let _ = builder.add(pattern);
+    }
+    builder.build().ok()
+}
+
+/// Checks `path` against a matcher built by [`build_include_matcher`].
+pub fn is_included(matcher: &ignore::overrides::Override, path: &Path) -> bool {
+    matcher.matched(path, false).is_whitelist()
+}
+
 /// Creates a file system walker that includes hidden files.
 ///
 /// Similar to [`create_walker`], but configured to traverse hidden files
@@ -186,7 +262,6 @@ pub fn create_walker(root: &Path) -> WalkBuilder {
 ///     // Will include files like .eslintrc, .prettierrc, etc.
 /// }
 /// ```
-#[allow(dead_code)]
 pub fn create_walker_with_hidden(root: &Path) -> WalkBuilder {
     let mut builder = WalkBuilder::new(root);
 
@@ -246,7 +321,6 @@ pub fn create_walker_with_hidden(root: &Path) -> WalkBuilder {
 /// assert!(!should_ignore(Path::new("src/main.rs")));
 /// assert!(!should_ignore(Path::new(".github/workflows/ci.yml")));
 /// ```
-#[allow(dead_code)]
 pub fn should_ignore(path: &Path) -> bool {
     let path_str = path.to_string_lossy();
 
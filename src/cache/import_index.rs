@@ -0,0 +1,149 @@
+//! Project-wide bidirectional import index (`path -> {imports, imported_by}`),
+//! so `related` can answer import-graph queries by lookup instead of
+//! re-walking and re-parsing the whole tree for every query. Built from
+//! [`crate::analysis::import_graph`], whose resolution already keys edges on
+//! canonical resolved paths rather than name substrings, and persisted the
+//! same way as [`super::summaries::SummaryCache`]: a mismatch against any
+//! tracked file's current mtime forces a full rebuild.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::analysis::import_graph;
+use crate::analysis::treesitter::LanguageRegistry;
+
+use super::summaries::get_file_mtime;
+
+const CACHE_FILE: &str = ".ctx/cache/import_index.json";
+
+/// One resolved edge as seen from the index's perspective: the file it
+/// points at, plus the specifier text that produced the edge (e.g.
+/// `std::fs` or `./sibling`), kept for display.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportEdgeRef {
+    pub path: String,
+    pub specifier: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportIndexEntry {
+    pub imports: Vec<ImportEdgeRef>,
+    pub imported_by: Vec<ImportEdgeRef>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImportIndex {
+    /// mtime (seconds since epoch) of every file the index was built from,
+    /// keyed the same way as `entries`. A mismatch against the live file
+    /// set invalidates the whole cache.
+    file_mtimes: HashMap<String, u64>,
+    entries: HashMap<String, ImportIndexEntry>,
+}
+
+impl ImportIndex {
+    /// Builds a fresh index from `files` (relative to `project_root`),
+    /// parsing each one (resolving its language through `registry`, so
+    /// dynamically loaded grammars are recognized) and resolving its
+    /// imports via [`import_graph::build_dependency_graph_with_registry`].
+    pub fn build(project_root: &Path, files: &[PathBuf], registry: &LanguageRegistry) -> Self {
+        let graph = import_graph::build_dependency_graph_with_registry(files, registry);
+
+        let mut entries: HashMap<String, ImportIndexEntry> = HashMap::new();
+        let mut file_mtimes = HashMap::new();
+
+        for file in files {
+            let key = relative_key(project_root, file);
+            if let Ok(mtime) = get_file_mtime(file) {
+                file_mtimes.insert(key.clone(), mtime);
+            }
+            entries.entry(key).or_default();
+        }
+
+        for (file, edges) in &graph.edges {
+            let from = relative_key(project_root, file);
+            for edge in edges {
+                let Some(resolved) = &edge.resolved else {
+                    continue;
+                };
+                let to = relative_key(project_root, resolved);
+
+                entries.entry(from.clone()).or_default().imports.push(ImportEdgeRef {
+                    path: to.clone(),
+                    specifier: edge.imported.clone(),
+                });
+                entries.entry(to).or_default().imported_by.push(ImportEdgeRef {
+                    path: from.clone(),
+                    specifier: edge.imported.clone(),
+                });
+            }
+        }
+
+        Self { file_mtimes, entries }
+    }
+
+    /// Loads the cached index for `project_root` if one exists and is still
+    /// fresh against `files`' current mtimes; otherwise rebuilds from
+    /// scratch (via `registry`, for dynamic-grammar awareness) and
+    /// persists the result.
+    pub fn load_or_build(project_root: &Path, files: &[PathBuf], registry: &LanguageRegistry) -> Result<Self> {
+        let current_mtimes: HashMap<String, u64> = files
+            .iter()
+            .filter_map(|f| get_file_mtime(f).ok().map(|m| (relative_key(project_root, f), m)))
+            .collect();
+
+        if let Ok(cached) = Self::load(project_root) {
+            if cached.file_mtimes == current_mtimes {
+                return Ok(cached);
+            }
+        }
+
+        let fresh = Self::build(project_root, files, registry);
+        fresh.save(project_root)?;
+        Ok(fresh)
+    }
+
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let cache_path = project_root.join(CACHE_FILE);
+        let content =
+            fs::read_to_string(&cache_path).context("Failed to read import index cache")?;
+        serde_json::from_str(&content).context("Failed to parse import index cache")
+    }
+
+    pub fn save(&self, project_root: &Path) -> Result<()> {
+        let cache_path = project_root.join(CACHE_FILE);
+
+        if let Some(parent) = cache_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&cache_path, content)?;
+
+        Ok(())
+    }
+
+    pub fn imports_of(&self, path: &str) -> &[ImportEdgeRef] {
+        self.entries.get(path).map(|e| e.imports.as_slice()).unwrap_or(&[])
+    }
+
+    pub fn imported_by_of(&self, path: &str) -> &[ImportEdgeRef] {
+        self.entries.get(path).map(|e| e.imported_by.as_slice()).unwrap_or(&[])
+    }
+}
+
+/// Normalizes `path` to a stable, root-relative string key: canonicalized
+/// so `./foo.rs` and `foo.rs` land on the same entry, then stripped of the
+/// `project_root` prefix so the index stays portable across checkouts.
+pub fn relative_key(project_root: &Path, path: &Path) -> String {
+    let canonical_root = project_root.canonicalize().unwrap_or_else(|_| project_root.to_path_buf());
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+    canonical
+        .strip_prefix(&canonical_root)
+        .unwrap_or(&canonical)
+        .to_string_lossy()
+        .to_string()
+}
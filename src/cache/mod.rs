@@ -0,0 +1,3 @@
+pub mod import_index;
+pub mod summaries;
+pub mod symbol_index;
@@ -5,7 +5,6 @@ use std::fs;
 use std::path::Path;
 use std::time::SystemTime;
 
-#[allow(dead_code)]
 const CACHE_FILE: &str = ".ctx/cache/summaries.json";
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -35,7 +34,6 @@ pub struct SymbolSummary {
     pub signature: Option<String>,
 }
 
-#[allow(dead_code)]
 impl SummaryCache {
     pub fn new() -> Self {
         Self {
@@ -68,6 +66,7 @@ impl SummaryCache {
         Ok(())
     }
 
+    #[allow(dead_code)]
     pub fn get(&self, path: &str, current_mtime: u64) -> Option<&FileSummary> {
         self.entries.get(path).and_then(|entry| {
             if entry.mtime == current_mtime {
@@ -93,12 +92,12 @@ impl SummaryCache {
         self.entries.remove(path);
     }
 
+    #[allow(dead_code)]
     pub fn clear(&mut self) {
         self.entries.clear();
     }
 }
 
-#[allow(dead_code)]
 pub fn get_file_mtime(path: &Path) -> Result<u64> {
     let metadata = fs::metadata(path)?;
     let mtime = metadata.modified()?;
@@ -0,0 +1,110 @@
+//! Persisted, per-file symbol shards backing
+//! [`crate::analysis::symbol_index::SymbolIndex`], so `ctx search --symbol`
+//! and `ctx find` only reparse files whose mtime has changed instead of
+//! rebuilding the whole project's symbols on every invocation. Keyed and
+//! invalidated the same way as [`super::summaries::SummaryCache`]: each
+//! file gets its own shard with its own mtime, so an edit to one file
+//! leaves every other file's shard untouched.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::analysis::symbol_index::SymbolIndex;
+use crate::analysis::symbols::{self, Symbol};
+use crate::analysis::treesitter::{self, LanguageRegistry};
+
+use super::summaries::get_file_mtime;
+
+const CACHE_FILE: &str = ".ctx/cache/symbol_index.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedFile {
+    mtime: u64,
+    symbols: Vec<Symbol>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SymbolIndexCache {
+    entries: HashMap<String, CachedFile>,
+}
+
+impl SymbolIndexCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let cache_path = project_root.join(CACHE_FILE);
+        if !cache_path.exists() {
+            return Ok(Self::new());
+        }
+
+        let content = fs::read_to_string(&cache_path).context("Failed to read symbol index cache")?;
+        serde_json::from_str(&content).context("Failed to parse symbol index cache")
+    }
+
+    pub fn save(&self, project_root: &Path) -> Result<()> {
+        let cache_path = project_root.join(CACHE_FILE);
+
+        if let Some(parent) = cache_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&cache_path, content)?;
+
+        Ok(())
+    }
+
+    /// Builds a ready-to-query [`SymbolIndex`] over `files`: a file whose
+    /// mtime still matches its cached shard is restored straight from that
+    /// shard (no reparsing); everything else is parsed via `registry` and
+    /// its shard replaced. Shards for files no longer present in `files`
+    /// are dropped. Mutates `self` in place — call [`save`](Self::save)
+    /// afterward to persist the refreshed shards.
+    pub fn load_or_build(&mut self, files: &[PathBuf], registry: &LanguageRegistry) -> SymbolIndex {
+        let mut index = SymbolIndex::new();
+        let mut live_keys = HashSet::new();
+
+        for file in files {
+            let key = file.to_string_lossy().to_string();
+            live_keys.insert(key.clone());
+
+            let Ok(mtime) = get_file_mtime(file) else {
+                continue;
+            };
+
+            let symbols = match self.entries.get(&key) {
+                Some(cached) if cached.mtime == mtime => cached.symbols.clone(),
+                _ => {
+                    let symbols = extract_file_symbols(file, registry);
+                    self.entries.insert(key.clone(), CachedFile { mtime, symbols: symbols.clone() });
+                    symbols
+                }
+            };
+
+            index.load_symbols(file, symbols);
+        }
+
+        self.entries.retain(|key, _| live_keys.contains(key));
+        index
+    }
+}
+
+/// A single unparseable file shouldn't sink the whole index — an empty
+/// symbol list just means that file contributes nothing.
+fn extract_file_symbols(path: &Path, registry: &LanguageRegistry) -> Vec<Symbol> {
+    let Ok(source) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let Some(lang) = registry.detect(path, source.lines().next()) else {
+        return Vec::new();
+    };
+    let Ok(Some(tree)) = treesitter::parse_with_language(&source, &lang) else {
+        return Vec::new();
+    };
+    symbols::extract_symbols(&tree, &source, &lang)
+}
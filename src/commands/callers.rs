@@ -0,0 +1,115 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::analysis::deadline::Deadline;
+use crate::analysis::symbols;
+use crate::analysis::treesitter;
+use crate::analysis::walker;
+use crate::commands::config::{self, Config};
+use crate::output::OutputFormat;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CallerMatch {
+    pub caller: String,
+    pub path: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CallerResults {
+    pub function: String,
+    pub callers: Vec<CallerMatch>,
+    /// `true` if `--timeout` cut the scan short; `callers` then only
+    /// reflects the files visited before the deadline.
+    pub truncated: bool,
+}
+
+impl std::fmt::Display for CallerResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in &self.callers {
+            writeln!(f, "{}:{}: {}", c.path, c.line, c.caller)?;
+        }
+
+        if self.callers.is_empty() {
+            writeln!(f, "No callers found for '{}'", self.function)?;
+        }
+
+        if self.truncated {
+            writeln!(f, "\n(truncated: --timeout reached before the scan finished)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Finds every call site for `function` across the project, using
+/// tree-sitter's AST rather than a text search — see
+/// [`symbols::extract_calls`].
+pub fn run(function: &str, deadline: Deadline, format: OutputFormat) -> Result<()> {
+    let config = Config::load().unwrap_or_default();
+    let registry = config::build_registry(&config);
+    let mut callers = Vec::new();
+
+    let extra_ignores = config.ignore.clone().unwrap_or_default();
+    let file_walker = walker::create_walker_with_extra_ignores(Path::new("."), &extra_ignores).build();
+    let mut truncated = false;
+    for entry in file_walker.flatten() {
+        if deadline.is_expired() {
+            truncated = true;
+            break;
+        }
+
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let Some(lang) = registry.detect(path, content.lines().next()) else {
+            continue;
+        };
+        // A single unparseable file shouldn't sink the whole search.
+        let Ok(Some(tree)) = treesitter::parse_with_language(&content, &lang) else {
+            continue;
+        };
+
+        let graph = symbols::extract_calls(&tree, &content, &lang);
+        for edge in graph.callers_of(function) {
+            callers.push(CallerMatch {
+                caller: edge.caller.clone(),
+                path: path.to_string_lossy().to_string(),
+                line: edge.line,
+            });
+        }
+    }
+
+    callers.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+
+    let results = CallerResults {
+        function: function.to_string(),
+        callers,
+        truncated,
+    };
+
+    match format {
+        OutputFormat::Human => println!("{}", results),
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&results)?);
+        }
+        OutputFormat::Compact => {
+            println!("{}", serde_json::to_string(&results)?);
+        }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
+    }
+
+    Ok(())
+}
@@ -4,6 +4,7 @@
 //! in a format suitable for AI agents to understand ctx's capabilities.
 
 use anyhow::Result;
+use schemars::JsonSchema;
 use serde::Serialize;
 use serde_json::json;
 
@@ -14,6 +15,8 @@ pub struct Capabilities {
     pub name: String,
     /// Tool version
     pub version: String,
+    /// Wire/output protocol version — see [`PROTOCOL_VERSION`]
+    pub protocol_version: ProtocolVersion,
     /// Human-readable description
     pub description: String,
     /// Repository URL
@@ -30,6 +33,22 @@ pub struct Capabilities {
     pub exit_codes: ExitCodes,
 }
 
+/// `ctx`'s wire/output protocol version, independent of `CARGO_PKG_VERSION`:
+/// `major`/`minor` follow semver-ish compatibility (a minor bump is
+/// additive-only, a major bump may break an existing tool's shape), while
+/// `revision` is incremented on every release that changes any command's
+/// JSON output shape, so an agent that caches tool definitions across
+/// upgrades can detect drift and re-fetch just what changed rather than
+/// diffing full crate version strings.
+pub const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion { major: 1, minor: 0, revision: 1 };
+
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema)]
+pub struct ProtocolVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub revision: u32,
+}
+
 #[derive(Debug, Serialize)]
 pub struct Integrations {
     /// Agent Skills support
@@ -65,6 +84,13 @@ pub struct ToolDefinition {
     pub when_to_use: String,
     /// Input schema
     pub input_schema: serde_json::Value,
+    /// `major.minor` protocol version this tool's shape has been stable
+    /// since (e.g. `"1.0"`)
+    pub since_protocol: String,
+    /// Bumped whenever this command's JSON output shape changes, so an
+    /// agent can tell which cached schema it needs to re-fetch without
+    /// tracking the whole protocol revision
+    pub output_schema_version: u32,
 }
 
 /// Run the capabilities output.
@@ -72,6 +98,7 @@ pub fn run() -> Result<()> {
     let capabilities = Capabilities {
         name: "ctx".to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        protocol_version: PROTOCOL_VERSION,
         description: "Context tool for AI coding agents. Provides AST-aware codebase analysis using tree-sitter.".to_string(),
         repository: "https://github.com/konacodes/ctx".to_string(),
         license: "MIT".to_string(),
@@ -86,6 +113,9 @@ pub fn run() -> Result<()> {
             "human".to_string(),
             "json".to_string(),
             "compact".to_string(),
+            "html".to_string(),
+            "annotations".to_string(),
+            "github".to_string(),
         ],
         exit_codes: ExitCodes {
             success: 0,
@@ -105,6 +135,8 @@ pub fn run() -> Result<()> {
                     "properties": {},
                     "required": []
                 }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
             },
             ToolDefinition {
                 name: "map".to_string(),
@@ -119,6 +151,8 @@ pub fn run() -> Result<()> {
                     },
                     "required": []
                 }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
             },
             ToolDefinition {
                 name: "summarize".to_string(),
@@ -134,11 +168,13 @@ pub fn run() -> Result<()> {
                     },
                     "required": ["paths"]
                 }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
             },
             ToolDefinition {
                 name: "search".to_string(),
-                description: "Search codebase for text, symbol definitions (--symbol), or function callers (--caller). The --caller flag uses AST analysis and is impossible with grep.".to_string(),
-                usage: "ctx search <query> [--symbol] [--caller] [-C N] [--json]".to_string(),
+                description: "Search codebase for text, symbol definitions (--symbol), or function callers (--caller). The --caller flag uses AST analysis and is impossible with grep. Supports --format=annotations (vimgrep quickfix) and --format=github (inline PR annotations) in addition to the usual json/compact/human.".to_string(),
+                usage: "ctx search <query> [--symbol] [--caller] [--regex] [--fixed-string] [-C N] [--limit N] [--format human|json|compact|annotations|github]".to_string(),
                 when_to_use: "When finding where something is defined (--symbol) or who calls a function (--caller)".to_string(),
                 input_schema: json!({
                     "type": "object",
@@ -146,23 +182,75 @@ pub fn run() -> Result<()> {
                         "query": {"type": "string", "description": "Search query"},
                         "symbol": {"type": "boolean", "description": "Find symbol definitions only"},
                         "caller": {"type": "boolean", "description": "Find function callers (AST-based)"},
-                        "context": {"type": "integer", "description": "Lines of context"}
+                        "regex": {"type": "boolean", "description": "Treat query as a regular expression (text search only)"},
+                        "fixed_string": {"type": "boolean", "description": "Match query literally, overriding --regex"},
+                        "context": {"type": "integer", "description": "Lines of context"},
+                        "limit": {"type": "integer", "description": "Maximum number of results (only applies with --symbol)"}
                     },
                     "required": ["query"]
                 }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
             },
             ToolDefinition {
                 name: "related".to_string(),
-                description: "Find files related to a given file through imports, reverse imports, git co-changes, and test associations".to_string(),
-                usage: "ctx related <file> [--json]".to_string(),
+                description: "Find files related to a given file through imports, reverse imports, git co-changes, and test associations. Imports/reverse-imports are backed by a persisted project-wide index, so --depth can return the transitive closure".to_string(),
+                usage: "ctx related <file> [--depth N] [--json]".to_string(),
                 when_to_use: "When understanding file dependencies and what else might be affected by changes".to_string(),
                 input_schema: json!({
                     "type": "object",
                     "properties": {
-                        "file": {"type": "string", "description": "File to find relations for"}
+                        "file": {"type": "string", "description": "File to find relations for"},
+                        "depth": {"type": "integer", "description": "Hops of transitive imports/imported-by to include (default: 1)"}
                     },
                     "required": ["file"]
                 }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
+            },
+            ToolDefinition {
+                name: "find".to_string(),
+                description: "Fuzzy-search symbol names across the whole project (typo-tolerant, fst-backed)".to_string(),
+                usage: "ctx find <query> [--limit N] [--json]".to_string(),
+                when_to_use: "When you know roughly what a symbol is called but not exactly, or where it's defined".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "query": {"type": "string", "description": "Symbol name to fuzzy-match"},
+                        "limit": {"type": "integer", "description": "Maximum number of matches"}
+                    },
+                    "required": ["query"]
+                }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
+            },
+            ToolDefinition {
+                name: "callers".to_string(),
+                description: "Find every call site for a function across the project using tree-sitter AST analysis (not a text search)".to_string(),
+                usage: "ctx callers <function> [--json]".to_string(),
+                when_to_use: "When assessing the impact of changing or removing a function".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "function": {"type": "string", "description": "Function or method name to find callers of"}
+                    },
+                    "required": ["function"]
+                }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
+            },
+            ToolDefinition {
+                name: "deps".to_string(),
+                description: "Intra-repo module dependency graph built from import/use statements, with cycle detection".to_string(),
+                usage: "ctx deps [--json]".to_string(),
+                when_to_use: "When understanding module coupling or tracking down an import cycle".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {},
+                    "required": []
+                }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
             },
             ToolDefinition {
                 name: "diff-context".to_string(),
@@ -176,6 +264,39 @@ pub fn run() -> Result<()> {
                     },
                     "required": []
                 }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
+            },
+            ToolDefinition {
+                name: "metrics".to_string(),
+                description: "Aggregate project metrics (file/symbol counts, LOC) as flat, mergeable JSON for tracking over time".to_string(),
+                usage: "ctx metrics [path] [--tag <label>] [--json]".to_string(),
+                when_to_use: "When recording or comparing project-size snapshots, e.g. once per commit in CI".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {"type": "string", "description": "Path to measure"},
+                        "tag": {"type": "string", "description": "Label for this snapshot"}
+                    },
+                    "required": []
+                }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
+            },
+            ToolDefinition {
+                name: "projects".to_string(),
+                description: "Logical projects in a monorepo touched by a set of git changes, detected from manifest files".to_string(),
+                usage: "ctx projects [ref] [--json]".to_string(),
+                when_to_use: "When CI needs to scope work to only the projects a commit range affects".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "git_ref": {"type": "string", "description": "Git ref to diff against"}
+                    },
+                    "required": []
+                }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
             },
             ToolDefinition {
                 name: "schema".to_string(),
@@ -189,6 +310,25 @@ pub fn run() -> Result<()> {
                     },
                     "required": ["command"]
                 }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
+            },
+            ToolDefinition {
+                name: "watch".to_string(),
+                description: "Long-lived watch loop: streams debounced batches of changed files and keeps the on-disk summary cache warm, so subsequent summarize/search --symbol calls are instant".to_string(),
+                usage: "ctx watch [path] [--debounce MS] [--poll] [--json]".to_string(),
+                when_to_use: "When an agent needs to react to edits as they happen instead of polling, or wants the cache pre-warmed during a long editing session".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {"type": "string", "description": "Directory to watch (default: current directory)"},
+                        "debounce": {"type": "integer", "description": "Quiet period in milliseconds before a batch fires"},
+                        "poll": {"type": "boolean", "description": "Use polling instead of native OS file events"}
+                    },
+                    "required": []
+                }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
             },
             ToolDefinition {
                 name: "version".to_string(),
@@ -200,6 +340,8 @@ pub fn run() -> Result<()> {
                     "properties": {},
                     "required": []
                 }),
+                since_protocol: "1.0".to_string(),
+                output_schema_version: 1,
             },
         ],
     };
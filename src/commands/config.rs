@@ -1,11 +1,17 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::analysis::grammar::{self, GrammarSource, GrammarSpec};
+use crate::analysis::treesitter::LanguageRegistry;
+use crate::analysis::walker;
+use crate::error::CtxError;
 use crate::output::OutputFormat;
 
 const CONFIG_PATH: &str = ".ctx/config.toml";
+const CONFIG_BASENAMES: &[&str] = &["config.toml", "config.json", "config.yaml", "config.yml"];
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Config {
@@ -17,23 +23,500 @@ pub struct Config {
 
     #[serde(default)]
     pub ignore: Option<Vec<String>>,
+
+    /// Extra tree-sitter grammars to load at runtime, beyond the built-in
+    /// rust/python/javascript/typescript set. See [`GrammarEntry`].
+    #[serde(default)]
+    pub grammars: Vec<GrammarEntry>,
+
+    /// Shorthand subcommand names, e.g. `rel = "related"`. Resolved against
+    /// argv before clap ever sees it — see [`resolve_alias`].
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+}
+
+/// Real subcommand names, which an alias is never allowed to shadow —
+/// otherwise a config typo like `status = "map"` would silently hijack a
+/// built-in command for every invocation.
+const RESERVED_COMMAND_NAMES: &[&str] = &[
+    "init", "status", "map", "summarize", "search", "related", "find", "callers", "deps",
+    "diff-context", "projects", "metrics", "inject", "hook-inject", "config", "grammar",
+    "schema", "version", "watch",
+];
+
+/// Resolves `argv[1]` (the subcommand position) against `config.aliases`
+/// before clap parses anything, so `ctx rel foo.rs` behaves exactly like
+/// `ctx related foo.rs`. Follows a chain of aliases up to a small depth
+/// cap (to catch a cycle without hanging), and never resolves an alias
+/// whose name collides with a real subcommand.
+pub fn resolve_alias(config: &Config, command: &str) -> String {
+    const MAX_DEPTH: usize = 8;
+
+    let mut current = command.to_string();
+    for _ in 0..MAX_DEPTH {
+        if RESERVED_COMMAND_NAMES.contains(&current.as_str()) {
+            return current;
+        }
+        match config.aliases.get(&current) {
+            Some(target) if target != &current => current = target.clone(),
+            _ => return current,
+        }
+    }
+
+    // Cycle (or a chain deeper than MAX_DEPTH): give up and hand back
+    // whatever clap was originally asked to run, so it produces its usual
+    // "unrecognized subcommand" error instead of looping forever.
+    command.to_string()
 }
 
 fn default_budget() -> usize {
     2000
 }
 
+/// A single `[[grammars]]` entry in `.ctx/config.toml`, either a local
+/// source:
+///
+/// ```toml
+/// [[grammars]]
+/// name = "dockerfile"
+/// filenames = ["Dockerfile", "*.dockerfile"]
+/// path = "/opt/grammars/tree-sitter-dockerfile"
+/// ```
+///
+/// or a remote one, fetched on demand (or via `ctx grammar fetch`):
+///
+/// ```toml
+/// [[grammars]]
+/// name = "zig"
+/// extensions = ["zig"]
+/// git = "https://github.com/maxxnino/tree-sitter-zig"
+/// rev = "0995a9e"
+/// ```
+///
+/// `path` may point either at a `tree-sitter-<lang>` source directory
+/// (compiled on first use) or at an already-built `.so`/`.dylib`. `git`
+/// clones the repository at `rev` (a branch, tag, or commit) into a cache
+/// directory first; `subpath` roots the build at a subdirectory of the
+/// clone, for monorepos that bundle multiple `tree-sitter-*` grammars.
+/// `filenames` lets a grammar claim exact or glob (`*` wildcard) filenames
+/// such as `Makefile` or `*.in`, and `shebangs` lets it claim extensionless
+/// scripts by their `#!` interpreter (e.g. `bash`, `python3`) — see
+/// [`crate::analysis::treesitter::LanguageRegistry::detect`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrammarEntry {
+    pub name: String,
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    #[serde(default)]
+    pub filenames: Vec<String>,
+    #[serde(default)]
+    pub shebangs: Vec<String>,
+    /// A local `tree-sitter-<lang>` source directory or prebuilt library.
+    /// Mutually exclusive with `git`; `git` wins if both are set.
+    #[serde(default)]
+    pub path: Option<String>,
+    /// A remote `tree-sitter-<lang>` repository to clone, at `rev`.
+    #[serde(default)]
+    pub git: Option<String>,
+    /// Branch, tag, or commit to check out after cloning `git`. Defaults
+    /// to `HEAD` (the repository's default branch) if unset.
+    #[serde(default)]
+    pub rev: Option<String>,
+    /// Subdirectory of the `git` clone to compile, for monorepos bundling
+    /// multiple grammars.
+    #[serde(default)]
+    pub subpath: Option<String>,
+}
+
+impl GrammarEntry {
+    /// Converts this config entry into a [`GrammarSpec`] the loader can use.
+    ///
+    /// `git` takes priority over `path` when both are set. A `path` ending
+    /// in a known shared-library extension is treated as a prebuilt
+    /// grammar; otherwise it's assumed to be a grammar source directory to
+    /// compile. An entry with neither set compiles a `Directory` source
+    /// pointing at an empty path, which fails cleanly in
+    /// [`crate::analysis::grammar::load_all`] with a "no src/parser.c
+    /// found" warning rather than panicking here.
+    pub fn to_spec(&self) -> GrammarSpec {
+        let source = if let Some(url) = &self.git {
+            GrammarSource::Git {
+                url: url.clone(),
+                rev: self.rev.clone().unwrap_or_else(|| "HEAD".to_string()),
+                subpath: self.subpath.clone(),
+            }
+        } else {
+            let path = Path::new(self.path.as_deref().unwrap_or_default());
+            let is_prebuilt = matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("so") | Some("dylib") | Some("dll")
+            );
+
+            if is_prebuilt {
+                GrammarSource::Prebuilt(path.to_path_buf())
+            } else {
+                GrammarSource::Directory(path.to_path_buf())
+            }
+        };
+
+        GrammarSpec {
+            name: self.name.clone(),
+            extensions: self.extensions.clone(),
+            filenames: self.filenames.clone(),
+            shebangs: self.shebangs.clone(),
+            source,
+        }
+    }
+}
+
+/// Builds a language registry for this run, loading grammars declared in
+/// `.ctx/config.toml` plus any prebuilt grammar libraries auto-discovered
+/// under [`grammar::GRAMMAR_RUNTIME_DIR`] (config entries win on name
+/// conflicts), on top of the built-in languages. Shared by every command
+/// that parses files, so a dynamic grammar declared in config is
+/// recognized everywhere, not just by the command that happened to wire
+/// it up first.
+pub fn build_registry(config: &Config) -> LanguageRegistry {
+    let project_root = std::env::current_dir().unwrap_or_else(|_| Path::new(".").to_path_buf());
+
+    let mut specs: Vec<_> = config.grammars.iter().map(|g| g.to_spec()).collect();
+    let configured_names: std::collections::HashSet<String> =
+        specs.iter().map(|s| s.name.clone()).collect();
+
+    for discovered in grammar::discover_runtime_grammars(&project_root) {
+        if !configured_names.contains(&discovered.name) {
+            specs.push(discovered);
+        }
+    }
+
+    if specs.is_empty() {
+        return LanguageRegistry::new();
+    }
+
+    LanguageRegistry::with_dynamic(grammar::load_all(&specs, &project_root))
+}
+
+/// Where an effective config value ultimately came from, for
+/// [`ConfigProvenance`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSource {
+    /// Built-in default; no layer set this value.
+    Default,
+    /// A `.ctx/config.{toml,json,yaml}` file at this path.
+    File(PathBuf),
+    /// An environment variable, named here.
+    Env(&'static str),
+    /// An explicit CLI override flag.
+    Cli,
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::File(path) => write!(f, "file:{}", path.display()),
+            Self::Env(name) => write!(f, "env:{}", name),
+            Self::Cli => write!(f, "cli"),
+        }
+    }
+}
+
+/// Records which layer supplied each field of an effective [`Config`],
+/// so callers like `run_list` can show provenance rather than just values.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigProvenance {
+    pub budget: ConfigSource,
+    pub languages: ConfigSource,
+    pub ignore: ConfigSource,
+    pub grammars: ConfigSource,
+    pub aliases: ConfigSource,
+}
+
+impl Default for ConfigProvenance {
+    fn default() -> Self {
+        Self {
+            budget: ConfigSource::Default,
+            languages: ConfigSource::Default,
+            ignore: ConfigSource::Default,
+            grammars: ConfigSource::Default,
+            aliases: ConfigSource::Default,
+        }
+    }
+}
+
+/// Explicit CLI flags that should win over every other config layer.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOverrides {
+    pub budget: Option<usize>,
+    pub languages: Option<Vec<String>>,
+    pub ignore: Option<Vec<String>>,
+}
+
+/// The same fields as [`Config`], but every field is optional so partial
+/// files/layers can be merged without clobbering values an earlier layer set.
+#[derive(Debug, Default, Deserialize)]
+struct PartialConfig {
+    budget: Option<usize>,
+    languages: Option<Vec<String>>,
+    ignore: Option<Vec<String>>,
+    grammars: Option<Vec<GrammarEntry>>,
+    aliases: Option<HashMap<String, String>>,
+}
+
+/// Accumulates [`PartialConfig`] layers (file, env, CLI) into a final
+/// [`Config`] while tracking which layer last set each field.
+#[derive(Default)]
+struct Accumulator {
+    config: PartialConfig,
+    provenance: ConfigProvenance,
+}
+
+impl Accumulator {
+    /// Applies a layer's partial values on top of what's accumulated so far.
+    /// Only fields the layer actually set are overridden, and only those
+    /// fields' provenance is updated to `source`.
+    fn apply(&mut self, layer: PartialConfig, source: ConfigSource) {
+        if layer.budget.is_some() {
+            self.config.budget = layer.budget;
+            self.provenance.budget = source.clone();
+        }
+        if layer.languages.is_some() {
+            self.config.languages = layer.languages;
+            self.provenance.languages = source.clone();
+        }
+        if layer.ignore.is_some() {
+            self.config.ignore = layer.ignore;
+            self.provenance.ignore = source.clone();
+        }
+        if layer.grammars.is_some() {
+            self.config.grammars = layer.grammars;
+            self.provenance.grammars = source.clone();
+        }
+        if layer.aliases.is_some() {
+            self.config.aliases = layer.aliases;
+            self.provenance.aliases = source;
+        }
+    }
+
+    fn finish(self) -> (Config, ConfigProvenance) {
+        let config = Config {
+            budget: self.config.budget.unwrap_or_else(default_budget),
+            languages: self.config.languages,
+            ignore: self.config.ignore,
+            grammars: self.config.grammars.unwrap_or_default(),
+            aliases: self.config.aliases.unwrap_or_default(),
+        };
+        (config, self.provenance)
+    }
+}
+
+/// An unrecognized key found while parsing a config file, e.g. a typo like
+/// `languges` instead of `languages`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigWarning {
+    pub file: PathBuf,
+    pub key: String,
+}
+
+impl std::fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown config key `{}` in {}", self.key, self.file.display())
+    }
+}
+
+/// Parses a `.ctx/config.*` file into a [`PartialConfig`], picking the
+/// format (TOML, JSON, or YAML) from the file's extension.
+///
+/// Unrecognized keys (typos like `languges`) don't fail parsing, but are
+/// collected via `serde_ignored` and returned as [`ConfigWarning`]s instead
+/// of being silently dropped. Type errors (e.g. a string where `budget`
+/// expects a number) are reported with the exact dotted field path via
+/// `serde_path_to_error`, rather than a generic "invalid type" message.
+fn parse_config_file(path: &Path) -> Result<(PartialConfig, Vec<ConfigWarning>)> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+
+    let mut unknown_keys = Vec::new();
+    let record_unknown = |key_path: serde_ignored::Path| unknown_keys.push(key_path.to_string());
+
+    let config = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => {
+            let de = &mut serde_json::Deserializer::from_str(&content);
+            let ignored = serde_ignored::Deserializer::new(de, record_unknown);
+            serde_path_to_error::deserialize(ignored)
+                .map_err(|e| config_error(path, &e))?
+        }
+        Some("yaml") | Some("yml") => {
+            let de = serde_yaml::Deserializer::from_str(&content);
+            let ignored = serde_ignored::Deserializer::new(de, record_unknown);
+            serde_path_to_error::deserialize(ignored)
+                .map_err(|e| config_error(path, &e))?
+        }
+        _ => {
+            let de = toml::Deserializer::new(&content);
+            let ignored = serde_ignored::Deserializer::new(de, record_unknown);
+            serde_path_to_error::deserialize(ignored)
+                .map_err(|e| config_error(path, &e))?
+        }
+    };
+
+    let warnings = unknown_keys
+        .into_iter()
+        .map(|key| ConfigWarning {
+            file: path.to_path_buf(),
+            key,
+        })
+        .collect();
+
+    Ok((config, warnings))
+}
+
+/// Wraps a `serde_path_to_error` failure as a [`CtxError::ConfigError`], so
+/// malformed config surfaces with its own exit code rather than a generic
+/// runtime error.
+fn config_error<E: std::fmt::Display>(path: &Path, e: &serde_path_to_error::Error<E>) -> anyhow::Error {
+    CtxError::ConfigError {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    }
+    .into()
+}
+
+/// Finds the first `.ctx/config.{toml,json,yaml,yml}` in `dir`, if any.
+fn config_file_in(dir: &Path) -> Option<PathBuf> {
+    CONFIG_BASENAMES
+        .iter()
+        .map(|name| dir.join(".ctx").join(name))
+        .find(|path| path.exists())
+}
+
+/// Walks from `start` up through every ancestor directory, returning any
+/// `.ctx/config.*` files found, ordered root-most first so later entries
+/// (closer to `start`) are the more specific, overriding layer.
+fn discover_config_files(start: &Path) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = start.ancestors().filter_map(config_file_in).collect();
+    found.reverse();
+    found
+}
+
+/// Finds every nested `.ctx/config.*` strictly below `root` — the root's own
+/// config is already covered by [`discover_config_files`]'s upward walk —
+/// and returns each one's `ignore` patterns anchored to its directory, the
+/// way a nested `.gitignore` only applies beneath the directory it's in.
+///
+/// For example a `sub/dir/.ctx/config.toml` with `ignore = ["*.min.js"]`
+/// contributes the pattern `sub/dir/**/*.min.js`, so it's merged on top of
+/// the parent's ignore list for files beneath `sub/dir` without affecting
+/// the rest of the tree. Malformed nested files fail the same way a
+/// malformed root config does, via [`parse_config_file`].
+pub fn nested_ignore_patterns(root: &Path) -> Result<Vec<String>> {
+    let mut patterns = Vec::new();
+
+    for entry in walker::create_walker_with_hidden(root).build().flatten() {
+        let path = entry.path();
+
+        if !CONFIG_BASENAMES
+            .iter()
+            .any(|name| path.file_name().map(|f| f == *name).unwrap_or(false))
+        {
+            continue;
+        }
+
+        let Some(ctx_dir) = path.parent() else { continue };
+        if ctx_dir.file_name().map(|n| n != ".ctx").unwrap_or(true) {
+            continue;
+        }
+
+        let Some(project_dir) = ctx_dir.parent() else { continue };
+        if project_dir == root {
+            continue; // root's own config; already loaded by ascending discovery
+        }
+
+        let (layer, _warnings) = parse_config_file(path)?;
+        if let Some(ignore) = layer.ignore {
+            let rel = project_dir.strip_prefix(root).unwrap_or(project_dir);
+            for pattern in ignore {
+                patterns.push(format!("{}/**/{}", rel.display(), pattern));
+            }
+        }
+    }
+
+    Ok(patterns)
+}
+
+/// Reads `CTX_BUDGET`, `CTX_LANGUAGES`, and `CTX_IGNORE` (comma-separated
+/// lists) into a [`PartialConfig`] layer.
+fn env_layer() -> PartialConfig {
+    PartialConfig {
+        budget: std::env::var("CTX_BUDGET").ok().and_then(|v| v.parse().ok()),
+        languages: std::env::var("CTX_LANGUAGES")
+            .ok()
+            .map(|v| v.split(',').map(|s| s.trim().to_string()).collect()),
+        ignore: std::env::var("CTX_IGNORE")
+            .ok()
+            .map(|v| v.split(',').map(|s| s.trim().to_string()).collect()),
+        grammars: None,
+        aliases: None,
+    }
+}
+
 impl Config {
+    /// Loads the effective config by merging every `.ctx/config.*` file from
+    /// the filesystem root down to the current directory (child overrides
+    /// parent), then overlaying environment variables. Unknown keys are
+    /// printed as warnings to stderr rather than failing. For CLI overrides,
+    /// provenance, strict mode, and structured warnings, use
+    /// [`Self::load_layered`].
     pub fn load() -> Result<Self> {
-        let path = Path::new(CONFIG_PATH);
+        let (config, _, warnings) = Self::load_layered(&ConfigOverrides::default(), false)?;
+        for warning in &warnings {
+            eprintln!("warning: {}", warning);
+        }
+        Ok(config)
+    }
+
+    /// Loads the effective config the same way as [`Self::load`], plus an
+    /// explicit CLI-override layer. Returns a [`ConfigProvenance`] recording
+    /// which layer supplied each field, and any [`ConfigWarning`]s for
+    /// unrecognized keys found along the way. If `strict` is true, any
+    /// unrecognized key is a hard error instead of a warning.
+    pub fn load_layered(
+        overrides: &ConfigOverrides,
+        strict: bool,
+    ) -> Result<(Config, ConfigProvenance, Vec<ConfigWarning>)> {
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let mut acc = Accumulator::default();
+        let mut warnings = Vec::new();
 
-        if !path.exists() {
-            return Ok(Self::default());
+        for path in discover_config_files(&cwd) {
+            let (layer, file_warnings) = parse_config_file(&path)?;
+            acc.apply(layer, ConfigSource::File(path));
+            warnings.extend(file_warnings);
         }
 
-        let content = fs::read_to_string(path).context("Failed to read config file")?;
+        if strict && !warnings.is_empty() {
+            let message = warnings
+                .iter()
+                .map(|w| w.to_string())
+                .collect::<Vec<_>>()
+                .join("; ");
+            anyhow::bail!("unknown config keys found in strict mode: {}", message);
+        }
 
-        toml::from_str(&content).context("Failed to parse config file")
+        acc.apply(env_layer(), ConfigSource::Env("CTX_*"));
+
+        let cli_layer = PartialConfig {
+            budget: overrides.budget,
+            languages: overrides.languages.clone(),
+            ignore: overrides.ignore.clone(),
+            grammars: None,
+            aliases: None,
+        };
+        acc.apply(cli_layer, ConfigSource::Cli);
+
+        let (config, provenance) = acc.finish();
+        Ok((config, provenance, warnings))
     }
 
     pub fn save(&self) -> Result<()> {
@@ -77,6 +560,12 @@ pub fn run_get(key: &str, format: OutputFormat) -> Result<()> {
             });
             println!("{}", serde_json::to_string(&output)?);
         }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
     }
 
     Ok(())
@@ -114,31 +603,238 @@ pub fn run_set(key: &str, value: &str, format: OutputFormat) -> Result<()> {
             });
             println!("{}", serde_json::to_string(&output)?);
         }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
     }
 
     Ok(())
 }
 
 pub fn run_list(format: OutputFormat) -> Result<()> {
-    let config = Config::load()?;
+    run_list_with_overrides(&ConfigOverrides::default(), false, format)
+}
+
+/// Like [`run_list`], but applies CLI overrides and, for human output,
+/// annotates each value with the layer that supplied it. If `strict` is
+/// true, unknown config keys become a hard error instead of a warning.
+pub fn run_list_with_overrides(
+    overrides: &ConfigOverrides,
+    strict: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let (config, provenance, warnings) = Config::load_layered(overrides, strict)?;
 
     match format {
         OutputFormat::Human => {
-            println!("budget = {}", config.budget);
+            for warning in &warnings {
+                eprintln!("warning: {}", warning);
+            }
+            println!("budget = {}  ({})", config.budget, provenance.budget);
             if let Some(languages) = &config.languages {
-                println!("languages = {}", languages.join(", "));
+                println!("languages = {}  ({})", languages.join(", "), provenance.languages);
             }
             if let Some(ignore) = &config.ignore {
-                println!("ignore = {}", ignore.join(", "));
+                println!("ignore = {}  ({})", ignore.join(", "), provenance.ignore);
+            }
+            if !config.grammars.is_empty() {
+                println!(
+                    "grammars = {}  ({})",
+                    config
+                        .grammars
+                        .iter()
+                        .map(|g| g.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    provenance.grammars
+                );
+            }
+            if !config.aliases.is_empty() {
+                let mut names: Vec<&str> = config.aliases.keys().map(|k| k.as_str()).collect();
+                names.sort();
+                println!(
+                    "aliases = {}  ({})",
+                    names
+                        .iter()
+                        .map(|name| format!("{}={}", name, config.aliases[*name]))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    provenance.aliases
+                );
             }
         }
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&config)?);
+            let output = serde_json::json!({
+                "config": config,
+                "provenance": provenance,
+                "warnings": warnings.iter().map(|w| w.to_string()).collect::<Vec<_>>(),
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
         }
         OutputFormat::Compact => {
-            println!("{}", serde_json::to_string(&config)?);
+            let output = serde_json::json!({
+                "config": config,
+                "provenance": provenance,
+                "warnings": warnings.iter().map(|w| w.to_string()).collect::<Vec<_>>(),
+            });
+            println!("{}", serde_json::to_string(&output)?);
+        }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_config(dir: &Path, contents: &str) {
+        let ctx_dir = dir.join(".ctx");
+        fs::create_dir_all(&ctx_dir).unwrap();
+        fs::write(ctx_dir.join("config.toml"), contents).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_alias_splices_to_its_target() {
+        let mut config = Config::default();
+        config.aliases.insert("sk".to_string(), "summarize --skeleton".to_string());
+        assert_eq!(resolve_alias(&config, "sk"), "summarize --skeleton");
+    }
+
+    #[test]
+    fn test_resolve_alias_follows_a_chain() {
+        let mut config = Config::default();
+        config.aliases.insert("a".to_string(), "b".to_string());
+        config.aliases.insert("b".to_string(), "related".to_string());
+        assert_eq!(resolve_alias(&config, "a"), "related");
+    }
+
+    #[test]
+    fn test_resolve_alias_never_shadows_a_real_subcommand() {
+        let mut config = Config::default();
+        config.aliases.insert("status".to_string(), "map".to_string());
+        assert_eq!(resolve_alias(&config, "status"), "status");
+    }
+
+    #[test]
+    fn test_resolve_alias_gives_up_on_a_cycle() {
+        let mut config = Config::default();
+        config.aliases.insert("a".to_string(), "b".to_string());
+        config.aliases.insert("b".to_string(), "a".to_string());
+        // Should terminate rather than looping forever.
+        let _ = resolve_alias(&config, "a");
+    }
+
+    #[test]
+    fn test_resolve_alias_unknown_command_is_unchanged() {
+        let config = Config::default();
+        assert_eq!(resolve_alias(&config, "nope"), "nope");
+    }
+
+    #[test]
+    fn test_parse_config_file_reports_unknown_keys() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "budget = 1000\nlanguges = [\"rust\"]\n").unwrap();
+
+        let (config, warnings) = parse_config_file(&path).unwrap();
+        assert_eq!(config.budget, Some(1000));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].key, "languges");
+    }
+
+    #[test]
+    fn test_parse_config_file_no_unknown_keys_is_silent() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "budget = 1000\n").unwrap();
+
+        let (_config, warnings) = parse_config_file(&path).unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_parse_config_file_supports_json_and_yaml_by_extension() {
+        let dir = TempDir::new().unwrap();
+
+        let json_path = dir.path().join("config.json");
+        fs::write(&json_path, r#"{"budget": 2000}"#).unwrap();
+        let (json_config, _) = parse_config_file(&json_path).unwrap();
+        assert_eq!(json_config.budget, Some(2000));
+
+        let yaml_path = dir.path().join("config.yaml");
+        fs::write(&yaml_path, "budget: 3000\n").unwrap();
+        let (yaml_config, _) = parse_config_file(&yaml_path).unwrap();
+        assert_eq!(yaml_config.budget, Some(3000));
+    }
+
+    #[test]
+    fn test_discover_config_files_orders_root_most_first() {
+        let root = TempDir::new().unwrap();
+        let child = root.path().join("a").join("b");
+        fs::create_dir_all(&child).unwrap();
+
+        write_config(root.path(), "budget = 1000\n");
+        write_config(&child, "budget = 2000\n");
+
+        let found = discover_config_files(&child);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0], root.path().join(".ctx").join("config.toml"));
+        assert_eq!(found[1], child.join(".ctx").join("config.toml"));
+    }
+
+    #[test]
+    fn test_discover_config_files_none_found_is_empty() {
+        let root = TempDir::new().unwrap();
+        assert!(discover_config_files(root.path()).is_empty());
+    }
+
+    #[test]
+    fn test_accumulator_child_layer_overrides_parent_only_for_set_fields() {
+        let mut acc = Accumulator::default();
+        acc.apply(
+            PartialConfig { budget: Some(1000), languages: Some(vec!["rust".to_string()]), ..Default::default() },
+            ConfigSource::File(PathBuf::from("/root/.ctx/config.toml")),
+        );
+        acc.apply(
+            PartialConfig { budget: Some(2000), ..Default::default() },
+            ConfigSource::File(PathBuf::from("/root/a/.ctx/config.toml")),
+        );
+
+        let (config, provenance) = acc.finish();
+        assert_eq!(config.budget, 2000);
+        // Unset in the child layer, so the parent's value survives.
+        assert_eq!(config.languages, Some(vec!["rust".to_string()]));
+        assert_eq!(provenance.budget, ConfigSource::File(PathBuf::from("/root/a/.ctx/config.toml")));
+    }
+
+    #[test]
+    fn test_nested_ignore_patterns_anchors_to_subdirectory() {
+        let root = TempDir::new().unwrap();
+        let sub = root.path().join("sub").join("dir");
+        fs::create_dir_all(&sub).unwrap();
+        write_config(&sub, "ignore = [\"*.min.js\"]\n");
+
+        let patterns = nested_ignore_patterns(root.path()).unwrap();
+        assert_eq!(patterns, vec!["sub/dir/**/*.min.js".to_string()]);
+    }
+
+    #[test]
+    fn test_nested_ignore_patterns_ignores_roots_own_config() {
+        let root = TempDir::new().unwrap();
+        write_config(root.path(), "ignore = [\"*.log\"]\n");
+
+        let patterns = nested_ignore_patterns(root.path()).unwrap();
+        assert!(patterns.is_empty());
+    }
+}
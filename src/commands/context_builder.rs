@@ -1,33 +1,87 @@
 use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Serialize;
 
 use crate::analysis::git;
 use crate::analysis::relevance;
+use crate::analysis::stats;
+use crate::analysis::tokenizer::{self, Tokenizer};
 use crate::analysis::treesitter;
 use crate::analysis::walker;
 
+/// Default number of recent commits scanned for the churn signal in
+/// [`score_files_for_prompt`](relevance::score_files_for_prompt) when a
+/// caller doesn't need a different value. See [`build_context`].
+pub const DEFAULT_CHURN_POOL_SIZE: usize = 200;
+
+/// One named portion of the context string built by
+/// [`build_context_detailed`], with its own token cost, so a structured
+/// consumer (see `ctx inject --format json`) can see where its budget
+/// was actually spent rather than just the combined text.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ContextSource {
+    pub name: String,
+    pub tokens: usize,
+}
+
 /// Build context string for a prompt within a token budget.
 ///
 /// # Arguments
 /// * `prompt` - The user's prompt to analyze for context
 /// * `budget` - Maximum token budget for the context
 /// * `include_uncommitted` - Whether to include uncommitted diff stats
+/// * `churn_pool_size` - Number of recent commits to scan for the
+///   relevance-scoring churn signal (see [`DEFAULT_CHURN_POOL_SIZE`])
+/// * `excludes` - Glob patterns (e.g. `vendor/**`, `*.lock`, `dist/**`) to
+///   drop from both the candidate file list and the churn signal, so
+///   generated/lockfiles don't crowd out real source in the token budget
+/// * `tokenizer` - Token-count estimator used for every budget check, so
+///   callers targeting a specific model's tokenizer get accurate counts
+///   (see [`tokenizer::Tokenizer`])
 ///
 /// # Returns
-/// A formatted context string with project info, recent files, mentioned files,
-/// relevant files, and keywords.
-pub fn build_context(prompt: &str, budget: usize, include_uncommitted: bool) -> Result<String> {
+/// A formatted context string with project info, per-language code
+/// statistics, recent files, mentioned files, relevant files (each with a
+/// line-count annotation), and keywords.
+pub fn build_context(
+    prompt: &str,
+    budget: usize,
+    include_uncommitted: bool,
+    churn_pool_size: usize,
+    excludes: &[String],
+    tokenizer: &dyn Tokenizer,
+) -> Result<String> {
+    let (context, _sources) =
+        build_context_detailed(prompt, budget, include_uncommitted, churn_pool_size, excludes, tokenizer)?;
+    Ok(context)
+}
+
+/// Same as [`build_context`], but also returns a breakdown of which named
+/// section (`git_status`, `recent_files`, `mentioned_files`,
+/// `keyword_matches`) each spent token went to.
+pub fn build_context_detailed(
+    prompt: &str,
+    budget: usize,
+    include_uncommitted: bool,
+    churn_pool_size: usize,
+    excludes: &[String],
+    tokenizer: &dyn Tokenizer,
+) -> Result<(String, Vec<ContextSource>)> {
     let mut context_parts = Vec::new();
     let mut tokens_used = 0;
+    let mut sources = Vec::new();
 
     let cwd = std::env::current_dir()?;
 
     // Project info
+    let section_start = tokens_used;
+
     let project_name = treesitter::detect_project_name(&cwd).unwrap_or_else(|| "unknown".to_string());
     let project_type = treesitter::detect_project_type(&cwd).unwrap_or("unknown");
 
     // Git info
-    let git_info = if let Ok(repo) = git::find_repo(&cwd) {
-        let status = git::get_status(&repo).ok();
+    let git_info = if let Ok(mut repo) = git::find_repo(&cwd) {
+        let status = git::get_status(&mut repo).ok();
         let branch = status.as_ref().map(|s| s.branch.clone()).unwrap_or_else(|| "unknown".to_string());
 
         let dirty_marker = if status.as_ref().map(|s| s.is_dirty).unwrap_or(false) {
@@ -42,15 +96,37 @@ pub fn build_context(prompt: &str, budget: usize, include_uncommitted: bool) ->
     };
 
     let header = format!("[CTX: project={} lang={} {}]", project_name, project_type, git_info);
-    tokens_used += estimate_tokens(&header);
+    tokens_used += tokenizer.estimate_tokens(&header);
     context_parts.push(header);
 
+    // Tokei-style code/comment/blank statistics, so the model has a sense of
+    // each language's footprint before deciding what to pull in.
+    let project_stats = stats::collect_project_stats(&cwd, excludes);
+    for (language, s) in project_stats.by_language_sorted() {
+        let line = format!(
+            "[STATS: lang={} code={} comments={} blank={}]",
+            language, s.code, s.comments, s.blanks
+        );
+        let line_tokens = tokenizer.estimate_tokens(&line);
+        if tokens_used + line_tokens > budget {
+            break;
+        }
+        tokens_used += line_tokens;
+        context_parts.push(line);
+    }
+
+    if tokens_used > section_start {
+        sources.push(ContextSource { name: "git_status".to_string(), tokens: tokens_used - section_start });
+    }
+    let section_start = tokens_used;
+
     // Recent file activity
     if let Ok(repo) = git::find_repo(&cwd) {
-        if let Ok(activity) = git::get_recent_file_activity(&repo, 5) {
+        if let Ok(graph) = git::CommitGraph::build(&repo, git::DEFAULT_HISTORY_POOL_SIZE) {
+            let activity = git::get_recent_file_activity(&graph, 5);
             for file in activity.iter().take(3) {
                 let line = format!("[RECENT: {} modified {}]", file.path, file.last_modified);
-                let line_tokens = estimate_tokens(&line);
+                let line_tokens = tokenizer.estimate_tokens(&line);
                 if tokens_used + line_tokens > budget {
                     break;
                 }
@@ -64,7 +140,7 @@ pub fn build_context(prompt: &str, budget: usize, include_uncommitted: bool) ->
             if let Ok((ins, del)) = git::get_diff_summary(&repo) {
                 if ins > 0 || del > 0 {
                     let line = format!("[UNCOMMITTED: +{} -{}]", ins, del);
-                    let line_tokens = estimate_tokens(&line);
+                    let line_tokens = tokenizer.estimate_tokens(&line);
                     if tokens_used + line_tokens <= budget {
                         tokens_used += line_tokens;
                         context_parts.push(line);
@@ -74,11 +150,16 @@ pub fn build_context(prompt: &str, budget: usize, include_uncommitted: bool) ->
         }
     }
 
+    if tokens_used > section_start {
+        sources.push(ContextSource { name: "recent_files".to_string(), tokens: tokens_used - section_start });
+    }
+    let section_start = tokens_used;
+
     // Find files mentioned in prompt
     let mentioned_files = relevance::extract_mentioned_files(prompt);
     for file in mentioned_files.iter().take(5) {
         let line = format!("[MENTIONED: {}]", file);
-        let line_tokens = estimate_tokens(&line);
+        let line_tokens = tokenizer.estimate_tokens(&line);
         if tokens_used + line_tokens > budget {
             break;
         }
@@ -86,12 +167,18 @@ pub fn build_context(prompt: &str, budget: usize, include_uncommitted: bool) ->
         context_parts.push(line);
     }
 
+    if tokens_used > section_start {
+        sources.push(ContextSource { name: "mentioned_files".to_string(), tokens: tokens_used - section_start });
+    }
+    let section_start = tokens_used;
+
     // Extract keywords and find relevant files
     let keywords = relevance::extract_keywords(prompt);
     if !keywords.is_empty() {
-        // Collect all source files (respecting .gitignore and common ignores)
+        // Collect all source files (respecting .gitignore, common ignores,
+        // and any user-supplied exclude globs)
         let mut all_files = Vec::new();
-        let file_walker = walker::create_walker(&cwd).build();
+        let file_walker = walker::create_walker_with_extra_ignores(&cwd, excludes).build();
 
         for entry in file_walker.flatten() {
             if entry.path().is_file() {
@@ -103,46 +190,73 @@ pub fn build_context(prompt: &str, budget: usize, include_uncommitted: bool) ->
 
         // Score files for relevance
         if let Ok(repo) = git::find_repo(&cwd) {
-            if let Ok(scored) = relevance::score_files_for_prompt(&repo, prompt, &all_files, budget - tokens_used) {
+            if let Ok(scored) = relevance::score_files_for_prompt(
+                &repo,
+                prompt,
+                &all_files,
+                budget - tokens_used,
+                churn_pool_size,
+                excludes,
+                &[],
+                false,
+                false,
+            ) {
                 for scored_file in scored.iter().take(5) {
                     let reasons = scored_file.reasons.join(", ");
                     let line = format!("[RELEVANT: {} ({})]", scored_file.path, reasons);
-                    let line_tokens = estimate_tokens(&line);
+                    let line_tokens = tokenizer.estimate_tokens(&line);
                     if tokens_used + line_tokens > budget {
                         break;
                     }
                     tokens_used += line_tokens;
                     context_parts.push(line);
+
+                    if let Ok(Some(file_stats)) = stats::stats_for_file(&cwd.join(&scored_file.path)) {
+                        let loc_line = format!(
+                            "[LOC: {} code={} comments={}]",
+                            scored_file.path, file_stats.code, file_stats.comments
+                        );
+                        let loc_tokens = tokenizer.estimate_tokens(&loc_line);
+                        if tokens_used + loc_tokens <= budget {
+                            tokens_used += loc_tokens;
+                            context_parts.push(loc_line);
+                        }
+                    }
                 }
             }
         }
     }
 
-    // Keywords summary
-    if !keywords.is_empty() && tokens_used < budget {
-        let keywords_str = keywords.iter().take(10).cloned().collect::<Vec<_>>().join(", ");
-        let line = format!("[KEYWORDS: {}]", keywords_str);
-        let line_tokens = estimate_tokens(&line);
+    // Keywords summary - RAKE phrases carry more signal than isolated words
+    // (e.g. "connection pool" over "connection", "pool" separately), so
+    // downstream scoring can prefer the higher-weight ones.
+    let key_phrases = relevance::extract_key_phrases(prompt);
+    if !key_phrases.is_empty() && tokens_used < budget {
+        let phrases_str = key_phrases
+            .iter()
+            .map(|(score, phrase)| format!("{} ({:.1})", phrase, score))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let line = format!("[KEYWORDS: {}]", phrases_str);
+        let line_tokens = tokenizer.estimate_tokens(&line);
         if tokens_used + line_tokens <= budget {
+            tokens_used += line_tokens;
             context_parts.push(line);
         }
     }
 
-    Ok(context_parts.join("\n"))
+    if tokens_used > section_start {
+        sources.push(ContextSource { name: "keyword_matches".to_string(), tokens: tokens_used - section_start });
+    }
+
+    Ok((context_parts.join("\n"), sources))
 }
 
-/// Estimate token count for text using a hybrid word/character approach.
+/// Estimate token count for text using the default [`tokenizer::HeuristicTokenizer`].
 ///
-/// This provides a more accurate estimate than pure character count,
-/// especially for code which tends to have shorter tokens due to
-/// punctuation and operators.
-///
-/// # Algorithm
-/// 1. Count words (split on whitespace)
-/// 2. Count punctuation/operators (often individual tokens in code)
-/// 3. Character-based estimate (non-whitespace / 4)
-/// 4. Weighted word estimate (words * 1.3 + punctuation / 2)
-/// 5. Average the character and word estimates
+/// Kept as a free function for callers (e.g. [`summarize`](crate::commands::summarize))
+/// that just need a quick estimate and don't route through [`build_context`]'s
+/// pluggable [`tokenizer::Tokenizer`].
 ///
 /// # Examples
 /// ```
@@ -152,57 +266,7 @@ pub fn build_context(prompt: &str, budget: usize, include_uncommitted: bool) ->
 /// assert!(estimate_tokens("hello world") >= 2);
 /// ```
 pub fn estimate_tokens(text: &str) -> usize {
-    if text.is_empty() {
-        return 0;
-    }
-
-    // Count words
-    let word_count = text.split_whitespace().count();
-
-    // Count punctuation/operators (these are often individual tokens)
-    let punct_count = text
-        .chars()
-        .filter(|c| {
-            matches!(
-                c,
-                '(' | ')'
-                    | '{'
-                    | '}'
-                    | '['
-                    | ']'
-                    | ';'
-                    | ','
-                    | '.'
-                    | ':'
-                    | '<'
-                    | '>'
-                    | '='
-                    | '+'
-                    | '-'
-                    | '*'
-                    | '/'
-                    | '&'
-                    | '|'
-                    | '!'
-                    | '@'
-                    | '#'
-                    | '$'
-                    | '%'
-                    | '^'
-            )
-        })
-        .count();
-
-    // Character-based estimate (for non-whitespace)
-    let char_count = text.chars().filter(|c| !c.is_whitespace()).count();
-    let char_estimate = (char_count + 3) / 4;
-
-    // Weighted average: code typically has ~1.3 tokens per word due to operators
-    // and shorter identifiers
-    let word_estimate = (word_count as f64 * 1.3) as usize + punct_count / 2;
-
-    // Take the average of both approaches for robustness
-    (char_estimate + word_estimate) / 2
+    tokenizer::estimate_tokens_heuristic(text)
 }
 
 #[cfg(test)]
@@ -0,0 +1,126 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::analysis::deadline::Deadline;
+use crate::analysis::import_graph;
+use crate::analysis::walker;
+use crate::commands::config::{self, Config};
+use crate::output::OutputFormat;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FileDeps {
+    pub path: String,
+    pub depends_on: Vec<String>,
+    pub external_imports: Vec<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DepsResult {
+    pub files: Vec<FileDeps>,
+    /// A dependency cycle among `files`, if one exists (first file repeated
+    /// at the end).
+    pub cycle: Option<Vec<String>>,
+    /// `true` if `--timeout` cut the walk short before every file was
+    /// collected; `files` and `cycle` then only reflect a partial graph.
+    pub truncated: bool,
+}
+
+impl std::fmt::Display for DepsResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for file in &self.files {
+            writeln!(f, "{}", file.path)?;
+            for dep in &file.depends_on {
+                writeln!(f, "  -> {}", dep)?;
+            }
+            for ext in &file.external_imports {
+                writeln!(f, "  -> {} (external)", ext)?;
+            }
+        }
+
+        if let Some(cycle) = &self.cycle {
+            writeln!(f, "\nCycle detected: {}", cycle.join(" -> "))?;
+        }
+
+        if self.truncated {
+            writeln!(f, "\n(truncated: --timeout reached before the walk finished)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Builds the intra-repo module dependency graph and reports it, flagging
+/// the first cycle found (if any). See
+/// [`import_graph::build_dependency_graph_with_registry`].
+pub fn run(deadline: Deadline, format: OutputFormat) -> Result<()> {
+    let config = Config::load().unwrap_or_default();
+    let registry = config::build_registry(&config);
+    let extra_ignores = config.ignore.clone().unwrap_or_default();
+    let file_walker = walker::create_walker_with_extra_ignores(Path::new("."), &extra_ignores).build();
+    let mut truncated = false;
+    let mut files: Vec<PathBuf> = Vec::new();
+    for entry in file_walker.flatten() {
+        if deadline.is_expired() {
+            truncated = true;
+            break;
+        }
+        let path = entry.into_path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+
+    let graph = import_graph::build_dependency_graph_with_registry(&files, &registry);
+
+    let mut paths: Vec<&PathBuf> = graph.edges.keys().collect();
+    paths.sort();
+
+    let mut deps = Vec::new();
+    for path in paths {
+        let edges = &graph.edges[path];
+        if edges.is_empty() {
+            continue;
+        }
+
+        let mut depends_on = Vec::new();
+        let mut external_imports = Vec::new();
+        for edge in edges {
+            match &edge.resolved {
+                Some(resolved) => depends_on.push(resolved.to_string_lossy().to_string()),
+                None => external_imports.push(edge.imported.clone()),
+            }
+        }
+
+        deps.push(FileDeps {
+            path: path.to_string_lossy().to_string(),
+            depends_on,
+            external_imports,
+        });
+    }
+
+    let cycle = graph
+        .find_cycle()
+        .map(|files| files.into_iter().map(|p| p.to_string_lossy().to_string()).collect());
+
+    let result = DepsResult { files: deps, cycle, truncated };
+
+    match format {
+        OutputFormat::Human => println!("{}", result),
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+        OutputFormat::Compact => {
+            println!("{}", serde_json::to_string(&result)?);
+        }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
+    }
+
+    Ok(())
+}
@@ -1,23 +1,27 @@
 use anyhow::Result;
 use colored::Colorize;
 use git2::{DiffOptions, Repository};
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::collections::HashSet;
 use std::path::Path;
 
+use tree_sitter::QueryCursor;
+
 use crate::analysis::git;
-use crate::analysis::symbols::{self, SymbolKind};
-use crate::analysis::treesitter::{self, SupportedLanguage};
+use crate::analysis::symbols::{self, Symbol, SymbolKind, SymbolNode};
+use crate::analysis::treesitter::{self, LanguageRegistry};
+use crate::commands::config::{self, Config};
 use crate::output::OutputFormat;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct DiffContext {
     pub ref_name: String,
     pub files_changed: Vec<FileContext>,
     pub callers_affected: Vec<CallerInfo>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct FileContext {
     pub path: String,
     pub insertions: usize,
@@ -25,7 +29,7 @@ pub struct FileContext {
     pub functions_modified: Vec<FunctionContext>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct FunctionContext {
     pub name: String,
     pub kind: String,
@@ -33,7 +37,7 @@ pub struct FunctionContext {
     pub signature: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct CallerInfo {
     pub function_modified: String,
     pub called_from: Vec<String>,
@@ -83,8 +87,11 @@ pub fn run(git_ref: Option<&str>, format: OutputFormat) -> Result<()> {
     let cwd = std::env::current_dir()?;
     let repo = git::find_repo(&cwd)?;
 
+    let config = Config::load().unwrap_or_default();
+    let registry = config::build_registry(&config);
+
     let ref_name = git_ref.unwrap_or("HEAD");
-    let diff_context = analyze_diff(&repo, ref_name)?;
+    let diff_context = analyze_diff(&repo, ref_name, &registry)?;
 
     match format {
         OutputFormat::Human => println!("{}", diff_context),
@@ -94,12 +101,18 @@ pub fn run(git_ref: Option<&str>, format: OutputFormat) -> Result<()> {
         OutputFormat::Compact => {
             println!("{}", serde_json::to_string(&diff_context)?);
         }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
     }
 
     Ok(())
 }
 
-fn analyze_diff(repo: &Repository, ref_name: &str) -> Result<DiffContext> {
+fn analyze_diff(repo: &Repository, ref_name: &str, registry: &LanguageRegistry) -> Result<DiffContext> {
     let head = repo.head()?.peel_to_tree()?;
 
     let mut diff_opts = DiffOptions::new();
@@ -164,7 +177,7 @@ fn analyze_diff(repo: &Repository, ref_name: &str) -> Result<DiffContext> {
 
         let stats = diff.stats()?;
 
-        let functions_modified = find_modified_functions(path, &changed_lines)?;
+        let functions_modified = find_modified_functions(path, &changed_lines, registry)?;
 
         for func in &functions_modified {
             modified_functions.insert(format!("{}:{}", path_str, func.name));
@@ -179,7 +192,7 @@ fn analyze_diff(repo: &Repository, ref_name: &str) -> Result<DiffContext> {
     }
 
     // Find callers of modified functions
-    let callers_affected = find_callers(&modified_functions)?;
+    let callers_affected = find_callers(&modified_functions, registry)?;
 
     Ok(DiffContext {
         ref_name: ref_name.to_string(),
@@ -191,41 +204,41 @@ fn analyze_diff(repo: &Repository, ref_name: &str) -> Result<DiffContext> {
 fn find_modified_functions(
     path: &Path,
     changed_lines: &[(usize, bool)],
+    registry: &LanguageRegistry,
 ) -> Result<Vec<FunctionContext>> {
-    let lang = match SupportedLanguage::from_path(path) {
+    let content = std::fs::read_to_string(path)?;
+    // Extension first, falling back to a `#!` shebang for extensionless
+    // scripts, so e.g. a shebang-only `bin/migrate` isn't silently skipped.
+    let lang = match registry.detect(path, content.lines().next()) {
         Some(l) => l,
         None => return Ok(Vec::new()),
     };
 
-    let content = std::fs::read_to_string(path)?;
-    let tree = match treesitter::parse_file(path, &content)? {
+    let tree = match treesitter::parse_with_language(&content, &lang)? {
         Some(t) => t,
         None => return Ok(Vec::new()),
     };
 
-    let all_symbols = symbols::extract_symbols(&tree, &content, &lang);
+    // The hierarchical tree (rather than extract_symbols's flat list) carries
+    // each function/method's true end_line from its tree-sitter node range,
+    // so a changed line can be matched against its exact body instead of a
+    // brace-counting guess.
+    let symbol_tree = symbols::extract_symbol_tree(&tree, &content, &lang);
+    let mut functions = Vec::new();
+    collect_function_ranges(&symbol_tree.roots, &mut functions);
+
     let changed_line_nums: HashSet<usize> = changed_lines.iter().map(|(l, _)| *l).collect();
 
     let mut modified = Vec::new();
-    let lines: Vec<&str> = content.lines().collect();
-
-    for sym in all_symbols {
-        if sym.kind != SymbolKind::Function && sym.kind != SymbolKind::Method {
-            continue;
-        }
-
-        // Find function end (rough heuristic - look for closing brace at same indent level)
-        let func_end = find_function_end(&lines, sym.line - 1);
-
-        // Check if any changed line is within this function
-        let in_function = (sym.line..=func_end).any(|l| changed_line_nums.contains(&l));
+    for (symbol, start_line, end_line) in functions {
+        let in_function = (start_line..=end_line).any(|l| changed_line_nums.contains(&l));
 
         if in_function {
             modified.push(FunctionContext {
-                name: sym.name,
-                kind: sym.kind.to_string(),
-                start_line: sym.line,
-                signature: sym.signature,
+                name: symbol.name,
+                kind: symbol.kind.to_string(),
+                start_line,
+                signature: symbol.signature,
             });
         }
     }
@@ -233,44 +246,19 @@ fn find_modified_functions(
     Ok(modified)
 }
 
-fn find_function_end(lines: &[&str], start: usize) -> usize {
-    if start >= lines.len() {
-        return start + 1;
-    }
-
-    let start_line = lines[start];
-    let base_indent = start_line.len() - start_line.trim_start().len();
-
-    let mut brace_count = 0;
-    let mut found_opening = false;
-
-    for (i, line) in lines.iter().enumerate().skip(start) {
-        for c in line.chars() {
-            if c == '{' {
-                brace_count += 1;
-                found_opening = true;
-            } else if c == '}' {
-                brace_count -= 1;
-            }
-        }
-
-        if found_opening && brace_count == 0 {
-            return i + 1;
-        }
-
-        // For Python-style (no braces)
-        if !found_opening && i > start {
-            let current_indent = line.len() - line.trim_start().len();
-            if !line.trim().is_empty() && current_indent <= base_indent {
-                return i;
-            }
+/// Flattens a symbol tree into its function/method nodes, each paired with
+/// its exact `start_line..=end_line` range, recursing into containers
+/// (`impl`/class bodies, `mod` blocks) for nested methods.
+fn collect_function_ranges(nodes: &[SymbolNode], out: &mut Vec<(Symbol, usize, usize)>) {
+    for node in nodes {
+        if matches!(node.symbol.kind, SymbolKind::Function | SymbolKind::Method) {
+            out.push((node.symbol.clone(), node.start_line, node.end_line));
         }
+        collect_function_ranges(&node.children, out);
     }
-
-    lines.len()
 }
 
-fn find_callers(modified_functions: &HashSet<String>) -> Result<Vec<CallerInfo>> {
+fn find_callers(modified_functions: &HashSet<String>, registry: &LanguageRegistry) -> Result<Vec<CallerInfo>> {
     let mut callers = Vec::new();
 
     for func_ref in modified_functions {
@@ -295,26 +283,35 @@ fn find_callers(modified_functions: &HashSet<String>) -> Result<Vec<CallerInfo>>
                 continue;
             }
 
-            if SupportedLanguage::from_path(path).is_none() {
-                continue;
-            }
-
-            if let Ok(content) = std::fs::read_to_string(path) {
-                for (idx, line) in content.lines().enumerate() {
-                    // Simple pattern matching for function calls
-                    let patterns = [
-                        format!("{}(", func_name),
-                        format!("{} (", func_name),
-                        format!(".{}(", func_name),
-                    ];
-
-                    let is_call = patterns.iter().any(|p| line.contains(p));
-                    let is_definition = line.contains("fn ")
-                        || line.contains("def ")
-                        || line.contains("function ");
-
-                    if is_call && !is_definition {
-                        called_from.push(format!("{}:{}", path.display(), idx + 1));
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+
+            // Extension first, falling back to a `#!` shebang, so
+            // extensionless scripts aren't skipped before we even look.
+            let lang = match registry.detect(path, content.lines().next()) {
+                Some(l) => l,
+                None => continue,
+            };
+
+            let query = match treesitter::call_query(&lang) {
+                Some(q) => q,
+                None => continue,
+            };
+
+            let tree = match treesitter::parse_with_language(&content, &lang)? {
+                Some(t) => t,
+                None => continue,
+            };
+
+            let bytes = content.as_bytes();
+            let mut cursor = QueryCursor::new();
+            for m in cursor.matches(&query, tree.root_node(), bytes) {
+                for capture in m.captures {
+                    if capture.node.utf8_text(bytes) == Ok(func_name) {
+                        let row = capture.node.start_position().row;
+                        called_from.push(format!("{}:{}", path.display(), row + 1));
                     }
                 }
             }
@@ -0,0 +1,117 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::analysis::deadline::Deadline;
+use crate::analysis::walker;
+use crate::cache::symbol_index::SymbolIndexCache;
+use crate::commands::config::{self, Config};
+use crate::output::OutputFormat;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FindMatch {
+    pub name: String,
+    pub kind: String,
+    pub path: String,
+    pub line: usize,
+    pub signature: Option<String>,
+    /// Edit distance from the query to this symbol's name (0 = exact).
+    pub distance: u32,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FindResults {
+    pub query: String,
+    pub matches: Vec<FindMatch>,
+    /// `true` if `--timeout` cut the indexing walk short; `matches` then
+    /// only reflects the files visited before the deadline.
+    pub truncated: bool,
+}
+
+impl std::fmt::Display for FindResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for m in &self.matches {
+            let detail = m.signature.as_deref().unwrap_or(&m.name);
+            writeln!(f, "{}:{}: [{}] {}", m.path, m.line, m.kind, detail)?;
+        }
+
+        if self.matches.is_empty() {
+            writeln!(f, "No symbols found matching '{}'", self.query)?;
+        }
+
+        if self.truncated {
+            writeln!(f, "\n(truncated: --timeout reached before indexing finished)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Fuzzy-searches symbol names across the whole project and prints the
+/// best `limit` matches, ranked by descending relevance score (ties broken
+/// by symbol kind — see `SymbolIndex::search`). Backed by the persisted
+/// [`SymbolIndexCache`] in `.ctx/cache`, so only files whose mtime changed
+/// since the last run are reparsed.
+pub fn run(query: &str, limit: usize, deadline: Deadline, format: OutputFormat) -> Result<()> {
+    let config = Config::load().unwrap_or_default();
+    let registry = config::build_registry(&config);
+    let project_root = std::env::current_dir()?;
+
+    let extra_ignores = config.ignore.clone().unwrap_or_default();
+    let file_walker = walker::create_walker_with_extra_ignores(Path::new("."), &extra_ignores).build();
+    let mut truncated = false;
+    let mut files = Vec::new();
+    for entry in file_walker.flatten() {
+        if deadline.is_expired() {
+            truncated = true;
+            break;
+        }
+
+        let path = entry.into_path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+
+    let mut cache = SymbolIndexCache::load(&project_root).unwrap_or_default();
+    let mut index = cache.load_or_build(&files, &registry);
+    let _ = cache.save(&project_root);
+
+    let matches = index
+        .search(query, limit)?
+        .into_iter()
+        .map(|m| FindMatch {
+            name: m.symbol.name,
+            kind: m.symbol.kind.to_string(),
+            path: m.file.to_string_lossy().to_string(),
+            line: m.symbol.line,
+            signature: m.symbol.signature,
+            distance: m.distance,
+        })
+        .collect();
+
+    let results = FindResults {
+        query: query.to_string(),
+        matches,
+        truncated,
+    };
+
+    match format {
+        OutputFormat::Human => println!("{}", results),
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&results)?);
+        }
+        OutputFormat::Compact => {
+            println!("{}", serde_json::to_string(&results)?);
+        }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+use crate::analysis::grammar::{self, GrammarSource};
+use crate::commands::config::Config;
+use crate::output::OutputFormat;
+
+/// Clones (or reuses an existing clone of) every git-sourced grammar
+/// declared in `.ctx/config.toml`'s `[[grammars]]` entries, without
+/// compiling them. Useful for pre-warming the cache (e.g. in CI) ahead of
+/// time, separately from [`build`]'s compile step, and for surfacing a
+/// bad `git`/`rev` early rather than on whatever command first needs it.
+pub fn fetch(format: OutputFormat) -> Result<()> {
+    if format == OutputFormat::Html {
+        anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+    }
+    if matches!(format, OutputFormat::Annotations | OutputFormat::Github) {
+        anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+    }
+
+    let config = Config::load()?;
+    let project_root = std::env::current_dir()?;
+
+    let mut results = Vec::new();
+    for entry in &config.grammars {
+        let spec = entry.to_spec();
+        let outcome = match &spec.source {
+            GrammarSource::Git { url, rev, subpath } => {
+                match grammar::fetch_git_grammar(url, rev, subpath.as_deref(), &project_root) {
+                    Ok(_) => "fetched".to_string(),
+                    Err(e) => format!("error: {:#}", e),
+                }
+            }
+            _ => "not git-sourced, skipped".to_string(),
+        };
+        results.push((spec.name, outcome));
+    }
+
+    print_results(&results, format);
+    Ok(())
+}
+
+/// Compiles (fetching git sources first if needed) and loads every
+/// grammar declared in config, reporting per-grammar success or failure.
+/// This is the same work [`grammar::load_all`] does lazily the first time
+/// a command needs a dynamic grammar, run eagerly and with per-grammar
+/// results instead of a shared warning stream.
+pub fn build(format: OutputFormat) -> Result<()> {
+    if format == OutputFormat::Html {
+        anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+    }
+    if matches!(format, OutputFormat::Annotations | OutputFormat::Github) {
+        anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+    }
+
+    let config = Config::load()?;
+    let project_root = std::env::current_dir()?;
+
+    let results: Vec<(String, String)> = config
+        .grammars
+        .iter()
+        .map(|entry| {
+            let spec = entry.to_spec();
+            let loaded = grammar::load_all(std::slice::from_ref(&spec), &project_root);
+            let outcome = if loaded.is_empty() {
+                "error: see warning above".to_string()
+            } else {
+                "built".to_string()
+            };
+            (spec.name, outcome)
+        })
+        .collect();
+
+    print_results(&results, format);
+    Ok(())
+}
+
+fn print_results(results: &[(String, String)], format: OutputFormat) {
+    match format {
+        OutputFormat::Json | OutputFormat::Compact => {
+            let map: BTreeMap<&str, &str> =
+                results.iter().map(|(name, outcome)| (name.as_str(), outcome.as_str())).collect();
+            if let Ok(json) = serde_json::to_string(&map) {
+                println!("{}", json);
+            }
+        }
+        OutputFormat::Human => {
+            if results.is_empty() {
+                println!("No grammars configured.");
+            }
+            for (name, outcome) in results {
+                println!("{}: {}", name, outcome);
+            }
+        }
+        // Unreachable: `fetch`/`build` reject `Html`/`Annotations`/`Github`
+        // before calling this.
+        OutputFormat::Html | OutputFormat::Annotations | OutputFormat::Github => {}
+    }
+}
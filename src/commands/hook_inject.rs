@@ -2,6 +2,8 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::io::{self, Read};
 
+use crate::analysis::tokenizer;
+
 use super::context_builder;
 
 #[derive(Debug, Deserialize)]
@@ -33,7 +35,14 @@ pub fn run(budget: usize) -> Result<()> {
 
     let input: HookInput = serde_json::from_str(&input_str)?;
 
-    let context = context_builder::build_context(&input.prompt, budget, true)?;
+    let context = context_builder::build_context(
+        &input.prompt,
+        budget,
+        true,
+        context_builder::DEFAULT_CHURN_POOL_SIZE,
+        &[],
+        &tokenizer::HeuristicTokenizer,
+    )?;
 
     let output = HookOutput {
         hook_specific_output: HookSpecificOutput {
@@ -1,7 +1,11 @@
 use anyhow::Result;
+use serde::Serialize;
 use std::io::{self, Read};
 
-use super::context_builder;
+use crate::analysis::tokenizer::{self, Tokenizer};
+
+use super::config::Config;
+use super::context_builder::{self, ContextSource};
 
 /// Specifies how context should be combined with user input in the inject command.
 ///
@@ -13,6 +17,7 @@ use super::context_builder;
 /// * `Prepend` - Context appears before the prompt, separated by `---`
 /// * `Append` - Context appears after the prompt, separated by `---`
 /// * `Wrap` - Context is wrapped in `[CTX-START]`/`[CTX-END]` markers before the prompt
+/// * `Json` - A structured JSON object instead of concatenated text
 ///
 /// # Parsing
 /// Supports case-insensitive parsing from strings via `FromStr`.
@@ -27,6 +32,10 @@ pub enum InjectFormat {
     /// Wraps context in marker tags before the prompt.
     /// Output: `[CTX-START]\n<context>\n[CTX-END]\n<prompt>`
     Wrap,
+    /// Serializes a structured [`InjectOutput`] object to stdout instead of
+    /// concatenating text, so a programmatic consumer doesn't have to
+    /// re-split the output on `---` or marker tags to recover the pieces.
+    Json,
 }
 
 impl std::str::FromStr for InjectFormat {
@@ -37,11 +46,26 @@ impl std::str::FromStr for InjectFormat {
             "prepend" => Ok(InjectFormat::Prepend),
             "append" => Ok(InjectFormat::Append),
             "wrap" => Ok(InjectFormat::Wrap),
-            _ => anyhow::bail!("Invalid format: {}. Use prepend, append, or wrap", s),
+            "json" => Ok(InjectFormat::Json),
+            _ => anyhow::bail!("Invalid format: {}. Use prepend, append, wrap, or json", s),
         }
     }
 }
 
+/// Structured output for `InjectFormat::Json`: the original prompt and
+/// generated context kept as separate fields (rather than concatenated),
+/// plus the budget that was requested, the context's actual estimated
+/// token cost, and a breakdown of which section of the context that cost
+/// came from.
+#[derive(Debug, Serialize)]
+pub struct InjectOutput {
+    pub prompt: String,
+    pub context: String,
+    pub budget: usize,
+    pub estimated_tokens: usize,
+    pub sources: Vec<ContextSource>,
+}
+
 /// Executes the inject command to add project context to a prompt.
 ///
 /// Reads a prompt from stdin, generates relevant project context within
@@ -78,7 +102,32 @@ pub fn run(budget: usize, format: InjectFormat) -> Result<()> {
     let mut prompt = String::new();
     io::stdin().read_to_string(&mut prompt)?;
 
-    let context = context_builder::build_context(&prompt, budget, false)?;
+    let config = Config::load().unwrap_or_default();
+    let excludes = config.ignore.clone().unwrap_or_default();
+
+    if matches!(format, InjectFormat::Json) {
+        let (context, sources) = context_builder::build_context_detailed(
+            &prompt,
+            budget,
+            false,
+            context_builder::DEFAULT_CHURN_POOL_SIZE,
+            &excludes,
+            &tokenizer::HeuristicTokenizer,
+        )?;
+        let estimated_tokens = tokenizer::HeuristicTokenizer.estimate_tokens(&context);
+        let output = InjectOutput { prompt, context, budget, estimated_tokens, sources };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let context = context_builder::build_context(
+        &prompt,
+        budget,
+        false,
+        context_builder::DEFAULT_CHURN_POOL_SIZE,
+        &excludes,
+        &tokenizer::HeuristicTokenizer,
+    )?;
 
     match format {
         InjectFormat::Prepend => {
@@ -97,6 +146,7 @@ pub fn run(budget: usize, format: InjectFormat) -> Result<()> {
             println!("[CTX-END]");
             print!("{}", prompt);
         }
+        InjectFormat::Json => unreachable!("handled above"),
     }
 
     Ok(())
@@ -122,6 +172,10 @@ mod tests {
             InjectFormat::from_str("wrap").unwrap(),
             InjectFormat::Wrap
         ));
+        assert!(matches!(
+            InjectFormat::from_str("json").unwrap(),
+            InjectFormat::Json
+        ));
 
         // Test case insensitivity - uppercase
         assert!(matches!(
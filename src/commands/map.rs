@@ -1,30 +1,88 @@
 use anyhow::Result;
+use rayon::prelude::*;
+use schemars::JsonSchema;
 use serde::Serialize;
-use std::collections::BTreeMap;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
+use crate::analysis::deadline::Deadline;
+use crate::analysis::git;
+use crate::analysis::gitattributes::GitAttributes;
+use crate::analysis::pathspec::PathSpecSet;
 use crate::analysis::symbols;
 use crate::analysis::treesitter::{self, SupportedLanguage};
 use crate::analysis::walker;
-use crate::output::OutputFormat;
+use crate::commands::config::{self, Config};
+use crate::output::{html, OutputFormat};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct ProjectMap {
     pub directories: BTreeMap<String, DirectoryInfo>,
+    /// `true` if `--timeout` cut the walk short; `directories` then covers
+    /// only the portion of the tree visited before the deadline.
+    pub truncated: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct DirectoryInfo {
     pub path: String,
     pub description: Option<String>,
     pub files: Vec<FileInfo>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct FileInfo {
     pub name: String,
     pub language: Option<String>,
     pub symbols: usize,
+    /// One-letter git status marker (`M` modified, `A` staged/added, `?`
+    /// untracked, `D` deleted, `U` conflicted), or `None` when the file is
+    /// unchanged or this isn't a git repository.
+    pub git_status: Option<String>,
+}
+
+/// Builds a path -> one-letter-marker lookup from [`git::GitStatus`], so
+/// each file can be annotated in a single cheap hash lookup during the
+/// parallel fan-out rather than re-running git plumbing per file.
+fn build_status_index(root: &Path) -> HashMap<String, String> {
+    let mut index = HashMap::new();
+
+    let mut repo = match git::find_repo(root) {
+        Ok(repo) => repo,
+        Err(_) => return index,
+    };
+    let status = match git::get_status(&mut repo) {
+        Ok(status) => status,
+        Err(_) => return index,
+    };
+
+    for path in &status.conflicted_files {
+        index.insert(path.clone(), "U".to_string());
+    }
+    for path in &status.deleted_files {
+        index.insert(path.clone(), "D".to_string());
+    }
+    for path in &status.untracked_files {
+        index.entry(path.clone()).or_insert_with(|| "?".to_string());
+    }
+    for path in &status.modified_files {
+        index.entry(path.clone()).or_insert_with(|| "M".to_string());
+    }
+    for path in &status.staged_files {
+        index.entry(path.clone()).or_insert_with(|| "A".to_string());
+    }
+
+    index
+}
+
+/// Cheap, walk-order metadata for one file, collected during the serial
+/// walk so the actual parsing work (language detection, `count_symbols`)
+/// can be fanned out over a thread pool afterward.
+struct FileMeta {
+    entry_path: PathBuf,
+    rel: PathBuf,
+    parent: String,
+    file_name: String,
 }
 
 impl std::fmt::Display for ProjectMap {
@@ -43,24 +101,57 @@ impl std::fmt::Display for ProjectMap {
                     .as_ref()
                     .map(|l| format!(" [{}]", l))
                     .unwrap_or_default();
-                writeln!(f, "  {}{}", file.name, lang_info)?;
+                let status_info = file
+                    .git_status
+                    .as_ref()
+                    .map(|s| format!(" ({})", s))
+                    .unwrap_or_default();
+                writeln!(f, "  {}{}{}", file.name, lang_info, status_info)?;
             }
         }
+        if self.truncated {
+            writeln!(f, "\n(truncated: --timeout reached before the walk finished)")?;
+        }
         Ok(())
     }
 }
 
-pub fn run(path: Option<&str>, depth: Option<usize>, format: OutputFormat) -> Result<()> {
+pub fn run(
+    path: Option<&str>,
+    depth: Option<usize>,
+    include: &[String],
+    exclude: &[String],
+    deadline: Deadline,
+    format: OutputFormat,
+) -> Result<()> {
     let root = path.map(Path::new).unwrap_or(Path::new("."));
     let max_depth = depth.unwrap_or(3);
 
     let mut directories: BTreeMap<String, DirectoryInfo> = BTreeMap::new();
 
-    let file_walker = walker::create_walker(root)
+    let attrs = GitAttributes::load(root);
+    let config = Config::load().unwrap_or_default();
+    let registry = config::build_registry(&config);
+    let pathspec = PathSpecSet::new(include, exclude)?;
+    let status_index = build_status_index(root);
+
+    let extra_ignores = config.ignore.clone().unwrap_or_default();
+    let file_walker = walker::create_walker_with_extra_ignores(root, &extra_ignores)
         .max_depth(Some(max_depth))
         .build();
 
+    // Parsing is CPU-bound and independent per file, so defer the actual
+    // work (language detection + count_symbols) past this serial walk:
+    // collect just the cheap metadata here, then fan it out over threads.
+    let mut file_metas: Vec<FileMeta> = Vec::new();
+    let mut truncated = false;
+
     for entry in file_walker.flatten() {
+        if deadline.is_expired() {
+            truncated = true;
+            break;
+        }
+
         let entry_path = entry.path();
 
         if entry_path.is_dir() {
@@ -90,17 +181,26 @@ pub fn run(path: Option<&str>, depth: Option<usize>, format: OutputFormat) -> Re
                 },
             );
         } else if entry_path.is_file() {
-            let rel_path = entry_path
-                .strip_prefix(root)
-                .unwrap_or(entry_path)
-                .to_string_lossy()
-                .to_string();
+            let rel = entry_path.strip_prefix(root).unwrap_or(entry_path).to_path_buf();
+            let rel_path = rel.to_string_lossy().to_string();
 
             // Skip hidden files
             if rel_path.starts_with('.') || rel_path.contains("/.") {
                 continue;
             }
 
+            // Skip paths linguist marks as generated/vendored/documentation,
+            // same as how GitHub excludes them from language-aware analysis.
+            if attrs.is_excluded(&rel) {
+                continue;
+            }
+
+            // Apply --include/--exclude pathspecs on top of the walker's
+            // own ignore rules, for ad-hoc scoping of this one invocation.
+            if !pathspec.is_empty() && !pathspec.matches(&rel) {
+                continue;
+            }
+
             let parent = entry_path
                 .parent()
                 .and_then(|p| p.strip_prefix(root).ok())
@@ -113,36 +213,61 @@ pub fn run(path: Option<&str>, depth: Option<usize>, format: OutputFormat) -> Re
                 .unwrap_or("")
                 .to_string();
 
-            let lang = SupportedLanguage::from_path(entry_path);
-            let symbol_count = if lang.is_some() {
-                count_symbols(entry_path).unwrap_or(0)
-            } else {
-                0
+            file_metas.push(FileMeta {
+                entry_path: entry_path.to_path_buf(),
+                rel,
+                parent,
+                file_name,
+            });
+        }
+    }
+
+    let file_results: Vec<(String, FileInfo)> = file_metas
+        .par_iter()
+        .map(|meta| {
+            let lang = attrs
+                .forced_language(&meta.rel)
+                .and_then(|name| registry.resolve_name(&name))
+                .or_else(|| registry.resolve_path(&meta.entry_path));
+            let symbol_count = match &lang {
+                Some(l) => count_symbols(&meta.entry_path, l).unwrap_or(0),
+                None => 0,
             };
 
             let file_info = FileInfo {
-                name: file_name,
+                name: meta.file_name.clone(),
                 language: lang.map(|l| l.name().to_string()),
                 symbols: symbol_count,
+                git_status: status_index.get(&meta.rel.to_string_lossy().to_string()).cloned(),
             };
 
-            if let Some(dir) = directories.get_mut(&parent) {
-                dir.files.push(file_info);
-            } else if parent == "." || parent.is_empty() {
-                // Root level files
-                let root_dir = directories
-                    .entry(".".to_string())
-                    .or_insert_with(|| DirectoryInfo {
-                        path: ".".to_string(),
-                        description: None,
-                        files: Vec::new(),
-                    });
-                root_dir.files.push(file_info);
-            }
+            (meta.parent.clone(), file_info)
+        })
+        .collect();
+
+    for (parent, file_info) in file_results {
+        if let Some(dir) = directories.get_mut(&parent) {
+            dir.files.push(file_info);
+        } else if parent == "." || parent.is_empty() {
+            // Root level files
+            let root_dir = directories
+                .entry(".".to_string())
+                .or_insert_with(|| DirectoryInfo {
+                    path: ".".to_string(),
+                    description: None,
+                    files: Vec::new(),
+                });
+            root_dir.files.push(file_info);
         }
     }
 
-    let map = ProjectMap { directories };
+    // The parallel fan-out no longer guarantees walk order, so sort each
+    // directory's files back into a deterministic, repeatable listing.
+    for dir in directories.values_mut() {
+        dir.files.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    let map = ProjectMap { directories, truncated };
 
     match format {
         OutputFormat::Human => println!("{}", map),
@@ -152,11 +277,62 @@ pub fn run(path: Option<&str>, depth: Option<usize>, format: OutputFormat) -> Re
         OutputFormat::Compact => {
             println!("{}", serde_json::to_string(&map)?);
         }
+        OutputFormat::Html => {
+            println!("{}", render_html(&map)?);
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
     }
 
     Ok(())
 }
 
+/// Renders a [`ProjectMap`] as a collapsible directory tree: one
+/// `<details>` per directory (open by default), each listing its files
+/// with language/symbol-count badges and its description rendered through
+/// [`html::markdown_to_html`] rather than plain text.
+fn render_html(map: &ProjectMap) -> Result<String> {
+    let mut body = String::new();
+
+    for dir in map.directories.values() {
+        body.push_str(&format!("<details open><summary>{}/</summary>\n", html::escape(&dir.path)));
+
+        if let Some(desc) = &dir.description {
+            body.push_str(&format!("<div class=\"desc\">{}</div>\n", html::markdown_to_html(desc)));
+        }
+
+        for file in &dir.files {
+            let lang_badge = file
+                .language
+                .as_ref()
+                .map(|l| format!(" <span class=\"badge\">{}</span>", html::escape(l)))
+                .unwrap_or_default();
+            let symbol_badge = if file.symbols > 0 {
+                format!(" <span class=\"badge\">{} symbols</span>", file.symbols)
+            } else {
+                String::new()
+            };
+            let status_badge = file
+                .git_status
+                .as_ref()
+                .map(|s| format!(" <span class=\"badge\">{}</span>", html::escape(s)))
+                .unwrap_or_default();
+            body.push_str(&format!(
+                "<div class=\"file\">{}{}{}{}</div>\n",
+                html::escape(&file.name),
+                lang_badge,
+                symbol_badge,
+                status_badge,
+            ));
+        }
+
+        body.push_str("</details>\n");
+    }
+
+    html::page("Project map", &body)
+}
+
 fn get_directory_description(path: &Path) -> Option<String> {
     // Try to find a README or module-level doc comment
     let readme_names = ["README.md", "README", "readme.md"];
@@ -225,11 +401,10 @@ fn get_file_doc_comment(path: &Path) -> Option<String> {
     None
 }
 
-fn count_symbols(path: &Path) -> Result<usize> {
-    let lang = SupportedLanguage::from_path(path).ok_or_else(|| anyhow::anyhow!("Unsupported language"))?;
+fn count_symbols(path: &Path, lang: &SupportedLanguage) -> Result<usize> {
     let source = std::fs::read_to_string(path)?;
-    let tree = treesitter::parse_file(path, &source)?
+    let tree = treesitter::parse_with_language(&source, lang)?
         .ok_or_else(|| anyhow::anyhow!("Failed to parse"))?;
-    let syms = symbols::extract_symbols(&tree, &source, &lang);
+    let syms = symbols::extract_symbols(&tree, &source, lang);
     Ok(syms.len())
 }
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::analysis::deadline::Deadline;
+use crate::analysis::gitattributes::GitAttributes;
+use crate::analysis::stats;
+use crate::analysis::symbols;
+use crate::analysis::treesitter::{self, SupportedLanguage};
+use crate::analysis::walker;
+use crate::commands::config::{self, Config};
+use crate::output::OutputFormat;
+
+/// Aggregate, flat project metrics suitable for charting over time or
+/// diffing between commits — every field is a stable top-level key (no
+/// positional arrays), so two snapshots can be deep-merged or diffed
+/// without needing to know each other's ordering.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ProjectMetrics {
+    /// RFC 3339 timestamp of when this snapshot was taken.
+    pub generated_at: String,
+    /// Caller-supplied label for this snapshot (e.g. a commit SHA or CI run
+    /// id), for telling snapshots apart once collected over time.
+    pub tag: Option<String>,
+    pub total_files: usize,
+    pub files_by_language: BTreeMap<String, usize>,
+    pub total_symbols: usize,
+    pub symbols_by_kind: BTreeMap<String, usize>,
+    pub directory_count: usize,
+    pub lines_of_code: usize,
+    pub comment_lines: usize,
+    pub blank_lines: usize,
+    /// `true` if `--timeout` cut the walk short; the file, symbol and
+    /// directory counts then cover only the files visited before the
+    /// deadline. The line totals come from a separate walk of the whole
+    /// tree that the deadline does not cut short.
+    pub truncated: bool,
+}
+
+impl std::fmt::Display for ProjectMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Project metrics ({})", self.generated_at)?;
+        if let Some(tag) = &self.tag {
+            writeln!(f, "  tag: {}", tag)?;
+        }
+        writeln!(f, "  files: {} ({} directories)", self.total_files, self.directory_count)?;
+        for (lang, count) in &self.files_by_language {
+            writeln!(f, "    {}: {}", lang, count)?;
+        }
+        writeln!(f, "  symbols: {}", self.total_symbols)?;
+        for (kind, count) in &self.symbols_by_kind {
+            writeln!(f, "    {}: {}", kind, count)?;
+        }
+        writeln!(
+            f,
+            "  lines: {} code / {} comments / {} blank",
+            self.lines_of_code, self.comment_lines, self.blank_lines
+        )?;
+        if self.truncated {
+            writeln!(f, "  (truncated: --timeout reached before the walk finished)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects aggregate numbers about the project: file and symbol counts
+/// broken down by language/kind, directory count, and line-of-code totals.
+/// Unlike [`crate::commands::map::run`], which reports per-file detail,
+/// this is meant to be snapshotted repeatedly (e.g. once per commit) and
+/// compared over time, so every field is a flat, stably-keyed total.
+pub fn run(path: Option<&str>, tag: Option<String>, deadline: Deadline, format: OutputFormat) -> Result<()> {
+    let root = path.map(Path::new).unwrap_or(Path::new("."));
+
+    let attrs = GitAttributes::load(root);
+    let config = Config::load().unwrap_or_default();
+    let registry = config::build_registry(&config);
+
+    let mut total_files = 0usize;
+    let mut files_by_language: BTreeMap<String, usize> = BTreeMap::new();
+    let mut symbols_by_kind: BTreeMap<String, usize> = BTreeMap::new();
+    let mut directories: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut truncated = false;
+
+    let extra_ignores = config.ignore.clone().unwrap_or_default();
+    let file_walker = walker::create_walker_with_extra_ignores(root, &extra_ignores).build();
+    for entry in file_walker.flatten() {
+        if deadline.is_expired() {
+            truncated = true;
+            break;
+        }
+
+        let entry_path = entry.path();
+
+        if entry_path.is_dir() {
+            let rel = entry_path.strip_prefix(root).unwrap_or(entry_path).to_string_lossy().to_string();
+            if !rel.is_empty() {
+                directories.insert(rel);
+            }
+            continue;
+        }
+
+        if !entry_path.is_file() {
+            continue;
+        }
+
+        let rel = entry_path.strip_prefix(root).unwrap_or(entry_path).to_path_buf();
+        if attrs.is_excluded(&rel) {
+            continue;
+        }
+
+        total_files += 1;
+
+        let lang = attrs
+            .forced_language(&rel)
+            .and_then(|name| registry.resolve_name(&name))
+            .or_else(|| registry.resolve_path(entry_path));
+
+        let Some(lang) = lang else { continue };
+        *files_by_language.entry(lang.name().to_string()).or_insert(0) += 1;
+
+        if let Ok(count) = count_symbols_by_kind(entry_path, &lang) {
+            for (kind, n) in count {
+                *symbols_by_kind.entry(kind).or_insert(0) += n;
+            }
+        }
+    }
+
+    let total_symbols = symbols_by_kind.values().sum();
+
+    let project_stats = stats::collect_project_stats(root, &[]);
+    let (lines_of_code, comment_lines, blank_lines) = project_stats
+        .by_language_sorted()
+        .into_iter()
+        .fold((0, 0, 0), |(code, comments, blanks), (_, s)| {
+            (code + s.code, comments + s.comments, blanks + s.blanks)
+        });
+
+    let metrics = ProjectMetrics {
+        generated_at: Utc::now().to_rfc3339(),
+        tag,
+        total_files,
+        files_by_language,
+        total_symbols,
+        symbols_by_kind,
+        directory_count: directories.len(),
+        lines_of_code,
+        comment_lines,
+        blank_lines,
+        truncated,
+    };
+
+    match format {
+        OutputFormat::Human => println!("{}", metrics),
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&metrics)?);
+        }
+        OutputFormat::Compact => {
+            println!("{}", serde_json::to_string(&metrics)?);
+        }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
+    }
+
+    Ok(())
+}
+
+fn count_symbols_by_kind(path: &Path, lang: &SupportedLanguage) -> Result<BTreeMap<String, usize>> {
+    let source = std::fs::read_to_string(path)?;
+    let tree = treesitter::parse_with_language(&source, lang)?
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse"))?;
+    let syms = symbols::extract_symbols(&tree, &source, lang);
+
+    let mut by_kind: BTreeMap<String, usize> = BTreeMap::new();
+    for sym in syms {
+        *by_kind.entry(sym.kind.to_string()).or_insert(0) += 1;
+    }
+    Ok(by_kind)
+}
@@ -0,0 +1,21 @@
+pub mod callers;
+pub mod capabilities;
+pub mod config;
+pub mod context_builder;
+pub mod deps;
+pub mod diff_context;
+pub mod find;
+pub mod grammar;
+pub mod hook_inject;
+pub mod init;
+pub mod inject;
+pub mod map;
+pub mod metrics;
+pub mod projects;
+pub mod related;
+pub mod schema;
+pub mod search;
+pub mod status;
+pub mod summarize;
+pub mod version;
+pub mod watch;
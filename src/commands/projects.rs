@@ -0,0 +1,117 @@
+use anyhow::Result;
+use colored::Colorize;
+use git2::{DiffOptions, Repository};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::analysis::git;
+use crate::analysis::monorepo::ProjectTrie;
+use crate::output::OutputFormat;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ProjectImpact {
+    pub ref_name: String,
+    /// Project root (relative to the repo root, `.` for the repo root
+    /// itself) mapped to the changed files attributed to it.
+    pub affected_projects: BTreeMap<String, Vec<String>>,
+}
+
+impl std::fmt::Display for ProjectImpact {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Projects affected by changes against {}:", self.ref_name.cyan())?;
+
+        if self.affected_projects.is_empty() {
+            writeln!(f, "  (none)")?;
+            return Ok(());
+        }
+
+        for (project, files) in &self.affected_projects {
+            writeln!(f, "\n{} ({} files)", project.bold(), files.len())?;
+            for file in files {
+                writeln!(f, "  {}", file)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reports which logical projects in a monorepo are touched by the changes
+/// against `git_ref`, so a caller (typically CI) can scope work to only the
+/// projects a commit range actually affects. See
+/// [`crate::analysis::monorepo::ProjectTrie`] for how files are attributed
+/// to their nearest enclosing project.
+pub fn run(git_ref: Option<&str>, format: OutputFormat) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let repo = git::find_repo(&cwd)?;
+
+    let ref_name = git_ref.unwrap_or("HEAD");
+    let changed = changed_files(&repo, ref_name)?;
+
+    let repo_root = repo.workdir().unwrap_or(&cwd);
+    let trie = ProjectTrie::build(repo_root);
+
+    let mut affected_projects: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for path in changed {
+        let project = trie.owning_project(std::path::Path::new(&path));
+        affected_projects.entry(project).or_default().push(path);
+    }
+    for files in affected_projects.values_mut() {
+        files.sort();
+    }
+
+    let impact = ProjectImpact {
+        ref_name: ref_name.to_string(),
+        affected_projects,
+    };
+
+    match format {
+        OutputFormat::Human => println!("{}", impact),
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&impact)?);
+        }
+        OutputFormat::Compact => {
+            println!("{}", serde_json::to_string(&impact)?);
+        }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects the paths changed between `ref_name` and the working tree (or
+/// between `ref_name` and `HEAD` when `ref_name` isn't `HEAD`), mirroring
+/// [`crate::commands::diff_context::analyze_diff`]'s diff-to-file-list logic.
+fn changed_files(repo: &Repository, ref_name: &str) -> Result<Vec<String>> {
+    let head = repo.head()?.peel_to_tree()?;
+
+    let mut diff_opts = DiffOptions::new();
+    let diff = if ref_name == "HEAD" {
+        repo.diff_tree_to_workdir_with_index(Some(&head), Some(&mut diff_opts))?
+    } else {
+        let obj = repo.revparse_single(ref_name)?;
+        let tree = obj.peel_to_tree()?;
+        repo.diff_tree_to_tree(Some(&tree), Some(&head), Some(&mut diff_opts))?
+    };
+
+    let mut paths: Vec<String> = Vec::new();
+    diff.foreach(
+        &mut |delta, _| {
+            if let Some(path) = delta.new_file().path() {
+                paths.push(path.to_string_lossy().to_string());
+            }
+            true
+        },
+        None,
+        None,
+        None,
+    )?;
+
+    Ok(paths)
+}
@@ -1,24 +1,29 @@
 use anyhow::Result;
+use schemars::JsonSchema;
 use serde::Serialize;
-use std::collections::HashSet;
-use std::path::Path;
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 
+use crate::analysis::deadline::Deadline;
 use crate::analysis::git;
-use crate::analysis::symbols;
-use crate::analysis::treesitter::{self, SupportedLanguage};
 use crate::analysis::walker;
+use crate::cache::import_index::{relative_key, ImportEdgeRef, ImportIndex};
+use crate::commands::config::{self, Config};
 use crate::output::OutputFormat;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct RelatedFiles {
     pub source: String,
     pub imports: Vec<RelatedFile>,
     pub imported_by: Vec<RelatedFile>,
     pub co_changed: Vec<RelatedFile>,
     pub test_files: Vec<RelatedFile>,
+    /// `true` if `--timeout` cut the file walk for the import index or the
+    /// test-file walk short.
+    pub truncated: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct RelatedFile {
     pub path: String,
     pub reason: String,
@@ -56,21 +61,38 @@ impl std::fmt::Display for RelatedFiles {
             }
         }
 
+        if self.truncated {
+            writeln!(f, "\n(truncated: --timeout reached before the scan finished)")?;
+        }
+
         Ok(())
     }
 }
 
-pub fn run(file_path: &str, format: OutputFormat) -> Result<()> {
+pub fn run(file_path: &str, depth: usize, deadline: Deadline, format: OutputFormat) -> Result<()> {
     let path = Path::new(file_path);
 
     if !path.exists() {
         anyhow::bail!("File does not exist: {}", file_path);
     }
 
-    let imports = find_imports(path)?;
-    let imported_by = find_imported_by(path)?;
+    let depth = depth.max(1);
+    let project_root = std::env::current_dir()?;
+
+    let config = Config::load().unwrap_or_default();
+    let registry = config::build_registry(&config);
+    let extra_ignores = config.ignore.clone().unwrap_or_default();
+
+    let (files, mut truncated) = collect_files(Path::new("."), &extra_ignores, deadline);
+
+    let index = ImportIndex::load_or_build(&project_root, &files, &registry)?;
+    let source_key = relative_key(&project_root, path);
+
+    let imports = import_closure(&index, &source_key, depth, true);
+    let imported_by = import_closure(&index, &source_key, depth, false);
     let co_changed = find_co_changed(path)?;
-    let test_files = find_test_files(path)?;
+    let (test_files, test_walk_truncated) = find_test_files(path, &extra_ignores, deadline)?;
+    truncated |= test_walk_truncated;
 
     let related = RelatedFiles {
         source: file_path.to_string(),
@@ -78,6 +100,7 @@ pub fn run(file_path: &str, format: OutputFormat) -> Result<()> {
         imported_by,
         co_changed,
         test_files,
+        truncated,
     };
 
     match format {
@@ -88,84 +111,88 @@ pub fn run(file_path: &str, format: OutputFormat) -> Result<()> {
         OutputFormat::Compact => {
             println!("{}", serde_json::to_string(&related)?);
         }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
     }
 
     Ok(())
 }
 
-fn find_imports(path: &Path) -> Result<Vec<RelatedFile>> {
+/// Walks the import index breadth-first from `source_key` up to `depth`
+/// hops, following `imports` edges when `forward` is true and
+/// `imported_by` edges otherwise. Each result's `reason` carries the
+/// specifier text for a direct (1-hop) edge, or the hop count for anything
+/// reached transitively, since a single specifier no longer describes a
+/// multi-hop chain.
+fn import_closure(index: &ImportIndex, source_key: &str, depth: usize, forward: bool) -> Vec<RelatedFile> {
+    let mut visited = HashSet::new();
+    visited.insert(source_key.to_string());
+
     let mut related = Vec::new();
+    let mut frontier: VecDeque<String> = VecDeque::from([source_key.to_string()]);
 
-    let lang = match SupportedLanguage::from_path(path) {
-        Some(l) => l,
-        None => return Ok(related),
-    };
+    for hop in 1..=depth {
+        let mut next_frontier = VecDeque::new();
 
-    let content = std::fs::read_to_string(path)?;
-    let tree = match treesitter::parse_file(path, &content)? {
-        Some(t) => t,
-        None => return Ok(related),
-    };
+        for node in &frontier {
+            let edges: &[ImportEdgeRef] = if forward {
+                index.imports_of(node)
+            } else {
+                index.imported_by_of(node)
+            };
+
+            for edge in edges {
+                if !visited.insert(edge.path.clone()) {
+                    continue;
+                }
 
-    let imports = symbols::find_imports(&tree, &content, &lang);
+                let reason = if hop == 1 {
+                    edge.specifier.clone()
+                } else {
+                    let via = if forward { "imports" } else { "imported by" };
+                    format!("{hop} hops away via {via}")
+                };
 
-    for import in imports {
-        // Try to resolve import to a file path
-        if let Some(resolved) = resolve_import(&import, path, &lang) {
-            related.push(RelatedFile {
-                path: resolved,
-                reason: import,
-            });
+                related.push(RelatedFile {
+                    path: edge.path.clone(),
+                    reason,
+                });
+                next_frontier.push_back(edge.path.clone());
+            }
         }
+
+        if next_frontier.is_empty() {
+            break;
+        }
+        frontier = next_frontier;
     }
 
-    Ok(related)
+    related
 }
 
-fn find_imported_by(path: &Path) -> Result<Vec<RelatedFile>> {
-    let mut related = Vec::new();
-    let target_name = path
-        .file_stem()
-        .and_then(|n| n.to_str())
-        .unwrap_or("")
-        .to_string();
-
-    if target_name.is_empty() {
-        return Ok(related);
-    }
-
-    let file_walker = walker::create_walker(Path::new(".")).build();
+/// Walks `root` collecting every file, stopping early if `deadline` expires.
+/// Returns the files gathered so far and whether the walk was cut short.
+fn collect_files(root: &Path, extra_ignores: &[String], deadline: Deadline) -> (Vec<PathBuf>, bool) {
+    let file_walker = walker::create_walker_with_extra_ignores(root, extra_ignores).build();
+    let mut files = Vec::new();
+    let mut truncated = false;
 
     for entry in file_walker.flatten() {
-        let entry_path = entry.path();
-
-        if !entry_path.is_file() || entry_path == path {
-            continue;
+        if deadline.is_expired() {
+            truncated = true;
+            break;
         }
-
-        let lang = match SupportedLanguage::from_path(entry_path) {
-            Some(l) => l,
-            None => continue,
-        };
-
-        if let Ok(content) = std::fs::read_to_string(entry_path) {
-            if let Ok(Some(tree)) = treesitter::parse_file(entry_path, &content) {
-                let imports = symbols::find_imports(&tree, &content, &lang);
-
-                for import in imports {
-                    if import.contains(&target_name) {
-                        related.push(RelatedFile {
-                            path: entry_path.to_string_lossy().to_string(),
-                            reason: import,
-                        });
-                        break;
-                    }
-                }
-            }
+        let path = entry.into_path();
+        if path.is_file() {
+            files.push(path);
         }
     }
 
-    Ok(related)
+    (files, truncated)
 }
 
 fn find_co_changed(path: &Path) -> Result<Vec<RelatedFile>> {
@@ -178,18 +205,18 @@ fn find_co_changed(path: &Path) -> Result<Vec<RelatedFile>> {
         .to_string_lossy()
         .to_string();
 
-    let co_changes = git::get_files_changed_with(&repo, &file_path, 10)?;
+    let coupled = git::get_temporal_coupling(&repo, &file_path, git::DEFAULT_COUPLING_POOL_SIZE, 0.02, 10)?;
 
-    Ok(co_changes
+    Ok(coupled
         .into_iter()
-        .map(|(path, count)| RelatedFile {
-            path,
-            reason: format!("{} commits together", count),
+        .map(|c| RelatedFile {
+            path: c.file,
+            reason: format!("{:.0}% confidence, {:.1}x lift ({} commits together)", c.confidence * 100.0, c.lift, c.co_change_count),
         })
         .collect())
 }
 
-fn find_test_files(path: &Path) -> Result<Vec<RelatedFile>> {
+fn find_test_files(path: &Path, extra_ignores: &[String], deadline: Deadline) -> Result<(Vec<RelatedFile>, bool)> {
     let mut related = Vec::new();
 
     let file_stem = path
@@ -199,7 +226,7 @@ fn find_test_files(path: &Path) -> Result<Vec<RelatedFile>> {
         .to_string();
 
     if file_stem.is_empty() {
-        return Ok(related);
+        return Ok((related, false));
     }
 
     let test_patterns = [
@@ -210,11 +237,17 @@ fn find_test_files(path: &Path) -> Result<Vec<RelatedFile>> {
         format!("{}_spec", file_stem),
     ];
 
-    let file_walker = walker::create_walker(Path::new(".")).build();
+    let file_walker = walker::create_walker_with_extra_ignores(Path::new("."), extra_ignores).build();
 
     let mut seen = HashSet::new();
+    let mut truncated = false;
 
     for entry in file_walker.flatten() {
+        if deadline.is_expired() {
+            truncated = true;
+            break;
+        }
+
         let entry_path = entry.path();
 
         if !entry_path.is_file() {
@@ -253,84 +286,6 @@ fn find_test_files(path: &Path) -> Result<Vec<RelatedFile>> {
         }
     }
 
-    Ok(related)
+    Ok((related, truncated))
 }
 
-fn resolve_import(import: &str, source: &Path, lang: &SupportedLanguage) -> Option<String> {
-    // This is a simplified resolver - production would need more sophisticated path resolution
-    match lang {
-        SupportedLanguage::Rust => {
-            // Extract crate/module name from use statement
-            let parts: Vec<&str> = import
-                .trim_start_matches("use ")
-                .trim_end_matches(';')
-                .split("::")
-                .collect();
-
-            if parts.is_empty() {
-                return None;
-            }
-
-            // Check for local modules
-            let parent = source.parent()?;
-            let module_name = parts.last()?;
-
-            let candidates = [
-                parent.join(format!("{}.rs", module_name)),
-                parent.join(module_name).join("mod.rs"),
-            ];
-
-            for candidate in candidates {
-                if candidate.exists() {
-                    return Some(candidate.to_string_lossy().to_string());
-                }
-            }
-        }
-        SupportedLanguage::Python => {
-            // Extract module path from import statement
-            let path_part = if import.starts_with("from ") {
-                import
-                    .trim_start_matches("from ")
-                    .split(' ')
-                    .next()
-                    .unwrap_or("")
-            } else {
-                import
-                    .trim_start_matches("import ")
-                    .split(' ')
-                    .next()
-                    .unwrap_or("")
-            };
-
-            let file_path = path_part.replace('.', "/") + ".py";
-            let candidate = Path::new(&file_path);
-
-            if candidate.exists() {
-                return Some(file_path);
-            }
-        }
-        SupportedLanguage::JavaScript | SupportedLanguage::TypeScript => {
-            // Extract path from import statement
-            if let Some(start) = import.find(['\'', '"']) {
-                let rest = &import[start + 1..];
-                if let Some(end) = rest.find(['\'', '"']) {
-                    let path_str = &rest[..end];
-
-                    if path_str.starts_with('.') {
-                        let parent = source.parent()?;
-                        let extensions = ["", ".js", ".ts", ".jsx", ".tsx", "/index.js", "/index.ts"];
-
-                        for ext in extensions {
-                            let candidate = parent.join(format!("{}{}", path_str, ext));
-                            if candidate.exists() {
-                                return Some(candidate.to_string_lossy().to_string());
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    None
-}
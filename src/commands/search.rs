@@ -1,25 +1,45 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use grep_matcher::Matcher;
+use grep_regex::RegexMatcherBuilder;
+use grep_searcher::{Searcher, SearcherBuilder, Sink, SinkContext, SinkMatch};
+use schemars::JsonSchema;
 use serde::Serialize;
-use std::path::Path;
+use std::collections::{HashSet, VecDeque};
+use std::io;
+use std::path::{Path, PathBuf};
 
+use crate::analysis::deadline::Deadline;
 use crate::analysis::symbols::{self, SymbolKind};
-use crate::analysis::treesitter::{self, SupportedLanguage};
+use crate::analysis::treesitter;
 use crate::analysis::walker;
+use crate::cache::import_index::{relative_key, ImportIndex};
+use crate::cache::symbol_index::SymbolIndexCache;
+use crate::commands::config::{self, Config};
 use crate::output::OutputFormat;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct SearchResult {
     pub path: String,
     pub line: usize,
     pub column: usize,
     pub text: String,
     pub context: Vec<String>,
+    /// Relevance score from the symbol-ranking fuzzy matcher; `0.0` and
+    /// not printed in human output for text/caller searches, which have
+    /// no notion of relevance ranking.
+    pub score: f32,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct SearchResults {
     pub query: String,
     pub results: Vec<SearchResult>,
+    /// `true` if `--timeout` cut the search short; `results` then covers
+    /// only the files visited before the deadline.
+    pub truncated: bool,
+    /// With `--symbol`, set when `results` is empty: the closest indexed
+    /// symbol name by edit distance, in case `query` was a typo.
+    pub did_you_mean: Option<String>,
 }
 
 impl std::fmt::Display for SearchResults {
@@ -34,6 +54,13 @@ impl std::fmt::Display for SearchResults {
 
         if self.results.is_empty() {
             writeln!(f, "No results found for '{}'", self.query)?;
+            if let Some(suggestion) = &self.did_you_mean {
+                writeln!(f, "Did you mean '{}'?", suggestion)?;
+            }
+        }
+
+        if self.truncated {
+            writeln!(f, "\n(truncated: --timeout reached before the search finished)")?;
         }
 
         Ok(())
@@ -44,20 +71,28 @@ pub fn run(
     query: &str,
     symbol: bool,
     caller: bool,
+    regex: bool,
+    fixed_string: bool,
     context_lines: usize,
+    limit: usize,
+    deadline: Deadline,
     format: OutputFormat,
 ) -> Result<()> {
-    let results = if symbol {
-        search_symbols(query, context_lines)?
+    let (results, truncated, did_you_mean) = if symbol {
+        search_symbols(query, limit, deadline)?
     } else if caller {
-        search_callers(query, context_lines)?
+        let (results, truncated) = search_callers(query, context_lines, deadline)?;
+        (results, truncated, None)
     } else {
-        search_text(query, context_lines)?
+        let (results, truncated) = search_text(query, context_lines, regex, fixed_string, deadline)?;
+        (results, truncated, None)
     };
 
     let search_results = SearchResults {
         query: query.to_string(),
         results,
+        truncated,
+        did_you_mean,
     };
 
     match format {
@@ -68,190 +103,365 @@ pub fn run(
         OutputFormat::Compact => {
             println!("{}", serde_json::to_string(&search_results)?);
         }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations => print_annotations(&search_results),
+        OutputFormat::Github => print_github_annotations(&search_results),
     }
 
     Ok(())
 }
 
-fn search_text(query: &str, context_lines: usize) -> Result<Vec<SearchResult>> {
-    let mut results = Vec::new();
-    let query_lower = query.to_lowercase();
+/// Prints one vimgrep-style `file:line:col: message` line per result, so
+/// the output can be piped straight into an editor's quickfix list (e.g.
+/// `:cexpr system('ctx search --symbol foo --format=annotations')` or a
+/// `.vimrc`/`errorformat` of `%f:%l:%c:\ %m`).
+fn print_annotations(results: &SearchResults) {
+    for result in &results.results {
+        println!("{}:{}:{}: {}", result.path, result.line, result.column, result.text);
+    }
+}
+
+/// Prints each result as a GitHub Actions workflow-command annotation
+/// (`::notice file=PATH,line=N,col=M::TEXT`), so a CI step can surface
+/// search/caller hits as inline PR annotations without any JSON
+/// post-processing. See
+/// <https://docs.github.com/actions/using-workflows/workflow-commands-for-github-actions#setting-a-notice-message>.
+fn print_github_annotations(results: &SearchResults) {
+    for result in &results.results {
+        let message = result.text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A");
+        println!(
+            "::notice file={},line={},col={}::{}",
+            result.path, result.line, result.column, message
+        );
+    }
+}
 
-    let file_walker = walker::create_walker(Path::new(".")).build();
+/// Searches file contents for `query` using the ripgrep engine
+/// (`grep-regex` + `grep-searcher`) rather than a hand-rolled line scan:
+/// `regex` lets `query` be a real regular expression (including inline
+/// flags like `(?i)` and multiline patterns); `fixed_string` forces a
+/// literal match even when `query` contains regex metacharacters,
+/// overriding `regex`. Neither flag set matches `query` literally, same
+/// as this command's previous substring behavior. The searcher's own
+/// binary-file detection replaces the old extension allowlist.
+fn search_text(
+    query: &str,
+    context_lines: usize,
+    regex: bool,
+    fixed_string: bool,
+    deadline: Deadline,
+) -> Result<(Vec<SearchResult>, bool)> {
+    let pattern = if regex && !fixed_string {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+
+    let matcher = RegexMatcherBuilder::new()
+        .case_insensitive(true)
+        .build(&pattern)
+        .with_context(|| format!("Invalid search pattern: {}", query))?;
+
+    let mut searcher_builder = SearcherBuilder::new();
+    searcher_builder.line_number(true);
+    searcher_builder.binary_detection(grep_searcher::BinaryDetection::quit(0));
+    if context_lines > 0 {
+        searcher_builder.before_context(context_lines);
+        searcher_builder.after_context(context_lines);
+    }
+
+    let config = Config::load().unwrap_or_default();
+    let extra_ignores = config.ignore.clone().unwrap_or_default();
+    let file_walker = walker::create_walker_with_extra_ignores(Path::new("."), &extra_ignores).build();
+    let mut results = Vec::new();
+    let mut truncated = false;
 
     for entry in file_walker.flatten() {
-        let path = entry.path();
+        if deadline.is_expired() {
+            truncated = true;
+            break;
+        }
 
+        let path = entry.path();
         if !path.is_file() {
             continue;
         }
 
-        // Skip binary files
-        if is_binary_file(path) {
-            continue;
-        }
+        let mut searcher = searcher_builder.build();
+        let mut sink = ResultSink {
+            path,
+            matcher: &matcher,
+            context_lines,
+            matches: Vec::new(),
+            context: Vec::new(),
+        };
 
-        if let Ok(content) = std::fs::read_to_string(path) {
-            let lines: Vec<&str> = content.lines().collect();
-
-            for (idx, line) in lines.iter().enumerate() {
-                if line.to_lowercase().contains(&query_lower) {
-                    let start = idx.saturating_sub(context_lines);
-                    let end = (idx + context_lines + 1).min(lines.len());
-
-                    let context: Vec<String> = lines[start..end]
-                        .iter()
-                        .enumerate()
-                        .filter(|(i, _)| *i + start != idx)
-                        .map(|(i, l)| format!("{}: {}", start + i + 1, l))
-                        .collect();
-
-                    results.push(SearchResult {
-                        path: path.to_string_lossy().to_string(),
-                        line: idx + 1,
-                        column: line.to_lowercase().find(&query_lower).unwrap_or(0) + 1,
-                        text: line.to_string(),
-                        context,
-                    });
-                }
-            }
-        }
+        // A single unreadable or binary file shouldn't sink the whole search
+        // — the searcher detects and skips binary content on its own.
+        let _ = searcher.search_path(&matcher, path, &mut sink);
+        results.extend(sink.finish());
     }
 
-    Ok(results)
+    Ok((results, truncated))
 }
 
-fn search_symbols(query: &str, _context_lines: usize) -> Result<Vec<SearchResult>> {
-    let mut results = Vec::new();
-    let query_lower = query.to_lowercase();
+/// Drives [`grep_searcher::Searcher`] into [`SearchResult`]s: `matched`
+/// records each hit's line/column/text (column comes from re-running the
+/// matcher against just that line, since `SinkMatch` only carries the
+/// line's byte range, not the match's position within it); `context`
+/// buffers surrounding lines, stitched onto the nearest match afterward by
+/// [`finish`](Self::finish) since context callbacks can arrive before or
+/// after the match they belong to.
+struct ResultSink<'a> {
+    path: &'a Path,
+    matcher: &'a grep_regex::RegexMatcher,
+    context_lines: usize,
+    matches: Vec<SearchResult>,
+    context: Vec<(usize, String)>,
+}
+
+impl<'a> ResultSink<'a> {
+    fn finish(mut self) -> Vec<SearchResult> {
+        for result in &mut self.matches {
+            let line = result.line;
+            result.context = self
+                .context
+                .iter()
+                .filter(|(n, _)| {
+                    *n != line && line.saturating_sub(self.context_lines) <= *n && *n <= line + self.context_lines
+                })
+                .map(|(n, text)| format!("{}: {}", n, text))
+                .collect();
+        }
+        self.matches
+    }
+}
+
+impl<'a> Sink for ResultSink<'a> {
+    type Error = io::Error;
+
+    fn matched(&mut self, _searcher: &Searcher, mat: &SinkMatch<'_>) -> Result<bool, io::Error> {
+        let text = String::from_utf8_lossy(mat.bytes()).trim_end().to_string();
+        let line = mat.line_number().unwrap_or(0) as usize;
+        let column = self
+            .matcher
+            .find(mat.bytes())
+            .ok()
+            .flatten()
+            .map(|m| m.start() + 1)
+            .unwrap_or(1);
+
+        self.matches.push(SearchResult {
+            path: self.path.to_string_lossy().to_string(),
+            line,
+            column,
+            text,
+            context: Vec::new(),
+            score: 0.0,
+        });
+        Ok(true)
+    }
+
+    fn context(&mut self, _searcher: &Searcher, ctx: &SinkContext<'_>) -> Result<bool, io::Error> {
+        let line = ctx.line_number().unwrap_or(0) as usize;
+        let text = String::from_utf8_lossy(ctx.bytes()).trim_end().to_string();
+        self.context.push((line, text));
+        Ok(true)
+    }
+}
 
-    let file_walker = walker::create_walker(Path::new(".")).build();
+/// Fuzzy-searches symbol names via the persisted [`SymbolIndexCache`] (an
+/// `fst::Map` under the hood — see [`crate::analysis::symbol_index`])
+/// instead of walking and reparsing every file on each query; only files
+/// whose mtime changed since the last run get reparsed. Results are
+/// ranked by relevance score (see `score` on each [`SearchResult`]) and
+/// capped at `limit`; if nothing matches, a "did you mean" suggestion is
+/// returned instead.
+fn search_symbols(query: &str, limit: usize, deadline: Deadline) -> Result<(Vec<SearchResult>, bool, Option<String>)> {
+    let config = Config::load().unwrap_or_default();
+    let registry = config::build_registry(&config);
+    let project_root = std::env::current_dir()?;
+
+    let extra_ignores = config.ignore.clone().unwrap_or_default();
+    let file_walker = walker::create_walker_with_extra_ignores(Path::new("."), &extra_ignores).build();
+    let mut truncated = false;
+    let mut files = Vec::new();
 
     for entry in file_walker.flatten() {
-        let path = entry.path();
+        if deadline.is_expired() {
+            truncated = true;
+            break;
+        }
 
-        if !path.is_file() {
-            continue;
+        let path = entry.into_path();
+        if path.is_file() {
+            files.push(path);
         }
+    }
 
-        let lang = match SupportedLanguage::from_path(path) {
-            Some(l) => l,
-            None => continue,
-        };
+    let mut cache = SymbolIndexCache::load(&project_root).unwrap_or_default();
+    let mut index = cache.load_or_build(&files, &registry);
+    let _ = cache.save(&project_root);
 
-        if let Ok(content) = std::fs::read_to_string(path) {
-            if let Ok(Some(tree)) = treesitter::parse_file(path, &content) {
-                let syms = symbols::extract_symbols(&tree, &content, &lang);
-
-                for sym in syms {
-                    if sym.name.to_lowercase().contains(&query_lower) {
-                        let text = sym
-                            .signature
-                            .as_ref()
-                            .unwrap_or(&sym.name)
-                            .to_string();
-
-                        results.push(SearchResult {
-                            path: path.to_string_lossy().to_string(),
-                            line: sym.line,
-                            column: 1,
-                            text: format!("[{}] {}", sym.kind, text),
-                            context: Vec::new(),
-                        });
-                    }
-                }
+    let matches = index.search(query, limit)?;
+    let did_you_mean = if matches.is_empty() {
+        index.suggest(query)
+    } else {
+        None
+    };
+
+    let results = matches
+        .into_iter()
+        .map(|m| {
+            let text = m.symbol.signature.as_ref().unwrap_or(&m.symbol.name).to_string();
+            SearchResult {
+                path: m.file.to_string_lossy().to_string(),
+                line: m.symbol.line,
+                column: 1,
+                text: format!("[{}] {}", m.symbol.kind, text),
+                context: Vec::new(),
+                score: m.score,
             }
-        }
-    }
+        })
+        .collect();
 
-    Ok(results)
+    Ok((results, truncated, did_you_mean))
 }
 
-fn search_callers(function_name: &str, context_lines: usize) -> Result<Vec<SearchResult>> {
-    let mut results = Vec::new();
+/// One file's call graph, kept alongside its content and definition status
+/// so the second pass (filtering + formatting) doesn't need to re-read or
+/// re-parse anything.
+struct ParsedCalls {
+    path: PathBuf,
+    content: String,
+    graph: symbols::CallGraph,
+    defines_target: bool,
+}
 
-    // Simple heuristic: search for function calls
-    // This is a basic implementation - could be enhanced with proper call graph analysis
-    let patterns = [
-        format!("{}(", function_name),
-        format!("{} (", function_name),
-        format!(".{}(", function_name),
-        format!("self.{}(", function_name),
-    ];
+/// Finds call sites for `function_name` using the real AST call graph (see
+/// [`symbols::extract_calls`]) instead of string matching, so comments and
+/// string literals never produce a false positive and the column points at
+/// the callee itself rather than column 1. Calls are matched by callee name
+/// only, so a different function with the same name also counts.
+///
+/// To keep matches relevant in large trees, files are first narrowed to
+/// those that actually define `function_name` or import a file that does
+/// (via the project's [`ImportIndex`]); if no definition is found anywhere
+/// (e.g. the symbol is dynamically typed or re-exported), every file stays
+/// a candidate rather than silently returning nothing.
+fn search_callers(function_name: &str, context_lines: usize, deadline: Deadline) -> Result<(Vec<SearchResult>, bool)> {
+    let config = Config::load().unwrap_or_default();
+    let registry = config::build_registry(&config);
+    let mut results = Vec::new();
+    let mut truncated = false;
 
-    let file_walker = walker::create_walker(Path::new(".")).build();
+    let extra_ignores = config.ignore.clone().unwrap_or_default();
+    let file_walker = walker::create_walker_with_extra_ignores(Path::new("."), &extra_ignores).build();
+    let mut parsed = Vec::new();
+    let mut definition_files = Vec::new();
 
     for entry in file_walker.flatten() {
-        let path = entry.path();
+        if deadline.is_expired() {
+            truncated = true;
+            break;
+        }
 
+        let path = entry.path();
         if !path.is_file() {
             continue;
         }
 
-        let lang = match SupportedLanguage::from_path(path) {
-            Some(l) => l,
-            None => continue,
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let Some(lang) = registry.detect(path, content.lines().next()) else {
+            continue;
+        };
+        // A single unparseable file shouldn't sink the whole search.
+        let Ok(Some(tree)) = treesitter::parse_with_language(&content, &lang) else {
+            continue;
         };
 
-        if let Ok(content) = std::fs::read_to_string(path) {
-            let lines: Vec<&str> = content.lines().collect();
-
-            // Check if this file defines the function (for context, not currently used)
-            let _is_definition_file =
-                if let Ok(Some(tree)) = treesitter::parse_file(path, &content) {
-                    let syms = symbols::extract_symbols(&tree, &content, &lang);
-                    syms.iter().any(|s| {
-                        s.name == function_name
-                            && (s.kind == SymbolKind::Function || s.kind == SymbolKind::Method)
-                    })
-                } else {
-                    false
-                };
-
-            for (idx, line) in lines.iter().enumerate() {
-                let is_call = patterns.iter().any(|p| line.contains(p));
-
-                // Skip definition lines
-                let is_definition = line.contains("fn ")
-                    || line.contains("def ")
-                    || line.contains("function ")
-                    || line.contains("func ");
-
-                if is_call && !is_definition {
-                    let start = idx.saturating_sub(context_lines);
-                    let end = (idx + context_lines + 1).min(lines.len());
-
-                    let context: Vec<String> = lines[start..end]
-                        .iter()
-                        .enumerate()
-                        .filter(|(i, _)| *i + start != idx)
-                        .map(|(i, l)| format!("{}: {}", start + i + 1, l))
-                        .collect();
-
-                    results.push(SearchResult {
-                        path: path.to_string_lossy().to_string(),
-                        line: idx + 1,
-                        column: 1,
-                        text: line.to_string(),
-                        context,
-                    });
+        let syms = symbols::extract_symbols(&tree, &content, &lang);
+        let defines_target = syms.iter().any(|s| {
+            s.name == function_name && (s.kind == SymbolKind::Function || s.kind == SymbolKind::Method)
+        });
+        if defines_target {
+            definition_files.push(path.to_path_buf());
+        }
+
+        let graph = symbols::extract_calls(&tree, &content, &lang);
+        parsed.push(ParsedCalls { path: path.to_path_buf(), content, graph, defines_target });
+    }
+
+    let candidate_keys = if definition_files.is_empty() {
+        None
+    } else {
+        let project_root = std::env::current_dir()?;
+        let all_files: Vec<PathBuf> = parsed.iter().map(|p| p.path.clone()).collect();
+        ImportIndex::load_or_build(&project_root, &all_files, &registry).ok().map(|index| {
+            // A caller can be reached through an arbitrary chain of
+            // re-exports (c.rs imports b.rs imports a.rs), so this has to
+            // walk the full transitive closure of "imported by" edges, not
+            // just one hop — same BFS approach as
+            // `related::import_closure`, just unbounded in depth since
+            // "find every call site" has no natural cutoff.
+            let mut keys: HashSet<String> = HashSet::new();
+            let mut frontier: VecDeque<String> = VecDeque::new();
+            for def in &definition_files {
+                let def_key = relative_key(&project_root, def);
+                if keys.insert(def_key.clone()) {
+                    frontier.push_back(def_key);
                 }
             }
+            while let Some(node) = frontier.pop_front() {
+                for edge in index.imported_by_of(&node) {
+                    if keys.insert(edge.path.clone()) {
+                        frontier.push_back(edge.path.clone());
+                    }
+                }
+            }
+            (project_root, keys)
+        })
+    };
+
+    for file in &parsed {
+        if let Some((project_root, keys)) = &candidate_keys {
+            if !file.defines_target && !keys.contains(&relative_key(project_root, &file.path)) {
+                continue;
+            }
+        }
+
+        let lines: Vec<&str> = file.content.lines().collect();
+        for edge in file.graph.callers_of(function_name) {
+            let Some(line_text) = lines.get(edge.line - 1) else {
+                continue;
+            };
+
+            let start = (edge.line - 1).saturating_sub(context_lines);
+            let end = (edge.line + context_lines).min(lines.len());
+            let context: Vec<String> = lines[start..end]
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i + start != edge.line - 1)
+                .map(|(i, l)| format!("{}: {}", start + i + 1, l))
+                .collect();
+
+            let kind = if edge.is_method { "method call" } else { "call" };
+            results.push(SearchResult {
+                path: file.path.to_string_lossy().to_string(),
+                line: edge.line,
+                column: edge.column,
+                text: format!("[{}] {}", kind, line_text.trim()),
+                context,
+                score: 0.0,
+            });
         }
     }
 
-    Ok(results)
-}
+    results.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
 
-fn is_binary_file(path: &Path) -> bool {
-    let binary_extensions = [
-        "png", "jpg", "jpeg", "gif", "bmp", "ico", "svg", "pdf", "doc", "docx", "xls", "xlsx",
-        "ppt", "pptx", "zip", "tar", "gz", "bz2", "7z", "rar", "exe", "dll", "so", "dylib", "o",
-        "a", "lib", "bin", "dat", "db", "sqlite", "wasm", "class", "pyc", "pyo",
-    ];
-
-    path.extension()
-        .and_then(|e| e.to_str())
-        .map(|e| binary_extensions.contains(&e.to_lowercase().as_str()))
-        .unwrap_or(false)
+    Ok((results, truncated))
 }
@@ -1,12 +1,13 @@
 use anyhow::Result;
 use colored::Colorize;
+use schemars::JsonSchema;
 use serde::Serialize;
 
 use crate::analysis::git;
 use crate::analysis::treesitter;
-use crate::output::OutputFormat;
+use crate::output::{html, OutputFormat};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct ProjectStatus {
     pub project_name: Option<String>,
     pub project_type: Option<String>,
@@ -15,6 +16,12 @@ pub struct ProjectStatus {
     pub staged_count: usize,
     pub modified_count: usize,
     pub untracked_count: usize,
+    pub renamed_count: usize,
+    pub deleted_count: usize,
+    pub conflicted_count: usize,
+    pub ahead: usize,
+    pub behind: usize,
+    pub stash_count: usize,
     pub recent_commits: Vec<git::RecentCommit>,
     pub hot_directories: Vec<git::HotDirectory>,
     pub diff_stats: Option<(usize, usize)>,
@@ -33,10 +40,23 @@ impl std::fmt::Display for ProjectStatus {
 
         // Branch and status
         let status_icon = if self.is_dirty { "*" } else { "" };
-        writeln!(f, "Branch: {}{}", self.branch.cyan(), status_icon)?;
+        let mut divergence = String::new();
+        if self.ahead > 0 {
+            divergence.push_str(&format!(" ⇡{}", self.ahead));
+        }
+        if self.behind > 0 {
+            divergence.push_str(&format!(" ⇣{}", self.behind));
+        }
+        writeln!(f, "Branch: {}{}{}", self.branch.cyan(), status_icon, divergence)?;
 
         // File status
-        if self.staged_count > 0 || self.modified_count > 0 || self.untracked_count > 0 {
+        if self.staged_count > 0
+            || self.modified_count > 0
+            || self.untracked_count > 0
+            || self.renamed_count > 0
+            || self.deleted_count > 0
+            || self.conflicted_count > 0
+        {
             let mut status_parts = Vec::new();
             if self.staged_count > 0 {
                 status_parts.push(format!("{} staged", self.staged_count));
@@ -44,12 +64,26 @@ impl std::fmt::Display for ProjectStatus {
             if self.modified_count > 0 {
                 status_parts.push(format!("{} modified", self.modified_count));
             }
+            if self.renamed_count > 0 {
+                status_parts.push(format!("{} renamed", self.renamed_count));
+            }
+            if self.deleted_count > 0 {
+                status_parts.push(format!("{} deleted", self.deleted_count));
+            }
             if self.untracked_count > 0 {
                 status_parts.push(format!("{} untracked", self.untracked_count));
             }
+            if self.conflicted_count > 0 {
+                status_parts.push(format!("{} conflicted", self.conflicted_count).red().to_string());
+            }
             writeln!(f, "Changes: {}", status_parts.join(", "))?;
         }
 
+        // Stash
+        if self.stash_count > 0 {
+            writeln!(f, "Stash: {} entries", self.stash_count)?;
+        }
+
         // Diff stats
         if let Some((ins, del)) = self.diff_stats {
             if ins > 0 || del > 0 {
@@ -90,11 +124,12 @@ impl std::fmt::Display for ProjectStatus {
 
 pub fn run(format: OutputFormat) -> Result<()> {
     let cwd = std::env::current_dir()?;
-    let repo = git::find_repo(&cwd)?;
+    let mut repo = git::find_repo(&cwd)?;
 
-    let git_status = git::get_status(&repo)?;
-    let recent_commits = git::get_recent_commits(&repo, 5).unwrap_or_default();
-    let hot_directories = git::get_hot_directories(&repo, 7).unwrap_or_default();
+    let git_status = git::get_status(&mut repo)?;
+    let graph = git::CommitGraph::build(&repo, git::DEFAULT_HISTORY_POOL_SIZE).ok();
+    let recent_commits = graph.as_ref().map(|g| git::get_recent_commits(g, 5)).unwrap_or_default();
+    let hot_directories = graph.as_ref().map(|g| git::get_hot_directories(g, 7)).unwrap_or_default();
     let diff_stats = git::get_diff_summary(&repo).ok();
 
     let project_name = treesitter::detect_project_name(&cwd);
@@ -108,6 +143,12 @@ pub fn run(format: OutputFormat) -> Result<()> {
         staged_count: git_status.staged_files.len(),
         modified_count: git_status.modified_files.len(),
         untracked_count: git_status.untracked_files.len(),
+        renamed_count: git_status.renamed_files.len(),
+        deleted_count: git_status.deleted_files.len(),
+        conflicted_count: git_status.conflicted_files.len(),
+        ahead: git_status.ahead,
+        behind: git_status.behind,
+        stash_count: git_status.stash_count,
         recent_commits,
         hot_directories,
         diff_stats,
@@ -121,7 +162,93 @@ pub fn run(format: OutputFormat) -> Result<()> {
         OutputFormat::Compact => {
             println!("{}", serde_json::to_string(&status)?);
         }
+        OutputFormat::Html => {
+            println!("{}", render_html(&status)?);
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
     }
 
     Ok(())
 }
+
+/// Renders a [`ProjectStatus`] as a static HTML page: the same sections as
+/// the `Display` impl (branch/changes, recent commits, hot directories),
+/// without the terminal color codes. The branch line does not show the
+/// ahead/behind counts.
+fn render_html(status: &ProjectStatus) -> Result<String> {
+    let mut body = String::new();
+
+    if let Some(name) = &status.project_name {
+        let ptype = status
+            .project_type
+            .as_ref()
+            .map(|t| format!(" ({})", html::escape(t)))
+            .unwrap_or_default();
+        body.push_str(&format!("<p><strong>{}</strong>{}</p>\n", html::escape(name), ptype));
+    }
+
+    let dirty_marker = if status.is_dirty { " *" } else { "" };
+    body.push_str(&format!("<p>Branch: <code>{}</code>{}</p>\n", html::escape(&status.branch), dirty_marker));
+
+    let mut status_parts = Vec::new();
+    if status.staged_count > 0 {
+        status_parts.push(format!("{} staged", status.staged_count));
+    }
+    if status.modified_count > 0 {
+        status_parts.push(format!("{} modified", status.modified_count));
+    }
+    if status.renamed_count > 0 {
+        status_parts.push(format!("{} renamed", status.renamed_count));
+    }
+    if status.deleted_count > 0 {
+        status_parts.push(format!("{} deleted", status.deleted_count));
+    }
+    if status.untracked_count > 0 {
+        status_parts.push(format!("{} untracked", status.untracked_count));
+    }
+    if status.conflicted_count > 0 {
+        status_parts.push(format!("{} conflicted", status.conflicted_count));
+    }
+    if !status_parts.is_empty() {
+        body.push_str(&format!("<p>Changes: {}</p>\n", html::escape(&status_parts.join(", "))));
+    }
+
+    if status.stash_count > 0 {
+        body.push_str(&format!("<p>Stash: {} entries</p>\n", status.stash_count));
+    }
+
+    if let Some((ins, del)) = status.diff_stats {
+        if ins > 0 || del > 0 {
+            body.push_str(&format!("<p>Diff: +{} -{}</p>\n", ins, del));
+        }
+    }
+
+    if !status.recent_commits.is_empty() {
+        body.push_str("<h2>Recent commits</h2>\n<ul>\n");
+        for commit in status.recent_commits.iter().take(5) {
+            body.push_str(&format!(
+                "<li><code>{}</code> {} <span class=\"badge\">{}</span></li>\n",
+                html::escape(&commit.sha),
+                html::escape(&commit.message),
+                html::escape(&commit.time_ago),
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    if !status.hot_directories.is_empty() {
+        body.push_str("<h2>Hot directories (this week)</h2>\n<ul>\n");
+        for dir in status.hot_directories.iter().take(5) {
+            body.push_str(&format!(
+                "<li>{} ({} commits)</li>\n",
+                html::escape(&dir.path),
+                dir.commit_count,
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    html::page("Project status", &body)
+}
@@ -1,26 +1,143 @@
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::path::Path;
 
-use crate::analysis::symbols::{self, Symbol};
-use crate::analysis::treesitter::{self, SupportedLanguage};
+use crate::analysis::deadline::Deadline;
+use crate::analysis::symbols::{self, Symbol, SymbolKind};
+use crate::analysis::treesitter::{self, LanguageRegistry, SupportedLanguage};
 use crate::analysis::walker;
+use crate::commands::config::{self, Config};
+use crate::commands::context_builder::estimate_tokens;
 use crate::output::OutputFormat;
 
-#[derive(Debug, Serialize)]
+/// A shared pool of remaining token budget, drawn down as symbols are kept
+/// across potentially many files in a directory summary.
+struct TokenBudget {
+    remaining: usize,
+}
+
+impl TokenBudget {
+    fn new(total: usize) -> Self {
+        Self { remaining: total }
+    }
+
+    /// Reserves `cost` tokens if there's room, returning whether it fit.
+    fn take(&mut self, cost: usize) -> bool {
+        if cost <= self.remaining {
+            self.remaining -= cost;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Unconditionally spends `cost` tokens (saturating at zero), for
+    /// content that's always emitted regardless of budget, like imports.
+    fn spend(&mut self, cost: usize) {
+        self.remaining = self.remaining.saturating_sub(cost);
+    }
+}
+
+/// How "safe to drop" a symbol is when the token budget runs out: lower
+/// ranks are kept first. Public/exported, top-level declarations (types,
+/// then functions) outrank private helpers and plain variables.
+fn symbol_rank(symbol: &Symbol) -> u8 {
+    let is_public = symbol
+        .signature
+        .as_deref()
+        .map(|sig| {
+            let sig = sig.trim_start();
+            sig.starts_with("pub ") || sig.starts_with("pub(") || sig.starts_with("export")
+        })
+        .unwrap_or(true);
+
+    let kind_rank = match symbol.kind {
+        SymbolKind::Struct
+        | SymbolKind::Class
+        | SymbolKind::Enum
+        | SymbolKind::Interface
+        | SymbolKind::Trait
+        | SymbolKind::Type => 0,
+        SymbolKind::Function | SymbolKind::Method => 1,
+        SymbolKind::Module | SymbolKind::Const | SymbolKind::Impl => 2,
+        SymbolKind::Variable | SymbolKind::Import => 3,
+        SymbolKind::Field | SymbolKind::Variant => 4,
+    };
+
+    if is_public {
+        kind_rank
+    } else {
+        kind_rank + 10
+    }
+}
+
+/// Renders a symbol the same way [`FileSummary`]'s `Display` impl does, so
+/// its estimated token cost matches what actually ends up in the output.
+fn render_symbol(symbol: &Symbol) -> String {
+    match &symbol.signature {
+        Some(sig) => format!("{}:{} {}", symbol.kind, symbol.line, sig),
+        None => format!("{}:{} {}", symbol.kind, symbol.line, symbol.name),
+    }
+}
+
+/// Greedily keeps the highest-ranked symbols that fit in `budget`, in
+/// original declaration order, and reports how many were dropped.
+fn prune_symbols(symbols: Vec<Symbol>, budget: &mut TokenBudget) -> (Vec<Symbol>, usize) {
+    let mut order: Vec<usize> = (0..symbols.len()).collect();
+    order.sort_by_key(|&i| (symbol_rank(&symbols[i]), i));
+
+    let mut kept = vec![false; symbols.len()];
+    for i in order {
+        if budget.take(estimate_tokens(&render_symbol(&symbols[i]))) {
+            kept[i] = true;
+        }
+    }
+
+    let omitted = kept.iter().filter(|k| !**k).count();
+    let pruned = symbols
+        .into_iter()
+        .zip(kept)
+        .filter_map(|(symbol, keep)| keep.then_some(symbol))
+        .collect();
+
+    (pruned, omitted)
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct FileSummary {
     pub path: String,
     pub language: Option<String>,
     pub lines: usize,
+    /// Lines classified as code (neither blank nor entirely a comment).
+    pub code: usize,
+    /// Lines that are entirely a comment (a trailing `// note` on a code
+    /// line doesn't count — that line is `code`).
+    pub comments: usize,
+    /// Empty or whitespace-only lines.
+    pub blanks: usize,
     pub symbols: Vec<Symbol>,
     pub imports: Vec<String>,
+    /// Estimated tokens this summary costs an LLM to read (symbols + imports).
+    pub estimated_tokens: usize,
+    /// Number of lower-ranked symbols dropped to stay within the configured
+    /// token budget, if any were.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub omitted_symbols: Option<usize>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct DirectorySummary {
     pub path: String,
     pub file_count: usize,
     pub files: Vec<FileSummary>,
+    pub estimated_tokens: usize,
+    pub code: usize,
+    pub comments: usize,
+    pub blanks: usize,
+    /// `true` if `--timeout` cut the directory walk short; `files` then
+    /// covers only the files visited before the deadline.
+    pub truncated: bool,
 }
 
 impl std::fmt::Display for FileSummary {
@@ -31,7 +148,11 @@ impl std::fmt::Display for FileSummary {
             .map(|l| format!(" [{}]", l))
             .unwrap_or_default();
 
-        writeln!(f, "{}{} ({} lines)", self.path, lang_str, self.lines)?;
+        writeln!(
+            f,
+            "{}{} ({} lines: {} code, {} comments, {} blank)",
+            self.path, lang_str, self.lines, self.code, self.comments, self.blanks
+        )?;
 
         if !self.imports.is_empty() {
             writeln!(f, "\nImports:")?;
@@ -43,33 +164,41 @@ impl std::fmt::Display for FileSummary {
         if !self.symbols.is_empty() {
             writeln!(f, "\nSymbols:")?;
             for sym in &self.symbols {
-                if let Some(sig) = &sym.signature {
-                    writeln!(f, "  {}:{} {}", sym.kind, sym.line, sig)?;
-                } else {
-                    writeln!(f, "  {}:{} {}", sym.kind, sym.line, sym.name)?;
-                }
+                writeln!(f, "  {}", render_symbol(sym))?;
             }
         }
 
+        if let Some(omitted) = self.omitted_symbols {
+            writeln!(f, "\n… {} symbols omitted (token budget)", omitted)?;
+        }
+
         Ok(())
     }
 }
 
 impl std::fmt::Display for DirectorySummary {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{} ({} files)", self.path, self.file_count)?;
+        writeln!(
+            f,
+            "{} ({} files, ~{} tokens, {} code / {} comments / {} blank)",
+            self.path, self.file_count, self.estimated_tokens, self.code, self.comments, self.blanks
+        )?;
 
         for file in &self.files {
             writeln!(f)?;
             write!(f, "{}", file)?;
         }
 
+        if self.truncated {
+            writeln!(f, "\n(truncated: --timeout reached before the walk finished)")?;
+        }
+
         Ok(())
     }
 }
 
 /// Result of summarizing a single path (either file or directory)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 #[serde(untagged)]
 pub enum SummaryResult {
     File(FileSummary),
@@ -81,8 +210,12 @@ pub fn run(
     paths: &[String],
     depth: Option<usize>,
     skeleton: bool,
+    deadline: Deadline,
     format: OutputFormat,
 ) -> Result<()> {
+    let config = Config::load().unwrap_or_default();
+    let registry = config::build_registry(&config);
+    let mut budget = TokenBudget::new(config.budget);
     let mut results: Vec<SummaryResult> = Vec::new();
     let mut first = true;
 
@@ -95,14 +228,15 @@ pub fn run(
 
         if target.is_file() {
             if skeleton {
-                let skeleton_result = get_skeleton_result(target)?;
+                let skeleton_result = get_skeleton_result(target, &registry)?;
                 results.push(skeleton_result);
             } else {
-                let summary = summarize_file(target)?;
+                let summary = summarize_file(target, &registry, &mut budget)?;
                 results.push(SummaryResult::File(summary));
             }
         } else {
-            let summary = summarize_directory(target, depth.unwrap_or(1))?;
+            let summary =
+                summarize_directory(target, depth.unwrap_or(1), &registry, &config, &mut budget, deadline)?;
             results.push(SummaryResult::Directory(summary));
         }
     }
@@ -110,6 +244,19 @@ pub fn run(
     // Output based on format
     match format {
         OutputFormat::Human => {
+            if config.languages.is_some() || config.ignore.is_some() {
+                let languages = config
+                    .languages
+                    .as_ref()
+                    .map(|l| l.join(", "))
+                    .unwrap_or_else(|| "all".to_string());
+                let ignore = config
+                    .ignore
+                    .as_ref()
+                    .map(|i| i.join(", "))
+                    .unwrap_or_else(|| "(none)".to_string());
+                println!("Active filters: languages = {}, ignore = {}\n", languages, ignore);
+            }
             for result in &results {
                 if !first {
                     println!("\n{}", "=".repeat(60));
@@ -140,18 +287,25 @@ pub fn run(
                 println!("{}", serde_json::to_string(&results)?);
             }
         }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
     }
 
     Ok(())
 }
 
-fn get_skeleton_result(path: &Path) -> Result<SummaryResult> {
+fn get_skeleton_result(path: &Path, registry: &LanguageRegistry) -> Result<SummaryResult> {
     let source = std::fs::read_to_string(path).context("Failed to read file")?;
-    let lang =
-        SupportedLanguage::from_path(path).ok_or_else(|| anyhow::anyhow!("Unsupported language"))?;
+    let lang = registry
+        .detect(path, source.lines().next())
+        .ok_or_else(|| anyhow::anyhow!("Unsupported language"))?;
 
-    let tree =
-        treesitter::parse_file(path, &source)?.ok_or_else(|| anyhow::anyhow!("Failed to parse"))?;
+    let tree = treesitter::parse_with_language(&source, &lang)?
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse"))?;
 
     let skeleton = symbols::get_skeleton(&tree, &source, &lang);
 
@@ -161,56 +315,147 @@ fn get_skeleton_result(path: &Path) -> Result<SummaryResult> {
     })
 }
 
-fn summarize_file(path: &Path) -> Result<FileSummary> {
+fn summarize_file(
+    path: &Path,
+    registry: &LanguageRegistry,
+    budget: &mut TokenBudget,
+) -> Result<FileSummary> {
     let source = std::fs::read_to_string(path).context("Failed to read file")?;
     let lines = source.lines().count();
 
-    let lang = SupportedLanguage::from_path(path);
-    let (symbols_list, imports) = if let Some(ref l) = lang {
-        if let Some(tree) = treesitter::parse_file(path, &source)? {
+    let lang = registry.detect(path, source.lines().next());
+    let (symbols_list, imports, breakdown) = if let Some(ref l) = lang {
+        if let Some(tree) = treesitter::parse_with_language(&source, l)? {
             let syms = symbols::extract_symbols(&tree, &source, l);
-            let imps = symbols::find_imports(&tree, &source, l);
-            (syms, imps)
+            let imps: Vec<String> = symbols::find_imports(&tree, &source, l)
+                .into_iter()
+                .map(|i| i.raw)
+                .collect();
+            let breakdown = symbols::line_breakdown(&tree, &source);
+            (syms, imps, breakdown)
         } else {
-            (Vec::new(), Vec::new())
+            (Vec::new(), Vec::new(), fallback_line_breakdown(&source))
         }
     } else {
-        (Vec::new(), Vec::new())
+        (Vec::new(), Vec::new(), fallback_line_breakdown(&source))
     };
 
+    let import_tokens: usize = imports.iter().map(|i| estimate_tokens(i)).sum();
+    budget.spend(import_tokens);
+    let (symbols_list, omitted) = prune_symbols(symbols_list, budget);
+    let omitted_symbols = (omitted > 0).then_some(omitted);
+
+    let estimated_tokens = import_tokens
+        + symbols_list
+            .iter()
+            .map(|s| estimate_tokens(&render_symbol(s)))
+            .sum::<usize>();
+
     Ok(FileSummary {
         path: path.to_string_lossy().to_string(),
         language: lang.map(|l| l.name().to_string()),
         lines,
+        code: breakdown.code,
+        comments: breakdown.comments,
+        blanks: breakdown.blanks,
         symbols: symbols_list,
         imports,
+        estimated_tokens,
+        omitted_symbols,
     })
 }
 
-fn summarize_directory(path: &Path, depth: usize) -> Result<DirectorySummary> {
+/// Line breakdown for files with no parse tree (unsupported language):
+/// classify by whitespace alone, since there's no comment-node info.
+fn fallback_line_breakdown(source: &str) -> symbols::LineBreakdown {
+    let mut breakdown = symbols::LineBreakdown::default();
+    for line in source.lines() {
+        if line.trim().is_empty() {
+            breakdown.blanks += 1;
+        } else {
+            breakdown.code += 1;
+        }
+    }
+    breakdown
+}
+
+/// Checks whether `path` should be summarized: it must resolve to a known
+/// language (trying the extension first, then a `#!` shebang for
+/// extensionless files), and, if `config.languages` is set, that language
+/// must be in the allow-list.
+fn passes_filters(path: &Path, registry: &LanguageRegistry, config: &Config) -> bool {
+    let lang = match registry.resolve_path(path) {
+        Some(lang) => Some(lang),
+        None => first_line_of(path)
+            .ok()
+            .flatten()
+            .and_then(|first_line| registry.detect(path, Some(&first_line))),
+    };
+
+    let Some(lang) = lang else {
+        return false;
+    };
+
+    match &config.languages {
+        Some(allowed) => allowed.iter().any(|l| l == lang.name()),
+        None => true,
+    }
+}
+
+fn first_line_of(path: &Path) -> std::io::Result<Option<String>> {
+    use std::io::BufRead;
+    let file = std::fs::File::open(path)?;
+    let mut line = String::new();
+    std::io::BufReader::new(file).read_line(&mut line)?;
+    Ok((!line.is_empty()).then_some(line))
+}
+
+fn summarize_directory(
+    path: &Path,
+    depth: usize,
+    registry: &LanguageRegistry,
+    config: &Config,
+    budget: &mut TokenBudget,
+    deadline: Deadline,
+) -> Result<DirectorySummary> {
     let mut files = Vec::new();
+    let mut truncated = false;
 
-    let file_walker = walker::create_walker(path)
+    let mut extra_ignores = config.ignore.clone().unwrap_or_default();
+    extra_ignores.extend(config::nested_ignore_patterns(path)?);
+    let file_walker = walker::create_walker_with_extra_ignores(path, &extra_ignores)
         .max_depth(Some(depth + 1))
         .build();
 
     for entry in file_walker.flatten() {
+        if deadline.is_expired() {
+            truncated = true;
+            break;
+        }
+
         let entry_path = entry.path();
 
-        if entry_path.is_file() {
-            // Only summarize supported languages
-            if SupportedLanguage::from_path(entry_path).is_some() {
-                if let Ok(summary) = summarize_file(entry_path) {
-                    files.push(summary);
-                }
+        if entry_path.is_file() && passes_filters(entry_path, registry, config) {
+            if let Ok(summary) = summarize_file(entry_path, registry, budget) {
+                files.push(summary);
             }
         }
     }
 
+    let estimated_tokens = files.iter().map(|f| f.estimated_tokens).sum();
+    let code = files.iter().map(|f| f.code).sum();
+    let comments = files.iter().map(|f| f.comments).sum();
+    let blanks = files.iter().map(|f| f.blanks).sum();
+
     Ok(DirectorySummary {
         path: path.to_string_lossy().to_string(),
         file_count: files.len(),
         files,
+        estimated_tokens,
+        code,
+        truncated,
+        comments,
+        blanks,
     })
 }
 
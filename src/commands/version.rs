@@ -1,23 +1,31 @@
 use anyhow::Result;
+use schemars::JsonSchema;
 use serde::Serialize;
 
+use crate::commands::capabilities::{ProtocolVersion, PROTOCOL_VERSION};
+use crate::commands::config::{self, Config};
 use crate::output::OutputFormat;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct VersionInfo {
     pub version: String,
+    /// Wire/output protocol version — see [`crate::commands::capabilities::PROTOCOL_VERSION`]
+    pub protocol_version: ProtocolVersion,
     pub features: Features,
     pub capabilities: Capabilities,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct Features {
     pub languages: Vec<String>,
+    /// Tree-sitter grammars loaded from `.ctx/config.toml` or auto-discovered
+    /// under `grammars/`, on top of the built-in `languages`.
+    pub dynamic_grammars: Vec<String>,
     pub output_formats: Vec<String>,
     pub commands: Vec<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct Capabilities {
     pub tree_sitter: bool,
     pub git_integration: bool,
@@ -27,8 +35,16 @@ pub struct Capabilities {
 impl std::fmt::Display for VersionInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "ctx {}", self.version)?;
+        writeln!(
+            f,
+            "Protocol: {}.{} (revision {})",
+            self.protocol_version.major, self.protocol_version.minor, self.protocol_version.revision
+        )?;
         writeln!(f)?;
         writeln!(f, "Languages: {}", self.features.languages.join(", "))?;
+        if !self.features.dynamic_grammars.is_empty() {
+            writeln!(f, "Dynamic grammars: {}", self.features.dynamic_grammars.join(", "))?;
+        }
         writeln!(f, "Output formats: {}", self.features.output_formats.join(", "))?;
         writeln!(f, "Commands: {}", self.features.commands.join(", "))?;
         writeln!(f)?;
@@ -41,8 +57,12 @@ impl std::fmt::Display for VersionInfo {
 }
 
 pub fn run(format: OutputFormat) -> Result<()> {
+    let config = Config::load().unwrap_or_default();
+    let registry = config::build_registry(&config);
+
     let version_info = VersionInfo {
         version: env!("CARGO_PKG_VERSION").to_string(),
+        protocol_version: PROTOCOL_VERSION,
         features: Features {
             languages: vec![
                 "rust".to_string(),
@@ -50,10 +70,14 @@ pub fn run(format: OutputFormat) -> Result<()> {
                 "javascript".to_string(),
                 "typescript".to_string(),
             ],
+            dynamic_grammars: registry.dynamic_grammar_names(),
             output_formats: vec![
                 "human".to_string(),
                 "json".to_string(),
                 "compact".to_string(),
+                "html".to_string(),
+                "annotations".to_string(),
+                "github".to_string(),
             ],
             commands: vec![
                 "init".to_string(),
@@ -62,12 +86,19 @@ pub fn run(format: OutputFormat) -> Result<()> {
                 "summarize".to_string(),
                 "search".to_string(),
                 "related".to_string(),
+                "find".to_string(),
+                "callers".to_string(),
+                "deps".to_string(),
                 "diff-context".to_string(),
+                "projects".to_string(),
+                "metrics".to_string(),
                 "inject".to_string(),
                 "hook-inject".to_string(),
                 "config".to_string(),
+                "grammar".to_string(),
                 "schema".to_string(),
                 "version".to_string(),
+                "watch".to_string(),
             ],
         },
         capabilities: Capabilities {
@@ -85,6 +116,12 @@ pub fn run(format: OutputFormat) -> Result<()> {
         OutputFormat::Compact => {
             println!("{}", serde_json::to_string(&version_info)?);
         }
+        OutputFormat::Html => {
+            anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+        }
+        OutputFormat::Annotations | OutputFormat::Github => {
+            anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+        }
     }
 
     Ok(())
@@ -0,0 +1,312 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+
+use crate::analysis::symbols::{self, SymbolKind};
+use crate::analysis::treesitter::{self, LanguageRegistry};
+use crate::analysis::walker::{self, should_ignore};
+use crate::cache::summaries::{get_file_mtime, FileSummary, SummaryCache, SymbolSummary};
+use crate::commands::config::{self, Config};
+use crate::output::OutputFormat;
+
+/// One coalesced batch of file-change events.
+#[derive(Debug, Serialize)]
+pub struct ChangeBatch {
+    /// Paths that changed during this batch, relative to the watch root,
+    /// sorted and deduplicated.
+    pub changed_files: Vec<String>,
+    /// Number of files in `changed_files`.
+    pub count: usize,
+}
+
+impl std::fmt::Display for ChangeBatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Changed ({} files):", self.count)?;
+        for path in &self.changed_files {
+            writeln!(f, "  {}", path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs a long-lived watch loop that re-runs context extraction whenever
+/// source files change, so an editor/agent can keep up-to-date context
+/// without manually re-invoking the CLI.
+///
+/// Seeds the tracked-file set via [`walker::create_walker`], then registers
+/// a filesystem notifier on `root` and discards events under `target`,
+/// `node_modules`, `.git`, etc. using the same ignore rules. Raw events are
+/// coalesced: each new event resets a quiet-period timer, and one batch
+/// fires only once `debounce_ms` passes with no new events, so a burst of
+/// saves produces a single deduplicated batch rather than one per write.
+///
+/// Each fired batch also keeps the on-disk [`SummaryCache`] warm: changed
+/// files are reparsed and written back with their fresh mtime, deleted
+/// files are dropped, so a `summarize`/`search --symbol` run started right
+/// after never pays for a cold cache on files this loop already saw.
+///
+/// # Arguments
+/// * `path` - Root directory to watch (default: current directory)
+/// * `debounce_ms` - Quiet period, in milliseconds, to wait for more events
+///   before firing a batch
+/// * `poll` - Use polling instead of native OS file events, for filesystems
+///   (network mounts, some containers) where native watching is unreliable
+/// * `format` - Output format for each emitted batch; `Compact` (one JSON
+///   object per line) is the natural choice for streaming consumers
+///
+/// # Shutdown
+/// Runs until interrupted with Ctrl-C (SIGINT), at which point the watch
+/// loop exits cleanly without emitting a partial batch.
+pub fn run(path: Option<&str>, debounce_ms: u64, poll: bool, format: OutputFormat) -> Result<()> {
+    if format == OutputFormat::Html {
+        anyhow::bail!("the `html` output format is only supported by `map` and `status`");
+    }
+    if matches!(format, OutputFormat::Annotations | OutputFormat::Github) {
+        anyhow::bail!("the `annotations`/`github` output formats are only supported by `search`");
+    }
+
+    let root = path.map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+    let root = root.canonicalize().unwrap_or(root);
+
+    let config = Config::load().unwrap_or_default();
+    let registry = config::build_registry(&config);
+    let extra_ignores = config.ignore.clone().unwrap_or_default();
+    let mut cache = SummaryCache::load(&root).unwrap_or_else(|_| SummaryCache::new());
+
+    // Seed the initial tracked-file set via the normal walker, so events
+    // are judged against the same .gitignore-aware view code analysis uses,
+    // not just the coarser `should_ignore` heuristic.
+    let mut tracked: HashSet<PathBuf> = walker::create_walker_with_extra_ignores(&root, &extra_ignores)
+        .build()
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.path().to_path_buf())
+        .collect();
+
+    let (tx, rx) = channel();
+
+    let mut watcher: Box<dyn Watcher> = if poll {
+        let config = notify::Config::default().with_poll_interval(Duration::from_millis(debounce_ms.max(200)));
+        Box::new(notify::PollWatcher::new(
+            move |res| {
+                let _ = tx.send(res);
+            },
+            config,
+        )?)
+    } else {
+        Box::new(notify::RecommendedWatcher::new(
+            move |res| {
+                let _ = tx.send(res);
+            },
+            notify::Config::default(),
+        )?)
+    };
+
+    watcher.watch(&root, RecursiveMode::Recursive)?;
+
+    let running = Arc::new(AtomicBool::new(true));
+    {
+        let running = Arc::clone(&running);
+        ctrlc::set_handler(move || {
+            running.store(false, Ordering::SeqCst);
+        })?;
+    }
+
+    let debounce = Duration::from_millis(debounce_ms);
+    let mut pending: HashSet<String> = HashSet::new();
+
+    while running.load(Ordering::SeqCst) {
+        match rx.recv_timeout(debounce) {
+            Ok(Ok(event)) => {
+                for changed_path in event.paths {
+                    if !is_relevant(&changed_path, &tracked) {
+                        continue;
+                    }
+                    tracked.insert(changed_path.clone());
+                    let rel = changed_path.strip_prefix(&root).unwrap_or(&changed_path);
+                    pending.insert(rel.to_string_lossy().to_string());
+                }
+            }
+            Ok(Err(_)) => {
+                // Notifier reported an error for this event; skip it and keep watching.
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if !pending.is_empty() {
+                    refresh_cache(&mut cache, &root, &pending, &registry);
+                    let _ = cache.save(&root);
+                    emit_batch(&pending, format);
+                    pending.clear();
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether a raw filesystem event path is worth surfacing: not matching
+/// [`should_ignore`]'s built-in ignore list (`.gitignore` and config ignore
+/// patterns are not consulted here), and either already part of the seeded
+/// tracked-file set or a file that currently exists (a newly created file).
+fn is_relevant(changed_path: &Path, tracked: &HashSet<PathBuf>) -> bool {
+    if should_ignore(changed_path) {
+        return false;
+    }
+
+    tracked.contains(changed_path) || changed_path.is_file()
+}
+
+/// Brings `cache` in line with a batch of root-relative paths: a path that
+/// no longer exists is invalidated; an existing one is reparsed with
+/// tree-sitter and written back with its current mtime. A single
+/// unparseable or unreadable file is skipped rather than dropping the rest
+/// of the batch.
+fn refresh_cache(cache: &mut SummaryCache, root: &Path, pending: &HashSet<String>, registry: &LanguageRegistry) {
+    for rel in pending {
+        let full = root.join(rel);
+
+        if !full.is_file() {
+            cache.invalidate(rel);
+            continue;
+        }
+
+        let Ok(mtime) = get_file_mtime(&full) else {
+            continue;
+        };
+        let Ok(content) = std::fs::read_to_string(&full) else {
+            continue;
+        };
+        let Some(lang) = registry.detect(&full, content.lines().next()) else {
+            continue;
+        };
+        let Ok(Some(tree)) = treesitter::parse_with_language(&content, &lang) else {
+            continue;
+        };
+
+        let syms = symbols::extract_symbols(&tree, &content, &lang);
+        let imports = syms
+            .iter()
+            .filter(|s| s.kind == SymbolKind::Import)
+            .map(|s| s.name.clone())
+            .collect();
+        let symbol_summaries = syms
+            .into_iter()
+            .filter(|s| s.kind != SymbolKind::Import)
+            .map(|s| SymbolSummary {
+                name: s.name,
+                kind: s.kind.to_string(),
+                line: s.line,
+                signature: s.signature,
+            })
+            .collect();
+
+        let summary = FileSummary {
+            symbols: symbol_summaries,
+            imports,
+            lines: content.lines().count(),
+        };
+
+        cache.set(rel.clone(), mtime, summary);
+    }
+}
+
+fn emit_batch(pending: &HashSet<String>, format: OutputFormat) {
+    let mut changed_files: Vec<String> = pending.iter().cloned().collect();
+    changed_files.sort();
+    let batch = ChangeBatch {
+        count: changed_files.len(),
+        changed_files,
+    };
+
+    match format {
+        OutputFormat::Human => println!("{}", batch),
+        OutputFormat::Json => {
+            if let Ok(s) = serde_json::to_string_pretty(&batch) {
+                println!("{}", s);
+            }
+        }
+        OutputFormat::Compact => {
+            if let Ok(s) = serde_json::to_string(&batch) {
+                println!("{}", s);
+            }
+        }
+        // Unreachable: `run` rejects `Html`/`Annotations`/`Github` before
+        // entering the watch loop.
+        OutputFormat::Html | OutputFormat::Annotations | OutputFormat::Github => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_relevant_rejects_ignored_paths() {
+        let tracked = HashSet::new();
+        assert!(!is_relevant(Path::new("target/debug/foo"), &tracked));
+        assert!(!is_relevant(Path::new(".git/HEAD"), &tracked));
+    }
+
+    #[test]
+    fn test_is_relevant_accepts_tracked_or_existing_files() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("main.rs");
+        std::fs::write(&file, "fn main() {}\n").unwrap();
+
+        let tracked = HashSet::new();
+        assert!(is_relevant(&file, &tracked));
+
+        let deleted = dir.path().join("gone.rs");
+        let mut tracked_deleted = HashSet::new();
+        tracked_deleted.insert(deleted.clone());
+        assert!(is_relevant(&deleted, &tracked_deleted));
+        assert!(!is_relevant(&deleted, &HashSet::new()));
+    }
+
+    #[test]
+    fn test_refresh_cache_adds_an_entry_for_a_changed_file() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("main.rs");
+        std::fs::write(&file, "fn main() {}\n").unwrap();
+
+        let registry = LanguageRegistry::new();
+        let mut cache = SummaryCache::new();
+        let mut pending = HashSet::new();
+        pending.insert("main.rs".to_string());
+
+        refresh_cache(&mut cache, dir.path(), &pending, &registry);
+
+        let mtime = get_file_mtime(&file).unwrap();
+        let summary = cache.get("main.rs", mtime).expect("expected a cached summary");
+        assert!(summary.symbols.iter().any(|s| s.name == "main"));
+    }
+
+    #[test]
+    fn test_refresh_cache_invalidates_a_deleted_file() {
+        let dir = TempDir::new().unwrap();
+        let registry = LanguageRegistry::new();
+        let mut cache = SummaryCache::new();
+
+        cache.set(
+            "gone.rs".to_string(),
+            0,
+            FileSummary { symbols: Vec::new(), imports: Vec::new(), lines: 0 },
+        );
+
+        let mut pending = HashSet::new();
+        pending.insert("gone.rs".to_string());
+
+        refresh_cache(&mut cache, dir.path(), &pending, &registry);
+
+        assert!(cache.get("gone.rs", 0).is_none());
+    }
+}
@@ -36,6 +36,10 @@ pub enum CtxError {
     #[error("Timeout after {seconds} seconds")]
     #[serde(rename = "timeout")]
     Timeout { seconds: u64 },
+
+    #[error("Config error in {path}: {message}")]
+    #[serde(rename = "config_error")]
+    ConfigError { path: String, message: String },
 }
 
 /// Exit codes for different error categories
@@ -45,6 +49,7 @@ pub mod exit_codes {
     pub const RUNTIME_ERROR: i32 = 2;   // File not found, parse error
     pub const GIT_ERROR: i32 = 3;       // Git-related errors
     pub const IO_ERROR: i32 = 4;        // IO/serialization errors
+    pub const CONFIG_ERROR: i32 = 5;    // Malformed .ctx/config.* files
 }
 
 impl CtxError {
@@ -58,6 +63,7 @@ impl CtxError {
             CtxError::IoError { .. } => exit_codes::IO_ERROR,
             CtxError::SerializationError { .. } => exit_codes::IO_ERROR,
             CtxError::Timeout { .. } => exit_codes::RUNTIME_ERROR,
+            CtxError::ConfigError { .. } => exit_codes::CONFIG_ERROR,
         }
     }
 }
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::time::Duration;
 
 mod analysis;
 mod cache;
@@ -7,6 +8,7 @@ mod commands;
 mod error;
 mod output;
 
+use analysis::deadline::Deadline;
 use error::{exit_codes, CtxError};
 use output::{print_error, OutputFormat};
 
@@ -15,7 +17,8 @@ use output::{print_error, OutputFormat};
 #[command(about = "Context tool for coding agents")]
 #[command(version)]
 struct Cli {
-    /// Output format
+    /// Output format: human, json, compact, html (map/status only), or
+    /// annotations/github (search only)
     #[arg(long, global = true, default_value = "human")]
     format: String,
 
@@ -27,8 +30,10 @@ struct Cli {
     #[arg(long, global = true)]
     compact: bool,
 
-    /// Timeout in seconds for long-running operations
-    /// NOTE: Reserved for future implementation
+    /// Timeout in seconds for long-running operations. Walker-driven
+    /// commands check this deadline between entries and return whatever
+    /// they've found so far, marked `truncated: true`, instead of running
+    /// unbounded.
     #[arg(long, global = true)]
     timeout: Option<u64>,
 
@@ -60,6 +65,16 @@ enum Commands {
         /// Maximum depth to traverse
         #[arg(short, long)]
         depth: Option<usize>,
+
+        /// Only include paths matching this pathspec (repeatable). Supports
+        /// Git-style magic, e.g. `:(glob)src/**/*.rs` or `:(icase)readme.md`
+        #[arg(long)]
+        include: Vec<String>,
+
+        /// Exclude paths matching this pathspec (repeatable); always wins
+        /// over `--include`
+        #[arg(long)]
+        exclude: Vec<String>,
     },
 
     /// Summarize a file or directory
@@ -90,15 +105,72 @@ enum Commands {
         #[arg(long)]
         caller: bool,
 
+        /// Treat the query as a regular expression (text search only;
+        /// has no effect with --symbol or --caller)
+        #[arg(long)]
+        regex: bool,
+
+        /// Force the query to be matched literally even if it contains
+        /// regex metacharacters, overriding --regex
+        #[arg(long)]
+        fixed_string: bool,
+
         /// Lines of context to show
         #[arg(short = 'C', long, default_value = "2")]
         context: usize,
+
+        /// Maximum number of results to show (only applies with --symbol)
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
     },
 
     /// Find files related to a given file
     Related {
         /// File to find relations for
         file: String,
+
+        /// Follow the import graph transitively up to this many hops
+        /// (default: 1, i.e. direct imports/imported-by only)
+        #[arg(long, default_value = "1")]
+        depth: usize,
+    },
+
+    /// Fuzzy-search symbol names across the whole project
+    Find {
+        /// Symbol name to search for (typos and partial names are okay)
+        query: String,
+
+        /// Maximum number of matches to show
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+    },
+
+    /// Find every call site for a function, using AST analysis
+    Callers {
+        /// Function or method name to find callers of
+        function: String,
+    },
+
+    /// Show the intra-repo module dependency graph and detect cycles
+    Deps,
+
+    /// Show aggregate project metrics (file/symbol counts, LOC) as flat,
+    /// mergeable JSON, suitable for tracking over time
+    Metrics {
+        /// Path to measure (default: current directory)
+        path: Option<String>,
+
+        /// Label for this snapshot (e.g. a commit SHA or CI run id)
+        #[arg(long)]
+        tag: Option<String>,
+    },
+
+    /// Show which logical projects in a monorepo are touched by a set of
+    /// git changes, so CI can scope work to only the affected projects
+    Projects {
+        /// Git ref to diff against (default: uncommitted changes)
+        #[arg(name = "ref")]
+        git_ref: Option<String>,
     },
 
     /// Show diff with expanded context
@@ -110,9 +182,9 @@ enum Commands {
 
     /// Inject context into a prompt (reads stdin)
     Inject {
-        /// Maximum tokens to spend on context
-        #[arg(short, long, default_value = "2000")]
-        budget: usize,
+        /// Maximum tokens to spend on context (default: the configured budget)
+        #[arg(short, long)]
+        budget: Option<usize>,
 
         /// Where to put context: prepend, append, or wrap
         #[arg(short, long, default_value = "prepend")]
@@ -121,9 +193,9 @@ enum Commands {
 
     /// Claude Code hook handler (reads JSON from stdin)
     HookInject {
-        /// Maximum tokens to spend on context
-        #[arg(short, long, default_value = "2000")]
-        budget: usize,
+        /// Maximum tokens to spend on context (default: the configured budget)
+        #[arg(short, long)]
+        budget: Option<usize>,
     },
 
     /// Manage configuration
@@ -132,14 +204,43 @@ enum Commands {
         action: ConfigAction,
     },
 
+    /// Manage runtime-loaded tree-sitter grammars declared in config
+    Grammar {
+        #[command(subcommand)]
+        action: GrammarAction,
+    },
+
     /// Output JSON schema for a command's output format
     Schema {
-        /// Command name to get schema for (status, map, summarize, search, related, diff-context)
+        /// Command name to get schema for (status, map, summarize, search, related, find, callers, deps, diff-context, projects, metrics)
         command: String,
     },
 
     /// Show version and capability information
     Version,
+
+    /// Watch for file changes and re-emit context on each batch
+    Watch {
+        /// Directory to watch (default: current directory)
+        path: Option<String>,
+
+        /// Quiet period in milliseconds to wait for more events before
+        /// firing a batch
+        #[arg(long, default_value = "75")]
+        debounce: u64,
+
+        /// Use polling instead of native OS file events
+        #[arg(long)]
+        poll: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum GrammarAction {
+    /// Clone any git-sourced grammars into the local cache, without compiling them
+    Fetch,
+    /// Compile (fetching git sources first if needed) every configured grammar
+    Build,
 }
 
 #[derive(Subcommand)]
@@ -157,12 +258,62 @@ enum ConfigAction {
         value: String,
     },
     /// List all config values
-    List,
+    List {
+        /// Override the effective budget (highest-priority layer)
+        #[arg(long)]
+        budget: Option<usize>,
+
+        /// Override the effective languages (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        languages: Option<Vec<String>>,
+
+        /// Override the effective ignore patterns (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        ignore: Option<Vec<String>>,
+
+        /// Treat unrecognized config keys as hard errors
+        #[arg(long)]
+        strict: bool,
+    },
+}
+
+/// Splices any configured alias (`.ctx/config.toml`'s `[aliases]` table, e.g.
+/// `rel = "related"`) into the subcommand position of argv before clap ever
+/// sees it, so `ctx rel foo.rs` behaves exactly like `ctx related foo.rs`.
+/// Skips past global flags (including the value-taking `--format`/`--timeout`)
+/// to find that position, and resolves chained/cyclic aliases via
+/// [`commands::config::resolve_alias`].
+fn resolve_argv_aliases(args: Vec<String>) -> Vec<String> {
+    let Ok(config) = commands::config::Config::load() else {
+        return args;
+    };
+    if config.aliases.is_empty() {
+        return args;
+    }
+
+    const VALUE_FLAGS: &[&str] = &["--format", "--timeout"];
+
+    let mut result = args.clone();
+    let mut i = 1;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg.starts_with('-') {
+            i += if VALUE_FLAGS.contains(&arg.as_str()) { 2 } else { 1 };
+            continue;
+        }
+        let resolved = commands::config::resolve_alias(&config, arg);
+        let tokens: Vec<String> = resolved.split_whitespace().map(String::from).collect();
+        result.splice(i..=i, tokens);
+        break;
+    }
+    result
 }
 
 fn main() {
-    // Parse CLI early to get json_errors flag
-    let cli = Cli::parse();
+    // Resolve config-driven aliases before clap parses the subcommand, then
+    // parse CLI early to get json_errors flag
+    let args = resolve_argv_aliases(std::env::args().collect());
+    let cli = Cli::parse_from(args);
     let json_errors = cli.json_errors;
 
     // Handle --capabilities flag (no subcommand needed)
@@ -206,6 +357,8 @@ fn main() {
 }
 
 fn run_with_cli(cli: Cli) -> Result<()> {
+    let deadline = Deadline::new(cli.timeout.map(Duration::from_secs));
+
     let format = if cli.json {
         OutputFormat::Json
     } else if cli.compact {
@@ -214,6 +367,9 @@ fn run_with_cli(cli: Cli) -> Result<()> {
         match cli.format.as_str() {
             "json" => OutputFormat::Json,
             "compact" => OutputFormat::Compact,
+            "html" => OutputFormat::Html,
+            "annotations" => OutputFormat::Annotations,
+            "github" => OutputFormat::Github,
             _ => OutputFormat::Human,
         }
     };
@@ -226,35 +382,61 @@ fn run_with_cli(cli: Cli) -> Result<()> {
         Commands::Status => {
             commands::status::run(format)?;
         }
-        Commands::Map { path, depth } => {
-            commands::map::run(path.as_deref(), depth, format)?;
+        Commands::Map { path, depth, include, exclude } => {
+            commands::map::run(path.as_deref(), depth, &include, &exclude, deadline, format)?;
         }
         Commands::Summarize {
             paths,
             depth,
             skeleton,
         } => {
-            commands::summarize::run(&paths, depth, skeleton, format)?;
+            commands::summarize::run(&paths, depth, skeleton, deadline, format)?;
         }
         Commands::Search {
             query,
             symbol,
             caller,
+            regex,
+            fixed_string,
             context,
+            limit,
         } => {
-            commands::search::run(&query, symbol, caller, context, format)?;
+            commands::search::run(&query, symbol, caller, regex, fixed_string, context, limit, deadline, format)?;
         }
-        Commands::Related { file } => {
-            commands::related::run(&file, format)?;
+        Commands::Related { file, depth } => {
+            commands::related::run(&file, depth, deadline, format)?;
+        }
+        Commands::Find { query, limit } => {
+            commands::find::run(&query, limit, deadline, format)?;
+        }
+        Commands::Callers { function } => {
+            commands::callers::run(&function, deadline, format)?;
+        }
+        Commands::Deps => {
+            commands::deps::run(deadline, format)?;
         }
         Commands::DiffContext { git_ref } => {
             commands::diff_context::run(git_ref.as_deref(), format)?;
         }
+        Commands::Metrics { path, tag } => {
+            commands::metrics::run(path.as_deref(), tag, deadline, format)?;
+        }
+        Commands::Projects { git_ref } => {
+            commands::projects::run(git_ref.as_deref(), format)?;
+        }
         Commands::Inject { budget, format: fmt } => {
             let inject_format = fmt.parse()?;
+            let budget = match budget {
+                Some(b) => b,
+                None => commands::config::Config::load()?.budget,
+            };
             commands::inject::run(budget, inject_format)?;
         }
         Commands::HookInject { budget } => {
+            let budget = match budget {
+                Some(b) => b,
+                None => commands::config::Config::load()?.budget,
+            };
             commands::hook_inject::run(budget)?;
         }
         Commands::Config { action } => match action {
@@ -264,16 +446,33 @@ fn run_with_cli(cli: Cli) -> Result<()> {
             ConfigAction::Set { key, value } => {
                 commands::config::run_set(&key, &value, format)?;
             }
-            ConfigAction::List => {
-                commands::config::run_list(format)?;
+            ConfigAction::List {
+                budget,
+                languages,
+                ignore,
+                strict,
+            } => {
+                let overrides = commands::config::ConfigOverrides {
+                    budget,
+                    languages,
+                    ignore,
+                };
+                commands::config::run_list_with_overrides(&overrides, strict, format)?;
             }
         },
+        Commands::Grammar { action } => match action {
+            GrammarAction::Fetch => commands::grammar::fetch(format)?,
+            GrammarAction::Build => commands::grammar::build(format)?,
+        },
         Commands::Schema { command } => {
             commands::schema::run(&command)?;
         }
         Commands::Version => {
             commands::version::run(format)?;
         }
+        Commands::Watch { path, debounce, poll } => {
+            commands::watch::run(path.as_deref(), debounce, poll, format)?;
+        }
     }
 
     Ok(())
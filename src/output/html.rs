@@ -0,0 +1,57 @@
+//! Rendering helpers for [`super::OutputFormat::Html`]: a shared static
+//! page shell (via `tera`) and a Markdown-to-HTML helper (via
+//! `pulldown-cmark`) that `map` and `status` build their page bodies with.
+
+use anyhow::{Context, Result};
+use pulldown_cmark::{html as md_html, Parser};
+use tera::Tera;
+
+/// Renders a Markdown fragment (e.g. a directory's README-derived
+/// description) to an HTML fragment, so inline formatting renders as HTML
+/// rather than plain text. The input is rendered as given; `map` passes
+/// the same first-line description the other output formats show.
+pub fn markdown_to_html(markdown: &str) -> String {
+    let parser = Parser::new(markdown);
+    let mut html = String::new();
+    md_html::push_html(&mut html, parser);
+    html
+}
+
+/// Escapes text for safe placement inside an HTML element body.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+const PAGE_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{{ title }}</title>
+<style>
+  body { font-family: -apple-system, BlinkMacSystemFont, sans-serif; margin: 2rem; color: #1a1a1a; }
+  h1 { font-size: 1.4rem; }
+  h2 { font-size: 1.1rem; margin-top: 1.5rem; }
+  details { margin-left: 1rem; }
+  summary { cursor: pointer; font-weight: 600; }
+  .file { margin-left: 1.5rem; font-family: monospace; }
+  .badge { display: inline-block; padding: 0 0.4em; margin-left: 0.5em; border-radius: 3px; background: #eee; font-size: 0.8em; color: #444; }
+  .desc { color: #555; font-size: 0.9em; margin: 0.2em 0 0.2em 1rem; }
+</style>
+</head>
+<body>
+<h1>{{ title }}</h1>
+{{ body | safe }}
+</body>
+</html>
+"#;
+
+/// Wraps a pre-rendered HTML `body` fragment in a minimal, self-contained
+/// static page: one shared stylesheet, a `<title>`, nothing else fetched
+/// over the network. Rendered through `Tera::one_off` (no template files
+/// on disk) so the shell is still just a string a caller could override.
+pub fn page(title: &str, body: &str) -> Result<String> {
+    let mut ctx = tera::Context::new();
+    ctx.insert("title", title);
+    ctx.insert("body", body);
+    Tera::one_off(PAGE_TEMPLATE, &ctx, true).context("failed to render HTML page shell")
+}
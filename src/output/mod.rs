@@ -2,6 +2,8 @@ use serde::Serialize;
 
 use crate::error::CtxError;
 
+pub mod html;
+
 /// Specifies the output format for command results.
 ///
 /// This enum controls how data is formatted and displayed to the user.
@@ -12,6 +14,9 @@ use crate::error::CtxError;
 /// * `Human` - Human-readable text format using Display trait (default)
 /// * `Json` - Pretty-printed JSON with indentation
 /// * `Compact` - Minified JSON on a single line
+/// * `Html` - A self-contained static HTML page (only `map` and `status`)
+/// * `Annotations` - vimgrep-style `file:line:col: message` lines (only `search`)
+/// * `Github` - GitHub Actions `::notice ...::` workflow commands (only `search`)
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OutputFormat {
     /// Human-readable text format using the type's Display implementation.
@@ -23,6 +28,18 @@ pub enum OutputFormat {
     /// Minified JSON on a single line without extra whitespace.
     /// Most efficient for programmatic consumption and storage.
     Compact,
+    /// A self-contained static HTML page, for commands that support a
+    /// browsable rendering (currently `map` and `status`). Other commands
+    /// reject it with an error rather than silently falling back.
+    Html,
+    /// One `file:line:col: message` line per result, vimgrep-style, so
+    /// output can be piped straight into an editor's quickfix list.
+    /// Currently only `search` supports this.
+    Annotations,
+    /// GitHub Actions workflow-command annotations
+    /// (`::notice file=PATH,line=N,col=M::TEXT`), for surfacing hits as
+    /// inline PR annotations in CI. Currently only `search` supports this.
+    Github,
 }
 
 impl Default for OutputFormat {
@@ -72,6 +89,12 @@ pub fn print_output_result<T: Serialize + std::fmt::Display>(
             println!("{}", json);
             Ok(())
         }
+        OutputFormat::Html => Err(CtxError::InvalidArguments {
+            message: "html output is not supported by this command".to_string(),
+        }),
+        OutputFormat::Annotations | OutputFormat::Github => Err(CtxError::InvalidArguments {
+            message: "annotation output is not supported by this command".to_string(),
+        }),
     }
 }
 
@@ -101,6 +124,8 @@ pub fn print_output<T: Serialize + std::fmt::Display>(data: &T, format: OutputFo
                 println!("{}", json);
             }
         }
+        OutputFormat::Html => {}
+        OutputFormat::Annotations | OutputFormat::Github => {}
     }
 }
 
@@ -406,6 +406,146 @@ fn test_search_json_output() {
     assert!(parsed.get("results").is_some(), "JSON should contain 'results' field");
 }
 
+// ============================================================================
+// Find Command Tests
+// ============================================================================
+
+#[test]
+fn test_find_basic() {
+    let temp_dir = create_temp_test_directory();
+    init_git_repo(&temp_dir.path().to_path_buf());
+
+    let mut cmd = ctx_cmd();
+    cmd.current_dir(temp_dir.path())
+        .args(["find", "helper"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_find_fuzzy_typo() {
+    let temp_dir = create_temp_test_directory();
+    init_git_repo(&temp_dir.path().to_path_buf());
+
+    let mut cmd = ctx_cmd();
+    // One transposed letter should still match `helper_function` via the
+    // Levenshtein automaton.
+    cmd.current_dir(temp_dir.path())
+        .args(["find", "hleper_function"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_find_json_output() {
+    let temp_dir = create_temp_test_directory();
+    init_git_repo(&temp_dir.path().to_path_buf());
+
+    let mut cmd = ctx_cmd();
+    let output = cmd
+        .current_dir(temp_dir.path())
+        .arg("--json")
+        .args(["find", "TestStruct"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value =
+        serde_json::from_str(&stdout).expect("Find output should be valid JSON");
+
+    assert!(parsed.get("query").is_some(), "JSON should contain 'query' field");
+    assert!(parsed.get("matches").is_some(), "JSON should contain 'matches' field");
+}
+
+// ============================================================================
+// Callers Command Tests
+// ============================================================================
+
+#[test]
+fn test_callers_basic() {
+    let temp_dir = create_temp_test_directory();
+    init_git_repo(&temp_dir.path().to_path_buf());
+
+    let mut cmd = ctx_cmd();
+    cmd.current_dir(temp_dir.path())
+        .args(["callers", "helper_function"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("main"));
+}
+
+#[test]
+fn test_callers_json_output() {
+    let temp_dir = create_temp_test_directory();
+    init_git_repo(&temp_dir.path().to_path_buf());
+
+    let mut cmd = ctx_cmd();
+    let output = cmd
+        .current_dir(temp_dir.path())
+        .arg("--json")
+        .args(["callers", "helper_function"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value =
+        serde_json::from_str(&stdout).expect("Callers output should be valid JSON");
+
+    assert!(parsed.get("function").is_some(), "JSON should contain 'function' field");
+    assert!(parsed.get("callers").is_some(), "JSON should contain 'callers' field");
+}
+
+// ============================================================================
+// Deps Command Tests
+// ============================================================================
+
+#[test]
+fn test_deps_reports_local_rust_dependency() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    init_git_repo(&temp_dir.path().to_path_buf());
+
+    fs::write(
+        temp_dir.path().join("a.rs"),
+        "use crate::b::thing;\n\nfn use_it() {\n    thing();\n}\n",
+    )
+    .expect("Failed to write a.rs");
+    fs::write(temp_dir.path().join("b.rs"), "pub fn thing() {}\n").expect("Failed to write b.rs");
+
+    let mut cmd = ctx_cmd();
+    cmd.current_dir(temp_dir.path())
+        .arg("deps")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("b.rs"));
+}
+
+#[test]
+fn test_deps_json_output() {
+    let temp_dir = create_temp_test_directory();
+    init_git_repo(&temp_dir.path().to_path_buf());
+
+    let mut cmd = ctx_cmd();
+    let output = cmd
+        .current_dir(temp_dir.path())
+        .arg("--json")
+        .arg("deps")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value =
+        serde_json::from_str(&stdout).expect("Deps output should be valid JSON");
+
+    assert!(parsed.get("files").is_some(), "JSON should contain 'files' field");
+    assert!(parsed.get("cycle").is_some(), "JSON should contain 'cycle' field");
+}
+
 // ============================================================================
 // Init Command Tests
 // ============================================================================